@@ -0,0 +1,60 @@
+use tokio_postgres::{query, Client, NoTls};
+
+async fn connect() -> Client {
+    let (client, connection) =
+        tokio_postgres::connect("user=postgres host=localhost port=5433", NoTls)
+            .await
+            .unwrap();
+    tokio::spawn(connection);
+    client
+}
+
+#[tokio::test]
+async fn table_columns() {
+    let client = connect().await;
+
+    let record = query!(
+        "SELECT typname, typlen FROM pg_catalog.pg_type WHERE oid = $1",
+        25u32
+    )
+    .fetch_one(&client)
+    .await
+    .unwrap();
+    assert_eq!(record.typname, "text");
+    assert_eq!(record.typlen, -1);
+}
+
+#[tokio::test]
+async fn transaction() {
+    let mut client = connect().await;
+    let transaction = client.transaction().await.unwrap();
+
+    let name = "alice";
+    let records = query!(
+        "SELECT $1::INT4 + 1 AS \"n!\", $2::TEXT AS name, $3::INT8[] AS ids",
+        &41,
+        name,
+        None::<Vec<i64>>
+    )
+    .fetch_all(&transaction)
+    .await
+    .unwrap();
+    assert_eq!(records[0].n, 42);
+    assert_eq!(records[0].name.as_deref(), Some("alice"));
+    assert_eq!(records[0].ids, None);
+}
+
+#[tokio::test]
+async fn temporaries() {
+    let client = connect().await;
+
+    let name = String::from("alice");
+    let query = query!(
+        "SELECT $1::TEXT AS \"name!\", $2::TEXT AS \"greeting!\"",
+        name.clone(),
+        format!("hello {}", name)
+    );
+    let record = query.fetch_one(&client).await.unwrap();
+    assert_eq!(record.name, name);
+    assert_eq!(record.greeting, "hello alice");
+}
@@ -0,0 +1,98 @@
+use postgres::{query, Client, NoTls};
+use std::time::SystemTime;
+
+fn connect() -> Client {
+    Client::connect("user=postgres host=localhost port=5433", NoTls).unwrap()
+}
+
+#[test]
+fn table_columns() {
+    let mut conn = connect();
+
+    let oid = 23u32;
+    let record = query!(
+        "SELECT typname, typlen FROM pg_catalog.pg_type WHERE oid = $1",
+        oid
+    )
+    .fetch_one(&mut conn)
+    .unwrap();
+    let (name, len): (String, i16) = (record.typname, record.typlen);
+    assert_eq!(name, "int4");
+    assert_eq!(len, 4);
+
+    let record = query!(
+        "SELECT typname, typlen FROM pg_catalog.pg_type WHERE oid = $1",
+        &0u32
+    )
+    .fetch_optional(&mut conn)
+    .unwrap();
+    assert!(record.is_none());
+}
+
+#[test]
+fn expressions() {
+    let mut conn = connect();
+
+    let name = Some("alice");
+    let ids = vec![1i64, 2];
+    let records = query!(
+        "SELECT $1::INT4 + 1 AS \"n!\", $2::TEXT AS name, $3::INT8[] AS ids",
+        1i32,
+        name,
+        ids
+    )
+    .fetch_all(&mut conn)
+    .unwrap();
+    assert_eq!(records.len(), 1);
+    let n: i32 = records[0].n;
+    let name: &Option<String> = &records[0].name;
+    let ids: &Option<Vec<Option<i64>>> = &records[0].ids;
+    assert_eq!(n, 2);
+    assert_eq!(name.as_deref(), Some("alice"));
+    assert_eq!(ids.as_deref(), Some(&[Some(1), Some(2)][..]));
+}
+
+#[test]
+fn temporaries() {
+    let mut conn = connect();
+
+    let name = String::from("alice");
+    let record = query!(
+        "SELECT $1::TEXT AS \"name!\", $2::TEXT AS \"greeting!\", ARRAY[1, NULL] AS \"ids!\"",
+        name.clone(),
+        format!("hello {}", name)
+    )
+    .fetch_one(&mut conn)
+    .unwrap();
+    assert_eq!(record.name, name);
+    assert_eq!(record.greeting, "hello alice");
+    assert_eq!(record.ids, [Some(1), None]);
+}
+
+#[test]
+fn overrides() {
+    let mut conn = connect();
+
+    let record = query!("SELECT now() AS \"now!: std::time::SystemTime\", 1 AS \"one?\"")
+        .fetch_one(&mut conn)
+        .unwrap();
+    let now: SystemTime = record.now;
+    let one: Option<i32> = record.one;
+    assert!(now <= SystemTime::now());
+    assert_eq!(one, Some(1));
+}
+
+#[test]
+fn execute() {
+    let mut conn = connect();
+    let mut transaction = conn.transaction().unwrap();
+
+    let name = String::from("postgres-macros-test");
+    let query = query!("SELECT set_config('application_name', $1, true)", name);
+    assert_eq!(
+        query.sql(),
+        "SELECT set_config('application_name', $1, true)"
+    );
+    assert_eq!(query.params().len(), 1);
+    assert_eq!(query.execute(&mut transaction).unwrap(), 1);
+}
@@ -0,0 +1,4 @@
+#![cfg(test)]
+
+mod async_client;
+mod client;
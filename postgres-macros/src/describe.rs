@@ -0,0 +1,177 @@
+//! Statement metadata, either read from the database or from saved offline files.
+
+use postgres::types::Type;
+use postgres::{Client, Config};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+const OFFLINE_DIR: &str = ".postgres-macros";
+
+/// The metadata of a prepared statement.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Describe {
+    pub query: String,
+    pub params: Vec<TypeInfo>,
+    pub columns: Vec<ColumnInfo>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct TypeInfo {
+    pub oid: u32,
+    pub name: String,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct ColumnInfo {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub type_: TypeInfo,
+    pub not_null: bool,
+}
+
+/// Describes a query.
+///
+/// If `DATABASE_URL` is set and `POSTGRES_MACROS_OFFLINE` is not, the statement is prepared against the database. Its
+/// metadata is then saved to the offline directory of the crate being compiled if `POSTGRES_MACROS_PREPARE` is set.
+/// Otherwise, the saved metadata is used.
+pub fn describe(query: &str) -> Result<Describe, String> {
+    let path = offline_path(query)?;
+
+    match env::var("DATABASE_URL") {
+        Ok(url) if !flag("POSTGRES_MACROS_OFFLINE") => {
+            let describe = describe_online(&url, query)?;
+            // writing to the source tree during ordinary builds would fail for read-only or vendored sources
+            if flag("POSTGRES_MACROS_PREPARE") {
+                save(&path, &describe)?;
+            }
+            Ok(describe)
+        }
+        _ => {
+            let contents = fs::read_to_string(&path).map_err(|e| {
+                format!(
+                    "error reading offline metadata from `{}`: {}; set DATABASE_URL and POSTGRES_MACROS_PREPARE=1 to \
+                     save it from a database",
+                    path.display(),
+                    e
+                )
+            })?;
+            let describe = serde_json::from_str::<Describe>(&contents).map_err(|e| {
+                format!(
+                    "error parsing offline metadata from `{}`: {}",
+                    path.display(),
+                    e
+                )
+            })?;
+            if describe.query != query {
+                return Err(format!(
+                    "offline metadata in `{}` is for a different query",
+                    path.display()
+                ));
+            }
+            Ok(describe)
+        }
+    }
+}
+
+fn flag(name: &str) -> bool {
+    matches!(env::var(name).as_deref(), Ok("1") | Ok("true"))
+}
+
+fn offline_path(query: &str) -> Result<PathBuf, String> {
+    let dir = env::var_os("CARGO_MANIFEST_DIR")
+        .ok_or_else(|| "CARGO_MANIFEST_DIR is not set".to_string())?;
+
+    // FNV-1a, which is stable across compiler versions unlike the standard library's hasher
+    let hash = query.bytes().fold(0xcbf29ce484222325u64, |hash, b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x100000001b3)
+    });
+
+    Ok(PathBuf::from(dir)
+        .join(OFFLINE_DIR)
+        .join(format!("query-{:016x}.json", hash)))
+}
+
+fn save(path: &PathBuf, describe: &Describe) -> Result<(), String> {
+    let mut contents = serde_json::to_string_pretty(describe).map_err(|e| e.to_string())?;
+    contents.push('\n');
+
+    // avoid touching files which are already up to date
+    if fs::read_to_string(path).ok().as_deref() == Some(&*contents) {
+        return Ok(());
+    }
+
+    let result = fs::create_dir_all(path.parent().unwrap()).and_then(|_| fs::write(path, contents));
+    result.map_err(|e| {
+        format!(
+            "error writing offline metadata to `{}`: {}",
+            path.display(),
+            e
+        )
+    })
+}
+
+fn describe_online(url: &str, query: &str) -> Result<Describe, String> {
+    let config = url
+        .parse::<Config>()
+        .map_err(|e| format!("invalid DATABASE_URL: {}", e))?;
+    let mut client =
+        connect(&config).map_err(|e| format!("error connecting to DATABASE_URL: {}", e))?;
+    let statement = client.prepare(query).map_err(|e| e.to_string())?;
+
+    let (table_oids, column_ids) = statement
+        .columns()
+        .iter()
+        .filter_map(|c| Some((c.table_oid()?, c.column_id()?)))
+        .unzip::<_, _, Vec<_>, Vec<_>>();
+    let not_null = client
+        .query(
+            "SELECT attrelid, attnum FROM pg_catalog.pg_attribute \
+             WHERE attnotnull AND (attrelid, attnum) IN (SELECT * FROM unnest($1::OID[], $2::INT2[]))",
+            &[&table_oids, &column_ids],
+        )
+        .map_err(|e| e.to_string())?
+        .iter()
+        .map(|row| (row.get::<_, u32>(0), row.get::<_, i16>(1)))
+        .collect::<Vec<_>>();
+
+    Ok(Describe {
+        query: query.to_string(),
+        params: statement.params().iter().map(type_info).collect(),
+        columns: statement
+            .columns()
+            .iter()
+            .map(|c| ColumnInfo {
+                name: c.name().to_string(),
+                type_: type_info(c.type_()),
+                not_null: match (c.table_oid(), c.column_id()) {
+                    (Some(table_oid), Some(column_id)) => {
+                        not_null.contains(&(table_oid, column_id))
+                    }
+                    _ => false,
+                },
+            })
+            .collect(),
+    })
+}
+
+fn type_info(type_: &Type) -> TypeInfo {
+    TypeInfo {
+        oid: type_.oid(),
+        name: type_.name().to_string(),
+    }
+}
+
+#[cfg(feature = "native-tls")]
+fn connect(config: &Config) -> Result<Client, String> {
+    let connector = native_tls::TlsConnector::new().map_err(|e| e.to_string())?;
+    config
+        .connect(postgres_native_tls::MakeTlsConnector::new(connector))
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(not(feature = "native-tls"))]
+fn connect(config: &Config) -> Result<Client, String> {
+    config.connect(postgres::NoTls).map_err(|e| e.to_string())
+}
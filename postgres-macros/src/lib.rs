@@ -0,0 +1,228 @@
+//! The implementation of the `query!` macros of `tokio-postgres` and `postgres`.
+//!
+//! The macros are used through those crates, but this crate must be a direct dependency of the crate using them. Enable this crate's `native-tls` Cargo feature to connect to
+//! `DATABASE_URL` over TLS.
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2, TokenTree};
+use quote::{format_ident, quote, quote_spanned};
+use std::collections::HashSet;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Error, Expr, Ident, LitStr, Token};
+
+use crate::describe::{ColumnInfo, Describe};
+
+mod describe;
+mod types;
+
+struct QueryInput {
+    krate: TokenStream2,
+    query: LitStr,
+    args: Punctuated<Expr, Token![,]>,
+}
+
+impl Parse for QueryInput {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        // the path to the crate invoking the macro, passed along by its `query!` wrapper
+        let mut krate = TokenStream2::new();
+        while !input.peek(Token![,]) {
+            krate.extend(Some(input.parse::<TokenTree>()?));
+        }
+        input.parse::<Token![,]>()?;
+
+        let query = input.parse()?;
+        let args = if input.is_empty() {
+            Punctuated::new()
+        } else {
+            input.parse::<Token![,]>()?;
+            Punctuated::parse_terminated(input)?
+        };
+
+        Ok(QueryInput { krate, query, args })
+    }
+}
+
+#[doc(hidden)]
+#[proc_macro]
+pub fn query(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input);
+
+    expand_query(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+fn expand_query(input: QueryInput) -> syn::Result<TokenStream2> {
+    let query = input.query.value();
+    let describe = describe::describe(&query).map_err(|e| Error::new(input.query.span(), e))?;
+
+    let krate = &input.krate;
+    let fields = columns(krate, &input.query, &describe)?;
+    let params = params(krate, &input, &describe)?;
+
+    let names = fields.iter().map(|f| &f.name).collect::<Vec<_>>();
+    let types = fields.iter().map(|f| &f.type_).collect::<Vec<_>>();
+    let indices = 0..fields.len();
+    let (args, stmts) = params;
+    let sql = &input.query;
+
+    Ok(quote! {{
+        #[derive(Debug)]
+        #[allow(dead_code)]
+        struct Record {
+            #(pub #names: #types,)*
+        }
+
+        fn __map_row(
+            row: &#krate::Row,
+        ) -> ::std::result::Result<Record, #krate::Error> {
+            ::std::result::Result::Ok(Record {
+                #(#names: row.try_get::<_, #types>(#indices)?,)*
+            })
+        }
+
+        #(#stmts)*
+
+        #krate::macros::Query::new(
+            #sql,
+            ::std::vec![#(
+                ::std::boxed::Box::new(#args)
+                    as ::std::boxed::Box<dyn #krate::types::ToSql + ::std::marker::Sync + '_>
+            ),*],
+            __map_row,
+        )
+    }})
+}
+
+struct Field {
+    name: Ident,
+    type_: TokenStream2,
+}
+
+enum Nullability {
+    Inferred,
+    NotNull,
+    Nullable,
+}
+
+fn columns(krate: &TokenStream2, query: &LitStr, describe: &Describe) -> syn::Result<Vec<Field>> {
+    let mut seen = HashSet::new();
+    describe
+        .columns
+        .iter()
+        .map(|column| {
+            let field = column_field(krate, query, column)?;
+            if !seen.insert(field.name.to_string()) {
+                return Err(Error::new(
+                    query.span(),
+                    format!("duplicate column `{}`", field.name),
+                ));
+            }
+            Ok(field)
+        })
+        .collect()
+}
+
+// column names may be annotated as `name!` (not null), `name?` (nullable), or `name: Type` (overriding the Rust type)
+fn column_field(krate: &TokenStream2, query: &LitStr, column: &ColumnInfo) -> syn::Result<Field> {
+    let (name, override_) = match column.name.find(':') {
+        Some(idx) => (
+            column.name[..idx].trim(),
+            Some(column.name[idx + 1..].trim()),
+        ),
+        None => (&*column.name, None),
+    };
+    let (name, nullability) = if let Some(name) = name.strip_suffix('!') {
+        (name, Nullability::NotNull)
+    } else if let Some(name) = name.strip_suffix('?') {
+        (name, Nullability::Nullable)
+    } else {
+        (name, Nullability::Inferred)
+    };
+
+    let ident = syn::parse_str::<Ident>(name).map_err(|_| {
+        Error::new(
+            query.span(),
+            format!(
+                "column `{}` is not a valid Rust identifier; rename it with `AS`",
+                name
+            ),
+        )
+    })?;
+
+    let type_ = match override_ {
+        Some(type_) => {
+            let type_ = syn::parse_str::<syn::Type>(type_).map_err(|e| {
+                Error::new(
+                    query.span(),
+                    format!("invalid type override for column `{}`: {}", name, e),
+                )
+            })?;
+            quote!(#type_)
+        }
+        None => match types::rust_type(krate, column.type_.oid) {
+            Some(type_) => type_.column,
+            None => {
+                return Err(Error::new(
+                    query.span(),
+                    format!(
+                        "unsupported type `{}` of column `{}`; specify a Rust type with `AS \"{}: Type\"`",
+                        column.type_.name, name, name
+                    ),
+                ))
+            }
+        },
+    };
+
+    let not_null = match nullability {
+        Nullability::Inferred => column.not_null,
+        Nullability::NotNull => true,
+        Nullability::Nullable => false,
+    };
+    let type_ = if not_null {
+        type_
+    } else {
+        quote!(::std::option::Option<#type_>)
+    };
+
+    Ok(Field { name: ident, type_ })
+}
+
+fn params(
+    krate: &TokenStream2,
+    input: &QueryInput,
+    describe: &Describe,
+) -> syn::Result<(Vec<Ident>, Vec<TokenStream2>)> {
+    if input.args.len() != describe.params.len() {
+        return Err(Error::new(
+            input.query.span(),
+            format!(
+                "expected {} parameters but got {}",
+                describe.params.len(),
+                input.args.len()
+            ),
+        ));
+    }
+
+    let mut args = vec![];
+    let mut stmts = vec![];
+    for (i, (expr, param)) in input.args.iter().zip(&describe.params).enumerate() {
+        let arg = format_ident!("__arg{}", i, span = Span::mixed_site());
+        // arguments are moved into the query, so temporaries live as long as it does
+        stmts.push(quote_spanned!(expr.span()=> let #arg = #expr;));
+        // parameters of types without a known Rust equivalent are only checked at runtime
+        if let Some(type_) = types::rust_type(krate, param.oid) {
+            let marker = type_.param;
+            stmts.push(quote_spanned! {expr.span()=>
+                #krate::macros::check_param::<#marker, _>(&#arg);
+            });
+        }
+        args.push(arg);
+    }
+
+    Ok((args, stmts))
+}
@@ -0,0 +1,43 @@
+use postgres::types::{Kind, Type};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// The Rust types corresponding to a Postgres type.
+pub struct RustType {
+    /// The type a column is deserialized into.
+    pub column: TokenStream,
+    /// The marker type parameters are checked against.
+    pub param: TokenStream,
+}
+
+pub fn rust_type(krate: &TokenStream, oid: u32) -> Option<RustType> {
+    let type_ = Type::from_oid(oid)?;
+
+    let (column, param) = match type_ {
+        Type::BOOL => (quote!(bool), quote!(bool)),
+        Type::CHAR => (quote!(i8), quote!(i8)),
+        Type::INT2 => (quote!(i16), quote!(i16)),
+        Type::INT4 => (quote!(i32), quote!(i32)),
+        Type::INT8 => (quote!(i64), quote!(i64)),
+        Type::OID => (quote!(u32), quote!(u32)),
+        Type::FLOAT4 => (quote!(f32), quote!(f32)),
+        Type::FLOAT8 => (quote!(f64), quote!(f64)),
+        Type::TEXT | Type::VARCHAR | Type::BPCHAR | Type::NAME | Type::UNKNOWN => {
+            (quote!(::std::string::String), quote!(str))
+        }
+        Type::BYTEA => (quote!(::std::vec::Vec<u8>), quote!([u8])),
+        _ => match type_.kind() {
+            Kind::Array(member) => {
+                let member = rust_type(krate, member.oid())?;
+                let (column, param) = (member.column, member.param);
+                (
+                    quote!(::std::vec::Vec<::std::option::Option<#column>>),
+                    quote!(#krate::macros::Array<#param>),
+                )
+            }
+            _ => return None,
+        },
+    };
+
+    Some(RustType { column, param })
+}
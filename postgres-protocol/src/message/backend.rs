@@ -31,6 +31,7 @@ pub const PORTAL_SUSPENDED_TAG: u8 = b's';
 pub const PARAMETER_STATUS_TAG: u8 = b'S';
 pub const PARAMETER_DESCRIPTION_TAG: u8 = b't';
 pub const ROW_DESCRIPTION_TAG: u8 = b'T';
+pub const FUNCTION_CALL_RESPONSE_TAG: u8 = b'V';
 pub const READY_FOR_QUERY_TAG: u8 = b'Z';
 
 #[derive(Debug, Copy, Clone)]
@@ -96,6 +97,7 @@ pub enum Message {
     DataRow(DataRowBody),
     EmptyQueryResponse,
     ErrorResponse(ErrorResponseBody),
+    FunctionCallResponse(FunctionCallResponseBody),
     NoData,
     NoticeResponse(NoticeResponseBody),
     NotificationResponse(NotificationResponseBody),
@@ -170,6 +172,22 @@ impl Message {
                 let storage = buf.read_all();
                 Message::ErrorResponse(ErrorResponseBody { storage })
             }
+            FUNCTION_CALL_RESPONSE_TAG => {
+                let len = buf.read_i32::<BigEndian>()?;
+                let value = if len < 0 {
+                    None
+                } else {
+                    let storage = buf.read_all();
+                    if storage.len() != len as usize {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            "invalid function call result length",
+                        ));
+                    }
+                    Some(storage)
+                };
+                Message::FunctionCallResponse(FunctionCallResponseBody { value })
+            }
             COPY_IN_RESPONSE_TAG => {
                 let format = buf.read_u8()?;
                 let len = buf.read_u16::<BigEndian>()?;
@@ -657,6 +675,22 @@ impl<'a> ErrorField<'a> {
     }
 }
 
+pub struct FunctionCallResponseBody {
+    value: Option<Bytes>,
+}
+
+impl FunctionCallResponseBody {
+    #[inline]
+    pub fn value(&self) -> Option<&[u8]> {
+        self.value.as_deref()
+    }
+
+    #[inline]
+    pub fn into_value(self) -> Option<Bytes> {
+        self.value
+    }
+}
+
 pub struct NoticeResponseBody {
     storage: Bytes,
 }
@@ -191,6 +191,43 @@ pub fn execute(portal: &str, max_rows: i32, buf: &mut BytesMut) -> io::Result<()
     })
 }
 
+#[inline]
+pub fn function_call<I, J, F, T>(
+    function: Oid,
+    formats: I,
+    values: J,
+    mut serializer: F,
+    result_format: i16,
+    buf: &mut BytesMut,
+) -> Result<(), BindError>
+where
+    I: IntoIterator<Item = i16>,
+    J: IntoIterator<Item = T>,
+    F: FnMut(T, &mut BytesMut) -> Result<IsNull, Box<dyn Error + marker::Sync + Send>>,
+{
+    buf.put_u8(b'F');
+
+    write_body(buf, |buf| {
+        buf.put_u32(function);
+        write_counted(
+            formats,
+            |f, buf| {
+                buf.put_i16(f);
+                Ok::<_, io::Error>(())
+            },
+            buf,
+        )?;
+        write_counted(
+            values,
+            |v, buf| write_nullable(|buf| serializer(v, buf), buf),
+            buf,
+        )?;
+        buf.put_i16(result_format);
+
+        Ok(())
+    })
+}
+
 #[inline]
 pub fn parse<I>(name: &str, query: &str, param_types: I, buf: &mut BytesMut) -> io::Result<()>
 where
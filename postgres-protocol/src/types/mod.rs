@@ -421,6 +421,60 @@ pub fn time_from_sql(mut buf: &[u8]) -> Result<i64, StdBox<dyn Error + Sync + Se
     Ok(v)
 }
 
+/// Serializes an `INTERVAL` value.
+///
+/// The interval is made up of a number of microseconds, days, and months, each of which may be negative.
+#[inline]
+pub fn interval_to_sql(microseconds: i64, days: i32, months: i32, buf: &mut BytesMut) {
+    buf.put_i64(microseconds);
+    buf.put_i32(days);
+    buf.put_i32(months);
+}
+
+/// Deserializes an `INTERVAL` value.
+#[inline]
+pub fn interval_from_sql(mut buf: &[u8]) -> Result<Interval, StdBox<dyn Error + Sync + Send>> {
+    let microseconds = buf.read_i64::<BigEndian>()?;
+    let days = buf.read_i32::<BigEndian>()?;
+    let months = buf.read_i32::<BigEndian>()?;
+    if !buf.is_empty() {
+        return Err("invalid message length: interval not drained".into());
+    }
+    Ok(Interval {
+        microseconds,
+        days,
+        months,
+    })
+}
+
+/// A Postgres interval.
+#[derive(Copy, Clone)]
+pub struct Interval {
+    microseconds: i64,
+    days: i32,
+    months: i32,
+}
+
+impl Interval {
+    /// Returns the microseconds component of the interval.
+    #[inline]
+    pub fn microseconds(&self) -> i64 {
+        self.microseconds
+    }
+
+    /// Returns the days component of the interval.
+    #[inline]
+    pub fn days(&self) -> i32 {
+        self.days
+    }
+
+    /// Returns the months component of the interval.
+    #[inline]
+    pub fn months(&self) -> i32 {
+        self.months
+    }
+}
+
 /// Serializes a `MACADDR` value.
 #[inline]
 pub fn macaddr_to_sql(v: [u8; 6], buf: &mut BytesMut) {
@@ -977,6 +1031,196 @@ impl<'a> FallibleIterator for PathPoints<'a> {
     }
 }
 
+/// Serializes a Postgres line.
+///
+/// The line is represented by the coefficients of the equation `Ax + By + C = 0`.
+#[inline]
+pub fn line_to_sql(a: f64, b: f64, c: f64, buf: &mut BytesMut) {
+    buf.put_f64(a);
+    buf.put_f64(b);
+    buf.put_f64(c);
+}
+
+/// Deserializes a Postgres line.
+#[inline]
+pub fn line_from_sql(mut buf: &[u8]) -> Result<Line, StdBox<dyn Error + Sync + Send>> {
+    let a = buf.read_f64::<BigEndian>()?;
+    let b = buf.read_f64::<BigEndian>()?;
+    let c = buf.read_f64::<BigEndian>()?;
+    if !buf.is_empty() {
+        return Err("invalid buffer size".into());
+    }
+    Ok(Line { a, b, c })
+}
+
+/// A Postgres line, represented by the coefficients of the equation `Ax + By + C = 0`.
+#[derive(Copy, Clone)]
+pub struct Line {
+    a: f64,
+    b: f64,
+    c: f64,
+}
+
+impl Line {
+    /// Returns the `A` coefficient of the line.
+    #[inline]
+    pub fn a(&self) -> f64 {
+        self.a
+    }
+
+    /// Returns the `B` coefficient of the line.
+    #[inline]
+    pub fn b(&self) -> f64 {
+        self.b
+    }
+
+    /// Returns the `C` coefficient of the line.
+    #[inline]
+    pub fn c(&self) -> f64 {
+        self.c
+    }
+}
+
+/// Serializes a Postgres line segment.
+#[inline]
+pub fn lseg_to_sql(x1: f64, y1: f64, x2: f64, y2: f64, buf: &mut BytesMut) {
+    buf.put_f64(x1);
+    buf.put_f64(y1);
+    buf.put_f64(x2);
+    buf.put_f64(y2);
+}
+
+/// Deserializes a Postgres line segment.
+#[inline]
+pub fn lseg_from_sql(mut buf: &[u8]) -> Result<LineSegment, StdBox<dyn Error + Sync + Send>> {
+    let x1 = buf.read_f64::<BigEndian>()?;
+    let y1 = buf.read_f64::<BigEndian>()?;
+    let x2 = buf.read_f64::<BigEndian>()?;
+    let y2 = buf.read_f64::<BigEndian>()?;
+    if !buf.is_empty() {
+        return Err("invalid buffer size".into());
+    }
+    Ok(LineSegment {
+        start: Point { x: x1, y: y1 },
+        end: Point { x: x2, y: y2 },
+    })
+}
+
+/// A Postgres line segment.
+#[derive(Copy, Clone)]
+pub struct LineSegment {
+    start: Point,
+    end: Point,
+}
+
+impl LineSegment {
+    /// Returns the start point of the line segment.
+    #[inline]
+    pub fn start(&self) -> Point {
+        self.start
+    }
+
+    /// Returns the end point of the line segment.
+    #[inline]
+    pub fn end(&self) -> Point {
+        self.end
+    }
+}
+
+/// Serializes a Postgres polygon.
+#[inline]
+pub fn polygon_to_sql<I>(
+    points: I,
+    buf: &mut BytesMut,
+) -> Result<(), StdBox<dyn Error + Sync + Send>>
+where
+    I: IntoIterator<Item = (f64, f64)>,
+{
+    let points_idx = buf.len();
+    buf.put_i32(0);
+
+    let mut num_points = 0;
+    for (x, y) in points {
+        num_points += 1;
+        buf.put_f64(x);
+        buf.put_f64(y);
+    }
+
+    let num_points = i32::from_usize(num_points)?;
+    BigEndian::write_i32(&mut buf[points_idx..], num_points);
+
+    Ok(())
+}
+
+/// Deserializes a Postgres polygon.
+#[inline]
+pub fn polygon_from_sql(mut buf: &[u8]) -> Result<Polygon<'_>, StdBox<dyn Error + Sync + Send>> {
+    let points = buf.read_i32::<BigEndian>()?;
+
+    Ok(Polygon { points, buf })
+}
+
+/// A Postgres polygon.
+pub struct Polygon<'a> {
+    points: i32,
+    buf: &'a [u8],
+}
+
+impl<'a> Polygon<'a> {
+    /// Returns an iterator over the vertices of the polygon.
+    #[inline]
+    pub fn points(&self) -> PathPoints<'a> {
+        PathPoints {
+            remaining: self.points,
+            buf: self.buf,
+        }
+    }
+}
+
+/// Serializes a Postgres circle.
+#[inline]
+pub fn circle_to_sql(x: f64, y: f64, radius: f64, buf: &mut BytesMut) {
+    buf.put_f64(x);
+    buf.put_f64(y);
+    buf.put_f64(radius);
+}
+
+/// Deserializes a Postgres circle.
+#[inline]
+pub fn circle_from_sql(mut buf: &[u8]) -> Result<Circle, StdBox<dyn Error + Sync + Send>> {
+    let x = buf.read_f64::<BigEndian>()?;
+    let y = buf.read_f64::<BigEndian>()?;
+    let radius = buf.read_f64::<BigEndian>()?;
+    if !buf.is_empty() {
+        return Err("invalid buffer size".into());
+    }
+    Ok(Circle {
+        center: Point { x, y },
+        radius,
+    })
+}
+
+/// A Postgres circle.
+#[derive(Copy, Clone)]
+pub struct Circle {
+    center: Point,
+    radius: f64,
+}
+
+impl Circle {
+    /// Returns the center of the circle.
+    #[inline]
+    pub fn center(&self) -> Point {
+        self.center
+    }
+
+    /// Returns the radius of the circle.
+    #[inline]
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+}
+
 /// Serializes a Postgres inet.
 #[inline]
 pub fn inet_to_sql(addr: IpAddr, netmask: u8, buf: &mut BytesMut) {
@@ -1116,3 +1360,553 @@ pub fn ltxtquery_from_sql(buf: &[u8]) -> Result<&str, StdBox<dyn Error + Sync +
         _ => Err("ltxtquery version 1 only supported".into()),
     }
 }
+
+fn write_cstr(s: &str, buf: &mut BytesMut) -> Result<(), StdBox<dyn Error + Sync + Send>> {
+    if s.as_bytes().contains(&0) {
+        return Err("string contains embedded null".into());
+    }
+    buf.put_slice(s.as_bytes());
+    buf.put_u8(0);
+    Ok(())
+}
+
+fn read_cstr<'a>(buf: &mut &'a [u8]) -> Result<&'a str, StdBox<dyn Error + Sync + Send>> {
+    let end = memchr::memchr(0, buf).ok_or("unexpected EOF")?;
+    let s = str::from_utf8(&buf[..end])?;
+    *buf = &buf[end + 1..];
+    Ok(s)
+}
+
+/// The position of a lexeme in a `TSVECTOR`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TsVectorPosition {
+    /// The position of the lexeme, between 1 and 16383.
+    pub position: u16,
+
+    /// The weight of the lexeme at this position, from 0 (`D`) to 3 (`A`).
+    pub weight: u8,
+}
+
+/// Serializes a `TSVECTOR` value.
+///
+/// Postgres requires lexemes to be unique and sorted, and the positions of each lexeme to be sorted.
+#[inline]
+pub fn tsvector_to_sql<'a, I, J>(
+    lexemes: I,
+    buf: &mut BytesMut,
+) -> Result<(), StdBox<dyn Error + Sync + Send>>
+where
+    I: IntoIterator<Item = (&'a str, J)>,
+    J: IntoIterator<Item = TsVectorPosition>,
+{
+    let base = buf.len();
+    buf.put_i32(0);
+
+    let mut count = 0;
+    for (lexeme, positions) in lexemes {
+        count += 1;
+        write_cstr(lexeme, buf)?;
+
+        let positions_idx = buf.len();
+        buf.put_u16(0);
+        let mut num_positions = 0u16;
+        for position in positions {
+            if position.position > 0x3fff || position.weight > 3 {
+                return Err("invalid lexeme position".into());
+            }
+            num_positions = num_positions
+                .checked_add(1)
+                .ok_or("too many lexeme positions")?;
+            buf.put_u16(u16::from(position.weight) << 14 | position.position);
+        }
+        BigEndian::write_u16(&mut buf[positions_idx..], num_positions);
+    }
+
+    let count = i32::from_usize(count)?;
+    BigEndian::write_i32(&mut buf[base..], count);
+
+    Ok(())
+}
+
+/// Deserializes a `TSVECTOR` value.
+#[inline]
+pub fn tsvector_from_sql(
+    mut buf: &[u8],
+) -> Result<TsVectorLexemes<'_>, StdBox<dyn Error + Sync + Send>> {
+    let count = buf.read_i32::<BigEndian>()?;
+    if count < 0 {
+        return Err("invalid lexeme count".into());
+    }
+
+    Ok(TsVectorLexemes {
+        remaining: count,
+        buf,
+    })
+}
+
+/// A fallible iterator over the lexemes of a `TSVECTOR`.
+pub struct TsVectorLexemes<'a> {
+    remaining: i32,
+    buf: &'a [u8],
+}
+
+impl<'a> FallibleIterator for TsVectorLexemes<'a> {
+    type Item = TsVectorLexeme<'a>;
+    type Error = StdBox<dyn Error + Sync + Send>;
+
+    #[inline]
+    fn next(&mut self) -> Result<Option<TsVectorLexeme<'a>>, StdBox<dyn Error + Sync + Send>> {
+        if self.remaining == 0 {
+            if !self.buf.is_empty() {
+                return Err("invalid buffer size".into());
+            }
+            return Ok(None);
+        }
+
+        self.remaining -= 1;
+
+        let lexeme = read_cstr(&mut self.buf)?;
+        let count = self.buf.read_u16::<BigEndian>()? as usize;
+        if self.buf.len() < count * 2 {
+            return Err("unexpected EOF".into());
+        }
+        let (positions, tail) = self.buf.split_at(count * 2);
+        self.buf = tail;
+
+        Ok(Some(TsVectorLexeme { lexeme, positions }))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.remaining as usize;
+        (len, Some(len))
+    }
+}
+
+/// A lexeme of a `TSVECTOR`.
+pub struct TsVectorLexeme<'a> {
+    lexeme: &'a str,
+    positions: &'a [u8],
+}
+
+impl<'a> TsVectorLexeme<'a> {
+    /// Returns the lexeme.
+    #[inline]
+    pub fn lexeme(&self) -> &'a str {
+        self.lexeme
+    }
+
+    /// Returns an iterator over the positions of the lexeme.
+    #[inline]
+    pub fn positions(&self) -> TsVectorPositions<'a> {
+        TsVectorPositions(self.positions)
+    }
+}
+
+/// An iterator over the positions of a `TSVECTOR` lexeme.
+pub struct TsVectorPositions<'a>(&'a [u8]);
+
+impl<'a> Iterator for TsVectorPositions<'a> {
+    type Item = TsVectorPosition;
+
+    #[inline]
+    fn next(&mut self) -> Option<TsVectorPosition> {
+        if self.0.is_empty() {
+            return None;
+        }
+
+        let raw = BigEndian::read_u16(self.0);
+        self.0 = &self.0[2..];
+        Some(TsVectorPosition {
+            position: raw & 0x3fff,
+            weight: (raw >> 14) as u8,
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.len() / 2;
+        (len, Some(len))
+    }
+}
+
+/// An item of a `TSQUERY`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TsQueryItem<'a> {
+    /// A lexeme to match.
+    Operand {
+        /// The lexeme.
+        lexeme: &'a str,
+        /// A bitmask of the weights the lexeme must have: 8 for `A`, 4 for `B`, 2 for `C` and 1 for `D`. 0 matches any
+        /// weight.
+        weight: u8,
+        /// Whether the lexeme matches as a prefix.
+        prefix: bool,
+    },
+    /// The `!` operator, followed by its operand.
+    Not,
+    /// The `&` operator, followed by its right and then its left operand.
+    And,
+    /// The `|` operator, followed by its right and then its left operand.
+    Or,
+    /// The `<N>` operator, followed by its right and then its left operand.
+    Phrase {
+        /// The distance between the operands.
+        distance: u16,
+    },
+}
+
+/// Serializes a `TSQUERY` value.
+///
+/// The items are in prefix order, with the operands of binary operators in reverse order, as Postgres stores them.
+#[inline]
+pub fn tsquery_to_sql<'a, I>(
+    items: I,
+    buf: &mut BytesMut,
+) -> Result<(), StdBox<dyn Error + Sync + Send>>
+where
+    I: IntoIterator<Item = TsQueryItem<'a>>,
+{
+    let base = buf.len();
+    buf.put_i32(0);
+
+    let mut count = 0;
+    for item in items {
+        count += 1;
+        match item {
+            TsQueryItem::Operand {
+                lexeme,
+                weight,
+                prefix,
+            } => {
+                if weight > 0xf {
+                    return Err("invalid lexeme weight".into());
+                }
+                buf.put_u8(1);
+                buf.put_u8(weight);
+                buf.put_u8(prefix as u8);
+                write_cstr(lexeme, buf)?;
+            }
+            TsQueryItem::Not => {
+                buf.put_u8(2);
+                buf.put_u8(1);
+            }
+            TsQueryItem::And => {
+                buf.put_u8(2);
+                buf.put_u8(2);
+            }
+            TsQueryItem::Or => {
+                buf.put_u8(2);
+                buf.put_u8(3);
+            }
+            TsQueryItem::Phrase { distance } => {
+                if distance > 0x3fff {
+                    return Err("invalid phrase distance".into());
+                }
+                buf.put_u8(2);
+                buf.put_u8(4);
+                buf.put_u16(distance);
+            }
+        }
+    }
+
+    let count = i32::from_usize(count)?;
+    BigEndian::write_i32(&mut buf[base..], count);
+
+    Ok(())
+}
+
+/// Deserializes a `TSQUERY` value.
+///
+/// The items are in prefix order, with the operands of binary operators in reverse order, as Postgres stores them.
+#[inline]
+pub fn tsquery_from_sql(
+    mut buf: &[u8],
+) -> Result<TsQueryItems<'_>, StdBox<dyn Error + Sync + Send>> {
+    let count = buf.read_i32::<BigEndian>()?;
+    if count < 0 {
+        return Err("invalid item count".into());
+    }
+
+    Ok(TsQueryItems {
+        remaining: count,
+        buf,
+    })
+}
+
+/// A fallible iterator over the items of a `TSQUERY`.
+pub struct TsQueryItems<'a> {
+    remaining: i32,
+    buf: &'a [u8],
+}
+
+impl<'a> FallibleIterator for TsQueryItems<'a> {
+    type Item = TsQueryItem<'a>;
+    type Error = StdBox<dyn Error + Sync + Send>;
+
+    #[inline]
+    fn next(&mut self) -> Result<Option<TsQueryItem<'a>>, StdBox<dyn Error + Sync + Send>> {
+        if self.remaining == 0 {
+            if !self.buf.is_empty() {
+                return Err("invalid buffer size".into());
+            }
+            return Ok(None);
+        }
+
+        self.remaining -= 1;
+
+        let item = match self.buf.read_u8()? {
+            1 => {
+                let weight = self.buf.read_u8()?;
+                let prefix = self.buf.read_u8()? != 0;
+                let lexeme = read_cstr(&mut self.buf)?;
+                TsQueryItem::Operand {
+                    lexeme,
+                    weight,
+                    prefix,
+                }
+            }
+            2 => match self.buf.read_u8()? {
+                1 => TsQueryItem::Not,
+                2 => TsQueryItem::And,
+                3 => TsQueryItem::Or,
+                4 => TsQueryItem::Phrase {
+                    distance: self.buf.read_u16::<BigEndian>()?,
+                },
+                _ => return Err("invalid tsquery operator".into()),
+            },
+            _ => return Err("invalid tsquery item type".into()),
+        };
+
+        Ok(Some(item))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.remaining as usize;
+        (len, Some(len))
+    }
+}
+
+/// Serializes a pgvector `VECTOR` value.
+#[inline]
+pub fn vector_to_sql(
+    values: &[f32],
+    buf: &mut BytesMut,
+) -> Result<(), StdBox<dyn Error + Sync + Send>> {
+    let dim = i16::from_usize(values.len())?;
+    buf.put_i16(dim);
+    buf.put_i16(0);
+    for &value in values {
+        buf.put_f32(value);
+    }
+    Ok(())
+}
+
+/// Deserializes a pgvector `VECTOR` value.
+#[inline]
+pub fn vector_from_sql(
+    mut buf: &[u8],
+) -> Result<VectorValues<'_>, StdBox<dyn Error + Sync + Send>> {
+    let dim = buf.read_i16::<BigEndian>()?;
+    let _unused = buf.read_i16::<BigEndian>()?;
+    if dim < 0 || buf.len() != dim as usize * 4 {
+        return Err("invalid buffer size".into());
+    }
+    Ok(VectorValues(buf))
+}
+
+/// An iterator over the values of a pgvector `VECTOR`.
+pub struct VectorValues<'a>(&'a [u8]);
+
+impl<'a> Iterator for VectorValues<'a> {
+    type Item = f32;
+
+    #[inline]
+    fn next(&mut self) -> Option<f32> {
+        if self.0.is_empty() {
+            return None;
+        }
+
+        let value = BigEndian::read_f32(self.0);
+        self.0 = &self.0[4..];
+        Some(value)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.len() / 4;
+        (len, Some(len))
+    }
+}
+
+impl<'a> ExactSizeIterator for VectorValues<'a> {}
+
+/// Serializes a pgvector `HALFVEC` value.
+///
+/// The values are the bit patterns of IEEE 754 half precision floats.
+#[inline]
+pub fn halfvec_to_sql<I>(
+    values: I,
+    buf: &mut BytesMut,
+) -> Result<(), StdBox<dyn Error + Sync + Send>>
+where
+    I: IntoIterator<Item = u16>,
+{
+    let base = buf.len();
+    buf.put_i16(0);
+    buf.put_i16(0);
+
+    let mut dim = 0;
+    for value in values {
+        dim += 1;
+        buf.put_u16(value);
+    }
+
+    let dim = i16::from_usize(dim)?;
+    BigEndian::write_i16(&mut buf[base..], dim);
+
+    Ok(())
+}
+
+/// Deserializes a pgvector `HALFVEC` value.
+///
+/// The values are the bit patterns of IEEE 754 half precision floats.
+#[inline]
+pub fn halfvec_from_sql(
+    mut buf: &[u8],
+) -> Result<HalfVectorValues<'_>, StdBox<dyn Error + Sync + Send>> {
+    let dim = buf.read_i16::<BigEndian>()?;
+    let _unused = buf.read_i16::<BigEndian>()?;
+    if dim < 0 || buf.len() != dim as usize * 2 {
+        return Err("invalid buffer size".into());
+    }
+    Ok(HalfVectorValues(buf))
+}
+
+/// An iterator over the values of a pgvector `HALFVEC`.
+pub struct HalfVectorValues<'a>(&'a [u8]);
+
+impl<'a> Iterator for HalfVectorValues<'a> {
+    type Item = u16;
+
+    #[inline]
+    fn next(&mut self) -> Option<u16> {
+        if self.0.is_empty() {
+            return None;
+        }
+
+        let value = BigEndian::read_u16(self.0);
+        self.0 = &self.0[2..];
+        Some(value)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.len() / 2;
+        (len, Some(len))
+    }
+}
+
+impl<'a> ExactSizeIterator for HalfVectorValues<'a> {}
+
+/// Serializes a pgvector `SPARSEVEC` value.
+///
+/// `indices` are zero-based and must be sorted, and must be the same length as `values`.
+#[inline]
+pub fn sparsevec_to_sql(
+    dim: usize,
+    indices: &[u32],
+    values: &[f32],
+    buf: &mut BytesMut,
+) -> Result<(), StdBox<dyn Error + Sync + Send>> {
+    if indices.len() != values.len() {
+        return Err("mismatched sparse vector indices and values".into());
+    }
+
+    buf.put_i32(i32::from_usize(dim)?);
+    buf.put_i32(i32::from_usize(indices.len())?);
+    buf.put_i32(0);
+    for &index in indices {
+        buf.put_i32(i32::from_usize(index as usize)?);
+    }
+    for &value in values {
+        buf.put_f32(value);
+    }
+    Ok(())
+}
+
+/// Deserializes a pgvector `SPARSEVEC` value.
+#[inline]
+pub fn sparsevec_from_sql(
+    mut buf: &[u8],
+) -> Result<SparseVector<'_>, StdBox<dyn Error + Sync + Send>> {
+    let dim = buf.read_i32::<BigEndian>()?;
+    let nnz = buf.read_i32::<BigEndian>()?;
+    let _unused = buf.read_i32::<BigEndian>()?;
+    if dim < 0 || nnz < 0 || buf.len() != nnz as usize * 8 {
+        return Err("invalid buffer size".into());
+    }
+
+    let (indices, values) = buf.split_at(nnz as usize * 4);
+    Ok(SparseVector {
+        dim,
+        indices,
+        values,
+    })
+}
+
+/// A pgvector `SPARSEVEC`.
+pub struct SparseVector<'a> {
+    dim: i32,
+    indices: &'a [u8],
+    values: &'a [u8],
+}
+
+impl<'a> SparseVector<'a> {
+    /// Returns the number of dimensions of the vector.
+    #[inline]
+    pub fn dim(&self) -> i32 {
+        self.dim
+    }
+
+    /// Returns an iterator over the zero-based indices and values of the nonzero elements of the vector.
+    #[inline]
+    pub fn entries(&self) -> SparseVectorEntries<'a> {
+        SparseVectorEntries {
+            indices: self.indices,
+            values: self.values,
+        }
+    }
+}
+
+/// A fallible iterator over the nonzero elements of a pgvector `SPARSEVEC`.
+pub struct SparseVectorEntries<'a> {
+    indices: &'a [u8],
+    values: &'a [u8],
+}
+
+impl<'a> FallibleIterator for SparseVectorEntries<'a> {
+    type Item = (u32, f32);
+    type Error = StdBox<dyn Error + Sync + Send>;
+
+    #[inline]
+    fn next(&mut self) -> Result<Option<(u32, f32)>, StdBox<dyn Error + Sync + Send>> {
+        if self.indices.is_empty() {
+            return Ok(None);
+        }
+
+        let index = self.indices.read_i32::<BigEndian>()?;
+        if index < 0 {
+            return Err("invalid sparse vector index".into());
+        }
+        let value = self.values.read_f32::<BigEndian>()?;
+        Ok(Some((index as u32, value)))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.indices.len() / 4;
+        (len, Some(len))
+    }
+}
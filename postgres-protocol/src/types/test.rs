@@ -174,7 +174,7 @@ fn ltree_str() {
     let mut query = vec![1u8];
     query.extend_from_slice("A.B.C".as_bytes());
 
-    assert!(matches!(ltree_from_sql(query.as_slice()), Ok(_)))
+    assert!(ltree_from_sql(query.as_slice()).is_ok())
 }
 
 #[test]
@@ -182,7 +182,7 @@ fn ltree_wrong_version() {
     let mut query = vec![2u8];
     query.extend_from_slice("A.B.C".as_bytes());
 
-    assert!(matches!(ltree_from_sql(query.as_slice()), Err(_)))
+    assert!(ltree_from_sql(query.as_slice()).is_err())
 }
 
 #[test]
@@ -202,7 +202,7 @@ fn lquery_str() {
     let mut query = vec![1u8];
     query.extend_from_slice("A.B.C".as_bytes());
 
-    assert!(matches!(lquery_from_sql(query.as_slice()), Ok(_)))
+    assert!(lquery_from_sql(query.as_slice()).is_ok())
 }
 
 #[test]
@@ -210,7 +210,7 @@ fn lquery_wrong_version() {
     let mut query = vec![2u8];
     query.extend_from_slice("A.B.C".as_bytes());
 
-    assert!(matches!(lquery_from_sql(query.as_slice()), Err(_)))
+    assert!(lquery_from_sql(query.as_slice()).is_err())
 }
 
 #[test]
@@ -230,7 +230,7 @@ fn ltxtquery_str() {
     let mut query = vec![1u8];
     query.extend_from_slice("a & b*".as_bytes());
 
-    assert!(matches!(ltree_from_sql(query.as_slice()), Ok(_)))
+    assert!(ltree_from_sql(query.as_slice()).is_ok())
 }
 
 #[test]
@@ -238,5 +238,147 @@ fn ltxtquery_wrong_version() {
     let mut query = vec![2u8];
     query.extend_from_slice("a & b*".as_bytes());
 
-    assert!(matches!(ltree_from_sql(query.as_slice()), Err(_)))
+    assert!(ltree_from_sql(query.as_slice()).is_err())
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn lseg() {
+    let mut buf = BytesMut::new();
+    lseg_to_sql(0., 1.5, -2., 3.25, &mut buf);
+    let lseg = lseg_from_sql(&buf).unwrap();
+    assert_eq!((lseg.start().x(), lseg.start().y()), (0., 1.5));
+    assert_eq!((lseg.end().x(), lseg.end().y()), (-2., 3.25));
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn polygon() {
+    let points = vec![(0., 0.), (1., 0.), (1., 1.)];
+
+    let mut buf = BytesMut::new();
+    polygon_to_sql(points.iter().cloned(), &mut buf).unwrap();
+    let polygon = polygon_from_sql(&buf).unwrap();
+    let actual = polygon
+        .points()
+        .map(|p| Ok((p.x(), p.y())))
+        .collect::<Vec<_>>()
+        .unwrap();
+    assert_eq!(actual, points);
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn circle() {
+    let mut buf = BytesMut::new();
+    circle_to_sql(1., 2., 3.5, &mut buf);
+    let circle = circle_from_sql(&buf).unwrap();
+    assert_eq!((circle.center().x(), circle.center().y()), (1., 2.));
+    assert_eq!(circle.radius(), 3.5);
+}
+
+#[test]
+fn interval() {
+    let mut buf = BytesMut::new();
+    interval_to_sql(-1_500_000, 3, 14, &mut buf);
+    let interval = interval_from_sql(&buf).unwrap();
+    assert_eq!(interval.microseconds(), -1_500_000);
+    assert_eq!(interval.days(), 3);
+    assert_eq!(interval.months(), 14);
+}
+
+#[test]
+fn tsvector() {
+    let mut buf = BytesMut::new();
+    let lexemes = vec![
+        (
+            "a",
+            vec![
+                TsVectorPosition {
+                    position: 1,
+                    weight: 3,
+                },
+                TsVectorPosition {
+                    position: 3,
+                    weight: 0,
+                },
+            ],
+        ),
+        ("b'c", vec![]),
+    ];
+    tsvector_to_sql(
+        lexemes.iter().map(|(l, p)| (*l, p.iter().cloned())),
+        &mut buf,
+    )
+    .unwrap();
+    assert_eq!(&buf[..], b"\0\0\0\x02a\0\0\x02\xc0\x01\0\x03b'c\0\0\0");
+
+    let actual = tsvector_from_sql(&buf)
+        .unwrap()
+        .map(|l| Ok((l.lexeme(), l.positions().collect::<Vec<_>>())))
+        .collect::<Vec<_>>()
+        .unwrap();
+    assert_eq!(actual, lexemes);
+}
+
+#[test]
+fn tsquery() {
+    // 'a' & !'b':*AB <2> 'c'
+    let items = vec![
+        TsQueryItem::And,
+        TsQueryItem::Phrase { distance: 2 },
+        TsQueryItem::Operand {
+            lexeme: "c",
+            weight: 0,
+            prefix: false,
+        },
+        TsQueryItem::Not,
+        TsQueryItem::Operand {
+            lexeme: "b",
+            weight: 0xc,
+            prefix: true,
+        },
+        TsQueryItem::Operand {
+            lexeme: "a",
+            weight: 0,
+            prefix: false,
+        },
+    ];
+
+    let mut buf = BytesMut::new();
+    tsquery_to_sql(items.iter().cloned(), &mut buf).unwrap();
+    assert_eq!(
+        &buf[..],
+        b"\0\0\0\x06\x02\x02\x02\x04\0\x02\x01\0\0c\0\x02\x01\x01\x0c\x01b\0\x01\0\0a\0"
+    );
+
+    let actual = tsquery_from_sql(&buf).unwrap().collect::<Vec<_>>().unwrap();
+    assert_eq!(actual, items);
+}
+
+#[test]
+fn vector() {
+    let mut buf = BytesMut::new();
+    vector_to_sql(&[1.0, -2.5, 3.0], &mut buf).unwrap();
+    assert_eq!(buf.len(), 4 + 3 * 4);
+    let values = vector_from_sql(&buf).unwrap().collect::<Vec<_>>();
+    assert_eq!(values, [1.0, -2.5, 3.0]);
+
+    let mut buf = BytesMut::new();
+    halfvec_to_sql(vec![0x3c00, 0xc100], &mut buf).unwrap();
+    assert_eq!(&buf[..], b"\0\x02\0\0\x3c\x00\xc1\x00");
+    let values = halfvec_from_sql(&buf).unwrap().collect::<Vec<_>>();
+    assert_eq!(values, [0x3c00, 0xc100]);
+}
+
+#[test]
+fn sparsevec() {
+    let mut buf = BytesMut::new();
+    sparsevec_to_sql(5, &[0, 3], &[1.5, -2.0], &mut buf).unwrap();
+    let vector = sparsevec_from_sql(&buf).unwrap();
+    assert_eq!(vector.dim(), 5);
+    let entries = vector.entries().collect::<Vec<_>>().unwrap();
+    assert_eq!(entries, [(0, 1.5), (3, -2.0)]);
+
+    assert!(sparsevec_to_sql(5, &[0], &[], &mut buf).is_err());
 }
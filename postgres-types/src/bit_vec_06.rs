@@ -3,7 +3,7 @@ use bytes::BytesMut;
 use postgres_protocol::types;
 use std::error::Error;
 
-use crate::{FromSql, IsNull, ToSql, Type};
+use crate::{FromSql, FromSqlText, IsNull, ToSql, Type};
 
 impl<'a> FromSql<'a> for BitVec {
     fn from_sql(_: &Type, raw: &[u8]) -> Result<BitVec, Box<dyn Error + Sync + Send>> {
@@ -16,6 +16,20 @@ impl<'a> FromSql<'a> for BitVec {
         Ok(bitvec)
     }
 
+    accepts!(BIT, VARBIT);
+    from_text!();
+}
+
+impl<'a> FromSqlText<'a> for BitVec {
+    fn from_sql_text(_: &Type, raw: &str) -> Result<BitVec, Box<dyn Error + Sync + Send>> {
+        let bits = raw.as_bytes();
+        if !bits.iter().all(|&b| b == b'0' || b == b'1') {
+            return Err("invalid bit string".into());
+        }
+
+        Ok(BitVec::from_fn(bits.len(), |i| bits[i] == b'1'))
+    }
+
     accepts!(BIT, VARBIT);
 }
 
@@ -3,7 +3,8 @@ use chrono_04::{DateTime, Duration, FixedOffset, Local, NaiveDate, NaiveDateTime
 use postgres_protocol::types;
 use std::error::Error;
 
-use crate::{FromSql, IsNull, ToSql, Type};
+use crate::text;
+use crate::{FromSql, FromSqlText, IsNull, ToSql, Type};
 
 fn base() -> NaiveDateTime {
     NaiveDate::from_ymd_opt(2000, 1, 1)
@@ -20,6 +21,16 @@ impl<'a> FromSql<'a> for NaiveDateTime {
             .ok_or_else(|| "value too large to decode".into())
     }
 
+    accepts!(TIMESTAMP);
+    from_text!();
+}
+
+impl<'a> FromSqlText<'a> for NaiveDateTime {
+    fn from_sql_text(ty: &Type, raw: &str) -> Result<NaiveDateTime, Box<dyn Error + Sync + Send>> {
+        let value = text::timestamp_from_text(raw)?;
+        text::from_binary(ty, |buf| types::timestamp_to_sql(value, buf))
+    }
+
     accepts!(TIMESTAMP);
 }
 
@@ -43,6 +54,16 @@ impl<'a> FromSql<'a> for DateTime<Utc> {
         Ok(DateTime::from_utc(naive, Utc))
     }
 
+    accepts!(TIMESTAMPTZ);
+    from_text!();
+}
+
+impl<'a> FromSqlText<'a> for DateTime<Utc> {
+    fn from_sql_text(ty: &Type, raw: &str) -> Result<DateTime<Utc>, Box<dyn Error + Sync + Send>> {
+        let value = text::timestamp_from_text(raw)?;
+        text::from_binary(ty, |buf| types::timestamp_to_sql(value, buf))
+    }
+
     accepts!(TIMESTAMPTZ);
 }
 
@@ -65,6 +86,19 @@ impl<'a> FromSql<'a> for DateTime<Local> {
         Ok(utc.with_timezone(&Local))
     }
 
+    accepts!(TIMESTAMPTZ);
+    from_text!();
+}
+
+impl<'a> FromSqlText<'a> for DateTime<Local> {
+    fn from_sql_text(
+        ty: &Type,
+        raw: &str,
+    ) -> Result<DateTime<Local>, Box<dyn Error + Sync + Send>> {
+        let value = text::timestamp_from_text(raw)?;
+        text::from_binary(ty, |buf| types::timestamp_to_sql(value, buf))
+    }
+
     accepts!(TIMESTAMPTZ);
 }
 
@@ -90,6 +124,19 @@ impl<'a> FromSql<'a> for DateTime<FixedOffset> {
         Ok(utc.with_timezone(&FixedOffset::east_opt(0).unwrap()))
     }
 
+    accepts!(TIMESTAMPTZ);
+    from_text!();
+}
+
+impl<'a> FromSqlText<'a> for DateTime<FixedOffset> {
+    fn from_sql_text(
+        ty: &Type,
+        raw: &str,
+    ) -> Result<DateTime<FixedOffset>, Box<dyn Error + Sync + Send>> {
+        let value = text::timestamp_from_text(raw)?;
+        text::from_binary(ty, |buf| types::timestamp_to_sql(value, buf))
+    }
+
     accepts!(TIMESTAMPTZ);
 }
 
@@ -115,13 +162,23 @@ impl<'a> FromSql<'a> for NaiveDate {
             .ok_or_else(|| "value too large to decode".into())
     }
 
+    accepts!(DATE);
+    from_text!();
+}
+
+impl<'a> FromSqlText<'a> for NaiveDate {
+    fn from_sql_text(ty: &Type, raw: &str) -> Result<NaiveDate, Box<dyn Error + Sync + Send>> {
+        let value = text::date_from_text(raw)?;
+        text::from_binary(ty, |buf| types::date_to_sql(value, buf))
+    }
+
     accepts!(DATE);
 }
 
 impl ToSql for NaiveDate {
     fn to_sql(&self, _: &Type, w: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
         let jd = self.signed_duration_since(base().date()).num_days();
-        if jd > i64::from(i32::max_value()) || jd < i64::from(i32::min_value()) {
+        if jd > i64::from(i32::MAX) || jd < i64::from(i32::MIN) {
             return Err("value too large to transmit".into());
         }
 
@@ -139,6 +196,16 @@ impl<'a> FromSql<'a> for NaiveTime {
         Ok(NaiveTime::from_hms_opt(0, 0, 0).unwrap() + Duration::microseconds(usec))
     }
 
+    accepts!(TIME);
+    from_text!();
+}
+
+impl<'a> FromSqlText<'a> for NaiveTime {
+    fn from_sql_text(ty: &Type, raw: &str) -> Result<NaiveTime, Box<dyn Error + Sync + Send>> {
+        let value = text::time_from_text(raw)?;
+        text::from_binary(ty, |buf| types::time_to_sql(value, buf))
+    }
+
     accepts!(TIME);
 }
 
@@ -3,7 +3,7 @@ use cidr_02::{IpCidr, IpInet};
 use postgres_protocol::types;
 use std::error::Error;
 
-use crate::{FromSql, IsNull, ToSql, Type};
+use crate::{FromSql, FromSqlText, IsNull, ToSql, Type};
 
 impl<'a> FromSql<'a> for IpCidr {
     fn from_sql(_: &Type, raw: &[u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
@@ -11,6 +11,15 @@ impl<'a> FromSql<'a> for IpCidr {
         Ok(IpCidr::new(inet.addr(), inet.netmask())?)
     }
 
+    accepts!(CIDR);
+    from_text!();
+}
+
+impl<'a> FromSqlText<'a> for IpCidr {
+    fn from_sql_text(_: &Type, raw: &str) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        Ok(raw.parse()?)
+    }
+
     accepts!(CIDR);
 }
 
@@ -30,6 +39,15 @@ impl<'a> FromSql<'a> for IpInet {
         Ok(IpInet::new(inet.addr(), inet.netmask())?)
     }
 
+    accepts!(INET);
+    from_text!();
+}
+
+impl<'a> FromSqlText<'a> for IpInet {
+    fn from_sql_text(_: &Type, raw: &str) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        Ok(raw.parse()?)
+    }
+
     accepts!(INET);
 }
 
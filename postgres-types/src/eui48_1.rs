@@ -3,7 +3,7 @@ use eui48_1::MacAddress;
 use postgres_protocol::types;
 use std::error::Error;
 
-use crate::{FromSql, IsNull, ToSql, Type};
+use crate::{FromSql, FromSqlText, IsNull, ToSql, Type};
 
 impl<'a> FromSql<'a> for MacAddress {
     fn from_sql(_: &Type, raw: &[u8]) -> Result<MacAddress, Box<dyn Error + Sync + Send>> {
@@ -11,6 +11,15 @@ impl<'a> FromSql<'a> for MacAddress {
         Ok(MacAddress::new(bytes))
     }
 
+    accepts!(MACADDR);
+    from_text!();
+}
+
+impl<'a> FromSqlText<'a> for MacAddress {
+    fn from_sql_text(_: &Type, raw: &str) -> Result<MacAddress, Box<dyn Error + Sync + Send>> {
+        Ok(MacAddress::parse_str(raw)?)
+    }
+
     accepts!(MACADDR);
 }
 
@@ -1,10 +1,11 @@
 use bytes::BytesMut;
 use fallible_iterator::FallibleIterator;
-use geo_types_06::{Coordinate, LineString, Point, Rect};
+use geo_types_06::{Coordinate, Line, LineString, Point, Polygon, Rect};
 use postgres_protocol::types;
 use std::error::Error;
 
-use crate::{FromSql, IsNull, ToSql, Type};
+use crate::geometric::points_from_text;
+use crate::{FromSql, FromSqlText, IsNull, ToSql, Type};
 
 impl<'a> FromSql<'a> for Point<f64> {
     fn from_sql(_: &Type, raw: &[u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
@@ -12,6 +13,18 @@ impl<'a> FromSql<'a> for Point<f64> {
         Ok(Point::new(point.x(), point.y()))
     }
 
+    accepts!(POINT);
+    from_text!();
+}
+
+impl<'a> FromSqlText<'a> for Point<f64> {
+    fn from_sql_text(_: &Type, raw: &str) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        match *points_from_text(raw)? {
+            [point] => Ok(Point::new(point.x, point.y)),
+            _ => Err("invalid point".into()),
+        }
+    }
+
     accepts!(POINT);
 }
 
@@ -34,6 +47,21 @@ impl<'a> FromSql<'a> for Rect<f64> {
         ))
     }
 
+    accepts!(BOX);
+    from_text!();
+}
+
+impl<'a> FromSqlText<'a> for Rect<f64> {
+    fn from_sql_text(_: &Type, raw: &str) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        match *points_from_text(raw)? {
+            [upper_right, lower_left] => Ok(Rect::new(
+                (lower_left.x, lower_left.y),
+                (upper_right.x, upper_right.y),
+            )),
+            _ => Err("invalid box".into()),
+        }
+    }
+
     accepts!(BOX);
 }
 
@@ -57,6 +85,19 @@ impl<'a> FromSql<'a> for LineString<f64> {
         Ok(LineString(points))
     }
 
+    accepts!(PATH);
+    from_text!();
+}
+
+impl<'a> FromSqlText<'a> for LineString<f64> {
+    fn from_sql_text(_: &Type, raw: &str) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        let points = points_from_text(raw)?
+            .iter()
+            .map(|p| Coordinate { x: p.x, y: p.y })
+            .collect();
+        Ok(LineString(points))
+    }
+
     accepts!(PATH);
 }
 
@@ -70,3 +111,81 @@ impl ToSql for LineString<f64> {
     accepts!(PATH);
     to_sql_checked!();
 }
+
+impl<'a> FromSql<'a> for Line<f64> {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        let lseg = types::lseg_from_sql(raw)?;
+        Ok(Line::new(
+            (lseg.start().x(), lseg.start().y()),
+            (lseg.end().x(), lseg.end().y()),
+        ))
+    }
+
+    accepts!(LSEG);
+    from_text!();
+}
+
+impl<'a> FromSqlText<'a> for Line<f64> {
+    fn from_sql_text(_: &Type, raw: &str) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        match *points_from_text(raw)? {
+            [start, end] => Ok(Line::new((start.x, start.y), (end.x, end.y))),
+            _ => Err("invalid line segment".into()),
+        }
+    }
+
+    accepts!(LSEG);
+}
+
+impl ToSql for Line<f64> {
+    fn to_sql(&self, _: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        types::lseg_to_sql(self.start.x, self.start.y, self.end.x, self.end.y, out);
+        Ok(IsNull::No)
+    }
+
+    accepts!(LSEG);
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for Polygon<f64> {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        let polygon = types::polygon_from_sql(raw)?;
+        let points = polygon
+            .points()
+            .map(|p| Ok(Coordinate { x: p.x(), y: p.y() }))
+            .collect()?;
+        Ok(Polygon::new(LineString(points), vec![]))
+    }
+
+    accepts!(POLYGON);
+    from_text!();
+}
+
+impl<'a> FromSqlText<'a> for Polygon<f64> {
+    fn from_sql_text(_: &Type, raw: &str) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        let points = points_from_text(raw)?
+            .iter()
+            .map(|p| Coordinate { x: p.x, y: p.y })
+            .collect();
+        Ok(Polygon::new(LineString(points), vec![]))
+    }
+
+    accepts!(POLYGON);
+}
+
+impl ToSql for Polygon<f64> {
+    fn to_sql(&self, _: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        if !self.interiors().is_empty() {
+            return Err("POLYGON does not support interior rings".into());
+        }
+        // Postgres polygons are implicitly closed, so the closing coordinate of the exterior ring is omitted
+        let mut points = &self.exterior().0[..];
+        if points.len() > 1 && points.first() == points.last() {
+            points = &points[..points.len() - 1];
+        }
+        types::polygon_to_sql(points.iter().map(|p| (p.x, p.y)), out)?;
+        Ok(IsNull::No)
+    }
+
+    accepts!(POLYGON);
+    to_sql_checked!();
+}
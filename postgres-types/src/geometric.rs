@@ -0,0 +1,267 @@
+//! Postgres geometric types.
+
+use bytes::BytesMut;
+use fallible_iterator::FallibleIterator;
+use postgres_protocol::types;
+use std::error::Error;
+
+use crate::{FromSql, FromSqlText, IsNull, ToSql, Type};
+
+/// Postgres `POINT` type.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct Point {
+    /// The x coordinate of the point.
+    pub x: f64,
+    /// The y coordinate of the point.
+    pub y: f64,
+}
+
+impl Point {
+    /// Creates a new point.
+    pub fn new(x: f64, y: f64) -> Point {
+        Point { x, y }
+    }
+}
+
+impl From<types::Point> for Point {
+    fn from(point: types::Point) -> Point {
+        Point::new(point.x(), point.y())
+    }
+}
+
+impl<'a> FromSql<'a> for Point {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        types::point_from_sql(raw).map(Point::from)
+    }
+
+    accepts!(POINT);
+    from_text!();
+}
+
+impl<'a> FromSqlText<'a> for Point {
+    fn from_sql_text(_: &Type, raw: &str) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        match *points_from_text(raw)? {
+            [point] => Ok(point),
+            _ => Err("invalid point".into()),
+        }
+    }
+
+    accepts!(POINT);
+}
+
+impl ToSql for Point {
+    fn to_sql(&self, _: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        types::point_to_sql(self.x, self.y, out);
+        Ok(IsNull::No)
+    }
+
+    accepts!(POINT);
+    to_sql_checked!();
+}
+
+/// Postgres `LINE` type.
+///
+/// The line is represented by the coefficients of the equation `Ax + By + C = 0`.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct Line {
+    /// The `A` coefficient of the line.
+    pub a: f64,
+    /// The `B` coefficient of the line.
+    pub b: f64,
+    /// The `C` coefficient of the line.
+    pub c: f64,
+}
+
+impl Line {
+    /// Creates a new line from the coefficients of the equation `Ax + By + C = 0`.
+    pub fn new(a: f64, b: f64, c: f64) -> Line {
+        Line { a, b, c }
+    }
+}
+
+impl<'a> FromSql<'a> for Line {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        let line = types::line_from_sql(raw)?;
+        Ok(Line::new(line.a(), line.b(), line.c()))
+    }
+
+    accepts!(LINE);
+    from_text!();
+}
+
+impl<'a> FromSqlText<'a> for Line {
+    fn from_sql_text(_: &Type, raw: &str) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        match *floats_from_text(raw)? {
+            [a, b, c] => Ok(Line::new(a, b, c)),
+            _ => Err("invalid line".into()),
+        }
+    }
+
+    accepts!(LINE);
+}
+
+impl ToSql for Line {
+    fn to_sql(&self, _: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        types::line_to_sql(self.a, self.b, self.c, out);
+        Ok(IsNull::No)
+    }
+
+    accepts!(LINE);
+    to_sql_checked!();
+}
+
+/// Postgres `LSEG` type.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct LineSegment {
+    /// The start point of the line segment.
+    pub start: Point,
+    /// The end point of the line segment.
+    pub end: Point,
+}
+
+impl LineSegment {
+    /// Creates a new line segment.
+    pub fn new(start: Point, end: Point) -> LineSegment {
+        LineSegment { start, end }
+    }
+}
+
+impl<'a> FromSql<'a> for LineSegment {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        let lseg = types::lseg_from_sql(raw)?;
+        Ok(LineSegment::new(lseg.start().into(), lseg.end().into()))
+    }
+
+    accepts!(LSEG);
+    from_text!();
+}
+
+impl<'a> FromSqlText<'a> for LineSegment {
+    fn from_sql_text(_: &Type, raw: &str) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        match *points_from_text(raw)? {
+            [start, end] => Ok(LineSegment::new(start, end)),
+            _ => Err("invalid line segment".into()),
+        }
+    }
+
+    accepts!(LSEG);
+}
+
+impl ToSql for LineSegment {
+    fn to_sql(&self, _: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        types::lseg_to_sql(self.start.x, self.start.y, self.end.x, self.end.y, out);
+        Ok(IsNull::No)
+    }
+
+    accepts!(LSEG);
+    to_sql_checked!();
+}
+
+/// Postgres `POLYGON` type.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Polygon {
+    /// The vertices of the polygon.
+    pub points: Vec<Point>,
+}
+
+impl Polygon {
+    /// Creates a new polygon from its vertices.
+    pub fn new(points: Vec<Point>) -> Polygon {
+        Polygon { points }
+    }
+}
+
+impl<'a> FromSql<'a> for Polygon {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        let polygon = types::polygon_from_sql(raw)?;
+        let points = polygon.points().map(|p| Ok(Point::from(p))).collect()?;
+        Ok(Polygon::new(points))
+    }
+
+    accepts!(POLYGON);
+    from_text!();
+}
+
+impl<'a> FromSqlText<'a> for Polygon {
+    fn from_sql_text(_: &Type, raw: &str) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        points_from_text(raw).map(Polygon::new)
+    }
+
+    accepts!(POLYGON);
+}
+
+impl ToSql for Polygon {
+    fn to_sql(&self, _: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        types::polygon_to_sql(self.points.iter().map(|p| (p.x, p.y)), out)?;
+        Ok(IsNull::No)
+    }
+
+    accepts!(POLYGON);
+    to_sql_checked!();
+}
+
+/// Postgres `CIRCLE` type.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct Circle {
+    /// The center of the circle.
+    pub center: Point,
+    /// The radius of the circle.
+    pub radius: f64,
+}
+
+impl Circle {
+    /// Creates a new circle.
+    pub fn new(center: Point, radius: f64) -> Circle {
+        Circle { center, radius }
+    }
+}
+
+impl<'a> FromSql<'a> for Circle {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        let circle = types::circle_from_sql(raw)?;
+        Ok(Circle::new(circle.center().into(), circle.radius()))
+    }
+
+    accepts!(CIRCLE);
+    from_text!();
+}
+
+impl<'a> FromSqlText<'a> for Circle {
+    fn from_sql_text(_: &Type, raw: &str) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        match *floats_from_text(raw)? {
+            [x, y, radius] => Ok(Circle::new(Point::new(x, y), radius)),
+            _ => Err("invalid circle".into()),
+        }
+    }
+
+    accepts!(CIRCLE);
+}
+
+impl ToSql for Circle {
+    fn to_sql(&self, _: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        types::circle_to_sql(self.center.x, self.center.y, self.radius, out);
+        Ok(IsNull::No)
+    }
+
+    accepts!(CIRCLE);
+    to_sql_checked!();
+}
+
+/// Parses the coordinates of a geometric value in its text format, such as `<(1,2),3>`, ignoring the delimiters
+/// around them.
+pub(crate) fn floats_from_text(raw: &str) -> Result<Vec<f64>, Box<dyn Error + Sync + Send>> {
+    raw.split(|c: char| "()[]{}<>,".contains(c) || c.is_ascii_whitespace())
+        .filter(|s| !s.is_empty())
+        .map(|s| Ok(s.parse()?))
+        .collect()
+}
+
+/// Parses the points of a geometric value in its text format, such as `[(1,2),(3,4)]`.
+pub(crate) fn points_from_text(raw: &str) -> Result<Vec<Point>, Box<dyn Error + Sync + Send>> {
+    let floats = floats_from_text(raw)?;
+    let points = floats.chunks_exact(2);
+    if !points.remainder().is_empty() {
+        return Err("invalid geometric value: odd number of coordinates".into());
+    }
+    Ok(points.map(|point| Point::new(point[0], point[1])).collect())
+}
@@ -0,0 +1,313 @@
+use bytes::BytesMut;
+use jiff_01::{
+    civil::{Date, DateTime, Time},
+    tz::TimeZone,
+    Span, Timestamp, Zoned,
+};
+use postgres_protocol::types;
+use std::convert::TryFrom;
+use std::error::Error;
+
+use crate::text;
+use crate::{FromSql, FromSqlText, IsNull, ToSql, Type};
+
+/// The number of microseconds between the Unix epoch and midnight, January 1st, 2000.
+const PG_EPOCH_OFFSET: i64 = 946_684_800_000_000;
+
+const MICROS_PER_SECOND: i64 = 1_000_000;
+const MICROS_PER_MINUTE: i64 = 60 * MICROS_PER_SECOND;
+const MICROS_PER_HOUR: i64 = 60 * MICROS_PER_MINUTE;
+
+fn base() -> Date {
+    Date::constant(2000, 1, 1)
+}
+
+impl<'a> FromSql<'a> for Timestamp {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<Timestamp, Box<dyn Error + Sync + Send>> {
+        types::timestamp_from_sql(raw)?
+            .checked_add(PG_EPOCH_OFFSET)
+            .and_then(|t| Timestamp::from_microsecond(t).ok())
+            .ok_or_else(|| "value too large to decode".into())
+    }
+
+    accepts!(TIMESTAMPTZ);
+    from_text!();
+}
+
+impl<'a> FromSqlText<'a> for Timestamp {
+    fn from_sql_text(ty: &Type, raw: &str) -> Result<Timestamp, Box<dyn Error + Sync + Send>> {
+        let value = text::timestamp_from_text(raw)?;
+        text::from_binary(ty, |buf| types::timestamp_to_sql(value, buf))
+    }
+
+    accepts!(TIMESTAMPTZ);
+}
+
+impl ToSql for Timestamp {
+    fn to_sql(&self, _: &Type, w: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        let time = match self.as_microsecond().checked_sub(PG_EPOCH_OFFSET) {
+            Some(time) => time,
+            None => return Err("value too large to transmit".into()),
+        };
+        types::timestamp_to_sql(time, w);
+        Ok(IsNull::No)
+    }
+
+    accepts!(TIMESTAMPTZ);
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for Zoned {
+    fn from_sql(type_: &Type, raw: &[u8]) -> Result<Zoned, Box<dyn Error + Sync + Send>> {
+        let timestamp = Timestamp::from_sql(type_, raw)?;
+        Ok(timestamp.to_zoned(TimeZone::UTC))
+    }
+
+    accepts!(TIMESTAMPTZ);
+    from_text!();
+}
+
+impl<'a> FromSqlText<'a> for Zoned {
+    fn from_sql_text(ty: &Type, raw: &str) -> Result<Zoned, Box<dyn Error + Sync + Send>> {
+        let value = text::timestamp_from_text(raw)?;
+        text::from_binary(ty, |buf| types::timestamp_to_sql(value, buf))
+    }
+
+    accepts!(TIMESTAMPTZ);
+}
+
+impl ToSql for Zoned {
+    fn to_sql(
+        &self,
+        type_: &Type,
+        w: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        self.timestamp().to_sql(type_, w)
+    }
+
+    accepts!(TIMESTAMPTZ);
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for DateTime {
+    fn from_sql(type_: &Type, raw: &[u8]) -> Result<DateTime, Box<dyn Error + Sync + Send>> {
+        let timestamp = Timestamp::from_sql(type_, raw)?;
+        Ok(timestamp.to_zoned(TimeZone::UTC).datetime())
+    }
+
+    accepts!(TIMESTAMP);
+    from_text!();
+}
+
+impl<'a> FromSqlText<'a> for DateTime {
+    fn from_sql_text(ty: &Type, raw: &str) -> Result<DateTime, Box<dyn Error + Sync + Send>> {
+        let value = text::timestamp_from_text(raw)?;
+        text::from_binary(ty, |buf| types::timestamp_to_sql(value, buf))
+    }
+
+    accepts!(TIMESTAMP);
+}
+
+impl ToSql for DateTime {
+    fn to_sql(
+        &self,
+        type_: &Type,
+        w: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        let timestamp = match self.to_zoned(TimeZone::UTC) {
+            Ok(zoned) => zoned.timestamp(),
+            Err(_) => return Err("value too large to transmit".into()),
+        };
+        timestamp.to_sql(type_, w)
+    }
+
+    accepts!(TIMESTAMP);
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for Date {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<Date, Box<dyn Error + Sync + Send>> {
+        let jd = types::date_from_sql(raw)?;
+        Span::new()
+            .try_days(jd)
+            .ok()
+            .and_then(|span| base().checked_add(span).ok())
+            .ok_or_else(|| "value too large to decode".into())
+    }
+
+    accepts!(DATE);
+    from_text!();
+}
+
+impl<'a> FromSqlText<'a> for Date {
+    fn from_sql_text(ty: &Type, raw: &str) -> Result<Date, Box<dyn Error + Sync + Send>> {
+        let value = text::date_from_text(raw)?;
+        text::from_binary(ty, |buf| types::date_to_sql(value, buf))
+    }
+
+    accepts!(DATE);
+}
+
+impl ToSql for Date {
+    fn to_sql(&self, _: &Type, w: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        let jd = match self.since(base()) {
+            Ok(span) => span.get_days(),
+            Err(_) => return Err("value too large to transmit".into()),
+        };
+        types::date_to_sql(jd, w);
+        Ok(IsNull::No)
+    }
+
+    accepts!(DATE);
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for Time {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<Time, Box<dyn Error + Sync + Send>> {
+        let usec = types::time_from_sql(raw)?;
+        // Postgres allows 24:00:00, which can't be represented by a `Time`
+        if !(0..24 * MICROS_PER_HOUR).contains(&usec) {
+            return Err("value too large to decode".into());
+        }
+
+        let time = Time::new(
+            (usec / MICROS_PER_HOUR) as i8,
+            (usec % MICROS_PER_HOUR / MICROS_PER_MINUTE) as i8,
+            (usec % MICROS_PER_MINUTE / MICROS_PER_SECOND) as i8,
+            (usec % MICROS_PER_SECOND * 1000) as i32,
+        )?;
+        Ok(time)
+    }
+
+    accepts!(TIME);
+    from_text!();
+}
+
+impl<'a> FromSqlText<'a> for Time {
+    fn from_sql_text(ty: &Type, raw: &str) -> Result<Time, Box<dyn Error + Sync + Send>> {
+        let value = text::time_from_text(raw)?;
+        text::from_binary(ty, |buf| types::time_to_sql(value, buf))
+    }
+
+    accepts!(TIME);
+}
+
+impl ToSql for Time {
+    fn to_sql(&self, _: &Type, w: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        let usec = i64::from(self.hour()) * MICROS_PER_HOUR
+            + i64::from(self.minute()) * MICROS_PER_MINUTE
+            + i64::from(self.second()) * MICROS_PER_SECOND
+            + i64::from(self.subsec_nanosecond()) / 1000;
+        types::time_to_sql(usec, w);
+        Ok(IsNull::No)
+    }
+
+    accepts!(TIME);
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for Span {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<Span, Box<dyn Error + Sync + Send>> {
+        let interval = types::interval_from_sql(raw)?;
+        let usec = interval.microseconds();
+        let days = interval.days();
+        let months = interval.months();
+
+        // a `Span` has a single sign shared by all of its units
+        let signs = [
+            usec.signum(),
+            i64::from(days.signum()),
+            i64::from(months.signum()),
+        ];
+        if signs.contains(&1) && signs.contains(&-1) {
+            return Err("cannot decode an interval with mixed signs".into());
+        }
+
+        Span::new()
+            .try_years(months / 12)
+            .and_then(|span| span.try_months(months % 12))
+            .and_then(|span| span.try_days(days))
+            .and_then(|span| span.try_hours(usec / MICROS_PER_HOUR))
+            .and_then(|span| span.try_minutes(usec % MICROS_PER_HOUR / MICROS_PER_MINUTE))
+            .and_then(|span| span.try_seconds(usec % MICROS_PER_MINUTE / MICROS_PER_SECOND))
+            .and_then(|span| span.try_microseconds(usec % MICROS_PER_SECOND))
+            .map_err(|_| "value too large to decode".into())
+    }
+
+    accepts!(INTERVAL);
+    from_text!();
+}
+
+impl<'a> FromSqlText<'a> for Span {
+    fn from_sql_text(ty: &Type, raw: &str) -> Result<Span, Box<dyn Error + Sync + Send>> {
+        let (usec, days, months) = interval_from_text(raw)?;
+        text::from_binary(ty, |buf| types::interval_to_sql(usec, days, months, buf))
+    }
+
+    accepts!(INTERVAL);
+}
+
+impl ToSql for Span {
+    fn to_sql(&self, _: &Type, w: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        let months = i64::from(self.get_years()) * 12 + i64::from(self.get_months());
+        let days = i64::from(self.get_weeks()) * 7 + i64::from(self.get_days());
+        // sub-microsecond precision is truncated
+        let nsec = i128::from(self.get_hours()) * i128::from(MICROS_PER_HOUR) * 1000
+            + i128::from(self.get_minutes()) * i128::from(MICROS_PER_MINUTE) * 1000
+            + i128::from(self.get_seconds()) * i128::from(MICROS_PER_SECOND) * 1000
+            + i128::from(self.get_milliseconds()) * 1_000_000
+            + i128::from(self.get_microseconds()) * 1000
+            + i128::from(self.get_nanoseconds());
+        let usec = nsec / 1000;
+
+        match (
+            i64::try_from(usec),
+            i32::try_from(days),
+            i32::try_from(months),
+        ) {
+            (Ok(usec), Ok(days), Ok(months)) => {
+                types::interval_to_sql(usec, days, months, w);
+                Ok(IsNull::No)
+            }
+            _ => Err("value too large to transmit".into()),
+        }
+    }
+
+    accepts!(INTERVAL);
+    to_sql_checked!();
+}
+
+// parses the `postgres` `IntervalStyle`, such as `1 year 2 mons -3 days +04:05:06.789`
+fn interval_from_text(raw: &str) -> Result<(i64, i32, i32), Box<dyn Error + Sync + Send>> {
+    let mut usec = 0i64;
+    let mut days = 0i32;
+    let mut months = 0i32;
+
+    let mut tokens = raw.split_whitespace();
+    while let Some(token) = tokens.next() {
+        if token.contains(':') {
+            let (negative, time) = match token.as_bytes()[0] {
+                b'-' => (true, &token[1..]),
+                b'+' => (false, &token[1..]),
+                _ => (false, token),
+            };
+            let time = text::time_from_text(time)?;
+            usec = if negative { -time } else { time };
+            continue;
+        }
+
+        let value = token.parse::<i32>()?;
+        let (unit, scale) = match tokens.next() {
+            Some("year" | "years") => (&mut months, 12),
+            Some("mon" | "mons") => (&mut months, 1),
+            Some("day" | "days") => (&mut days, 1),
+            _ => return Err("invalid interval".into()),
+        };
+        *unit = value
+            .checked_mul(scale)
+            .and_then(|v| unit.checked_add(v))
+            .ok_or("value too large to decode")?;
+    }
+
+    Ok((usec, days, months))
+}
@@ -129,7 +129,7 @@
 #![warn(clippy::all, rust_2018_idioms, missing_docs)]
 
 use fallible_iterator::FallibleIterator;
-use postgres_protocol::types::{self, ArrayDimension};
+use postgres_protocol::types;
 use std::any::type_name;
 use std::borrow::Cow;
 use std::collections::HashMap;
@@ -147,13 +147,25 @@ pub use postgres_derive::{FromSql, ToSql};
 pub use crate::serde_json_1::Json;
 use crate::type_gen::{Inner, Other};
 
+#[doc(inline)]
+pub use postgres_protocol::types::ArrayDimension;
 #[doc(inline)]
 pub use postgres_protocol::Oid;
 
 #[doc(inline)]
 pub use pg_lsn::PgLsn;
 
+pub use crate::pg_array::{ArrayShapeError, PgArray};
+
+pub use crate::geometric::{Circle, Line, LineSegment, Point, Polygon};
+
+#[cfg(feature = "pgvector")]
+pub use crate::pgvector::{HalfVector, SparseVector, Vector};
+#[cfg(feature = "postgis")]
+pub use crate::postgis::Ewkb;
 pub use crate::special::{Date, Timestamp};
+pub use crate::text::{FromSqlText, FromSqlTextOwned};
+pub use crate::text_search::{TsLexeme, TsPosition, TsQuery, TsQueryOperand, TsVector, TsWeight};
 use bytes::BytesMut;
 
 // Number of seconds from 1970-01-01 to 2000-01-01
@@ -191,6 +203,21 @@ macro_rules! to_sql_checked {
     };
 }
 
+// Generates an implementation of `FromSql::from_text` which delegates to the type's `FromSqlText` implementation.
+macro_rules! from_text {
+    () => {
+        fn from_text(
+            ty: &$crate::Type,
+            raw: ::std::borrow::Cow<'_, str>,
+        ) -> ::std::result::Result<
+            Self,
+            Box<dyn ::std::error::Error + ::std::marker::Sync + ::std::marker::Send>,
+        > {
+            $crate::text::from_text(ty, &raw)
+        }
+    };
+}
+
 // WARNING: this function is not considered part of this crate's public API.
 // It is subject to change at any time.
 #[doc(hidden)]
@@ -222,6 +249,10 @@ mod eui48_1;
 mod geo_types_06;
 #[cfg(feature = "with-geo-types-0_7")]
 mod geo_types_07;
+#[cfg(feature = "with-jiff-0_1")]
+mod jiff_01;
+#[cfg(feature = "with-ndarray-0_16")]
+mod ndarray_016;
 #[cfg(feature = "with-serde_json-1")]
 mod serde_json_1;
 #[cfg(feature = "with-smol_str-01")]
@@ -239,10 +270,18 @@ mod uuid_1;
 #[cfg(feature = "with-time-0_2")]
 extern crate time_02 as time;
 
+mod geometric;
+mod pg_array;
 mod pg_lsn;
+#[cfg(feature = "pgvector")]
+mod pgvector;
+#[cfg(feature = "postgis")]
+mod postgis;
 #[doc(hidden)]
 pub mod private;
 mod special;
+mod text;
+mod text_search;
 mod type_gen;
 
 /// A Postgres type.
@@ -418,6 +457,13 @@ impl WrongType {
 /// | `HashMap<String, Option<String>>` | HSTORE                                        |
 /// | `SystemTime`                      | TIMESTAMP, TIMESTAMP WITH TIME ZONE           |
 /// | `IpAddr`                          | INET                                          |
+/// | `Point`                           | POINT                                         |
+/// | `Line`                            | LINE                                          |
+/// | `LineSegment`                     | LSEG                                          |
+/// | `Polygon`                         | POLYGON                                       |
+/// | `Circle`                          | CIRCLE                                        |
+/// | `TsVector`                        | TSVECTOR                                      |
+/// | `TsQuery`                         | TSQUERY                                       |
 ///
 /// In addition, some implementations are provided for types in third party
 /// crates. These are disabled by default; to opt into one of these
@@ -437,10 +483,19 @@ impl WrongType {
 /// | `time::OffsetDateTime`          | TIMESTAMP WITH TIME ZONE            |
 /// | `time::Date`                    | DATE                                |
 /// | `time::Time`                    | TIME                                |
+/// | `jiff::civil::DateTime`         | TIMESTAMP                           |
+/// | `jiff::Timestamp`               | TIMESTAMP WITH TIME ZONE            |
+/// | `jiff::Zoned`                   | TIMESTAMP WITH TIME ZONE            |
+/// | `jiff::civil::Date`             | DATE                                |
+/// | `jiff::civil::Time`             | TIME                                |
+/// | `jiff::Span`                    | INTERVAL                            |
 /// | `eui48::MacAddress`             | MACADDR                             |
 /// | `geo_types::Point<f64>`         | POINT                               |
 /// | `geo_types::Rect<f64>`          | BOX                                 |
 /// | `geo_types::LineString<f64>`    | PATH                                |
+/// | `geo_types::Line<f64>`          | LSEG                                |
+/// | `geo_types::Polygon<f64>`       | POLYGON                             |
+/// | `ndarray::ArrayD<T>`            | array types                         |
 /// | `serde_json::Value`             | JSON, JSONB                         |
 /// | `uuid::Uuid`                    | UUID                                |
 /// | `bit_vec::BitVec`               | BIT, VARBIT                         |
@@ -451,6 +506,15 @@ impl WrongType {
 /// |                                 | NAME, UNKNOWN, LTREE, LQUERY,       |
 /// |                                 | LTXTQUERY                           |
 ///
+/// The `pgvector` Cargo feature enables implementations for the `Vector`,
+/// `HalfVector` and `SparseVector` types, corresponding to the VECTOR, HALFVEC
+/// and SPARSEVEC types of the [pgvector](https://github.com/pgvector/pgvector)
+/// extension.
+///
+/// The `postgis` Cargo feature enables implementations for `Ewkb` and
+/// `geo_types::Geometry<f64>`, corresponding to the `geometry` and `geography`
+/// types of the [PostGIS](https://postgis.net) extension.
+///
 /// # Nullability
 ///
 /// In addition to the types listed above, `FromSql` is implemented for
@@ -462,6 +526,9 @@ impl WrongType {
 /// `FromSql` is implemented for `Vec<T>`, `Box<[T]>` and `[T; N]` where `T`
 /// implements `FromSql`, and corresponds to one-dimensional Postgres arrays.
 ///
+/// `PgArray<T>` supports Postgres arrays of any number of dimensions, and
+/// preserves their lower bounds.
+///
 /// **Note:** the impl for arrays only exist when the Cargo feature `array-impls`
 /// is enabled.
 pub trait FromSql<'a>: Sized {
@@ -495,6 +562,38 @@ pub trait FromSql<'a>: Sized {
         }
     }
 
+    /// Creates a new value of this type from the text format of a value of the
+    /// specified Postgres `Type`.
+    ///
+    /// The value may have been unescaped from an enclosing value, such as an
+    /// element of an array, in which case it is owned rather than borrowed.
+    ///
+    /// The caller of this method is responsible for ensuring that this type
+    /// is compatible with the Postgres `Type`.
+    ///
+    /// The types in this crate which implement [`FromSqlText`] delegate to it.
+    /// The default implementation returns an error.
+    #[allow(unused_variables)]
+    fn from_text(ty: &Type, raw: Cow<'a, str>) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        Err(format!(
+            "cannot decode `{}` from the text format",
+            type_name::<Self>()
+        )
+        .into())
+    }
+
+    /// A convenience function that delegates to `from_text` and `from_sql_null` depending on the
+    /// value of `raw`.
+    fn from_text_nullable(
+        ty: &Type,
+        raw: Option<Cow<'a, str>>,
+    ) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        match raw {
+            Some(raw) => Self::from_text(ty, raw),
+            None => Self::from_sql_null(ty),
+        }
+    }
+
     /// Determines if a value of this type can be created from the specified
     /// Postgres `Type`.
     fn accepts(ty: &Type) -> bool;
@@ -516,6 +615,10 @@ impl<'a, T: FromSql<'a>> FromSql<'a> for Option<T> {
         Ok(None)
     }
 
+    fn from_text(ty: &Type, raw: Cow<'a, str>) -> Result<Option<T>, Box<dyn Error + Sync + Send>> {
+        <T as FromSql>::from_text(ty, raw).map(Some)
+    }
+
     fn accepts(ty: &Type) -> bool {
         <T as FromSql>::accepts(ty)
     }
@@ -539,6 +642,19 @@ impl<'a, T: FromSql<'a>> FromSql<'a> for Vec<T> {
             .collect()
     }
 
+    fn from_text(ty: &Type, raw: Cow<'a, str>) -> Result<Vec<T>, Box<dyn Error + Sync + Send>> {
+        let member_type = text::member_type(ty);
+        let (dimensions, elements) = text::array_from_cow(ty, raw)?;
+        if dimensions.len() > 1 {
+            return Err("array contains too many dimensions".into());
+        }
+
+        elements
+            .into_iter()
+            .map(|e| T::from_text_nullable(member_type, e))
+            .collect()
+    }
+
     fn accepts(ty: &Type) -> bool {
         match *ty.kind() {
             Kind::Array(ref inner) => T::accepts(inner),
@@ -580,6 +696,28 @@ impl<'a, T: FromSql<'a>, const N: usize> FromSql<'a> for [T; N] {
         Ok(out)
     }
 
+    fn from_text(ty: &Type, raw: Cow<'a, str>) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        let values = Vec::<T>::from_text(ty, raw)?;
+        let len = values.len();
+        let mut values = values.into_iter();
+        let out = array_init::try_array_init(|i| {
+            values
+                .next()
+                .ok_or_else(|| -> Box<dyn Error + Sync + Send> {
+                    format!("too few elements in array (expected {}, got {})", N, i).into()
+                })
+        })?;
+        if len > N {
+            return Err(format!(
+                "excess elements in array (expected {}, got more than that)",
+                N,
+            )
+            .into());
+        }
+
+        Ok(out)
+    }
+
     fn accepts(ty: &Type) -> bool {
         match *ty.kind() {
             Kind::Array(ref inner) => T::accepts(inner),
@@ -593,6 +731,10 @@ impl<'a, T: FromSql<'a>> FromSql<'a> for Box<[T]> {
         Vec::<T>::from_sql(ty, raw).map(Vec::into_boxed_slice)
     }
 
+    fn from_text(ty: &Type, raw: Cow<'a, str>) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        Vec::<T>::from_text(ty, raw).map(Vec::into_boxed_slice)
+    }
+
     fn accepts(ty: &Type) -> bool {
         Vec::<T>::accepts(ty)
     }
@@ -604,6 +746,7 @@ impl<'a> FromSql<'a> for Vec<u8> {
     }
 
     accepts!(BYTEA);
+    from_text!();
 }
 
 impl<'a> FromSql<'a> for &'a [u8] {
@@ -622,6 +765,8 @@ impl<'a> FromSql<'a> for String {
     fn accepts(ty: &Type) -> bool {
         <&str as FromSql>::accepts(ty)
     }
+
+    from_text!();
 }
 
 impl<'a> FromSql<'a> for Box<str> {
@@ -634,6 +779,8 @@ impl<'a> FromSql<'a> for Box<str> {
     fn accepts(ty: &Type) -> bool {
         <&str as FromSql>::accepts(ty)
     }
+
+    from_text!();
 }
 
 impl<'a> FromSql<'a> for &'a str {
@@ -646,6 +793,10 @@ impl<'a> FromSql<'a> for &'a str {
         }
     }
 
+    fn from_text(ty: &Type, raw: Cow<'a, str>) -> Result<&'a str, Box<dyn Error + Sync + Send>> {
+        text::borrowed_from_text(ty, raw)
+    }
+
     fn accepts(ty: &Type) -> bool {
         match *ty {
             Type::VARCHAR | Type::TEXT | Type::BPCHAR | Type::NAME | Type::UNKNOWN => true,
@@ -670,6 +821,7 @@ macro_rules! simple_from {
             }
 
             accepts!($($expected),+);
+            from_text!();
         }
     }
 }
@@ -699,6 +851,8 @@ where
     fn accepts(ty: &Type) -> bool {
         ty.name() == "hstore"
     }
+
+    from_text!();
 }
 
 impl<'a> FromSql<'a> for SystemTime {
@@ -723,6 +877,7 @@ impl<'a> FromSql<'a> for SystemTime {
     }
 
     accepts!(TIMESTAMP, TIMESTAMPTZ);
+    from_text!();
 }
 
 impl<'a> FromSql<'a> for IpAddr {
@@ -732,6 +887,7 @@ impl<'a> FromSql<'a> for IpAddr {
     }
 
     accepts!(INET);
+    from_text!();
 }
 
 /// An enum representing the nullability of a Postgres value.
@@ -765,6 +921,13 @@ pub enum IsNull {
 /// | `HashMap<String, Option<String>>` | HSTORE                               |
 /// | `SystemTime`                      | TIMESTAMP, TIMESTAMP WITH TIME ZONE  |
 /// | `IpAddr`                          | INET                                 |
+/// | `Point`                           | POINT                                |
+/// | `Line`                            | LINE                                 |
+/// | `LineSegment`                     | LSEG                                 |
+/// | `Polygon`                         | POLYGON                              |
+/// | `Circle`                          | CIRCLE                               |
+/// | `TsVector`                        | TSVECTOR                             |
+/// | `TsQuery`                         | TSQUERY                              |
 ///
 /// In addition, some implementations are provided for types in third party
 /// crates. These are disabled by default; to opt into one of these
@@ -784,15 +947,33 @@ pub enum IsNull {
 /// | `time::OffsetDateTime`          | TIMESTAMP WITH TIME ZONE            |
 /// | `time::Date`                    | DATE                                |
 /// | `time::Time`                    | TIME                                |
+/// | `jiff::civil::DateTime`         | TIMESTAMP                           |
+/// | `jiff::Timestamp`               | TIMESTAMP WITH TIME ZONE            |
+/// | `jiff::Zoned`                   | TIMESTAMP WITH TIME ZONE            |
+/// | `jiff::civil::Date`             | DATE                                |
+/// | `jiff::civil::Time`             | TIME                                |
+/// | `jiff::Span`                    | INTERVAL                            |
 /// | `eui48::MacAddress`             | MACADDR                             |
 /// | `geo_types::Point<f64>`         | POINT                               |
 /// | `geo_types::Rect<f64>`          | BOX                                 |
 /// | `geo_types::LineString<f64>`    | PATH                                |
+/// | `geo_types::Line<f64>`          | LSEG                                |
+/// | `geo_types::Polygon<f64>`       | POLYGON                             |
+/// | `ndarray::ArrayD<T>`            | array types                         |
 /// | `serde_json::Value`             | JSON, JSONB                         |
 /// | `uuid::Uuid`                    | UUID                                |
 /// | `bit_vec::BitVec`               | BIT, VARBIT                         |
 /// | `eui48::MacAddress`             | MACADDR                             |
 ///
+/// The `pgvector` Cargo feature enables implementations for the `Vector`,
+/// `HalfVector` and `SparseVector` types, corresponding to the VECTOR, HALFVEC
+/// and SPARSEVEC types of the [pgvector](https://github.com/pgvector/pgvector)
+/// extension.
+///
+/// The `postgis` Cargo feature enables implementations for `Ewkb` and
+/// `geo_types::Geometry<f64>`, corresponding to the `geometry` and `geography`
+/// types of the [PostGIS](https://postgis.net) extension.
+///
 /// # Nullability
 ///
 /// In addition to the types listed above, `ToSql` is implemented for
@@ -805,6 +986,9 @@ pub enum IsNull {
 /// where `T` implements `ToSql` and `N` is const usize, and corresponds to one-dimensional
 /// Postgres arrays with an index offset of 1.
 ///
+/// `PgArray<T>` supports Postgres arrays of any number of dimensions and
+/// with any lower bounds.
+///
 /// **Note:** the impl for arrays only exist when the Cargo feature `array-impls`
 /// is enabled.
 pub trait ToSql: fmt::Debug {
@@ -846,7 +1030,7 @@ pub trait ToSql: fmt::Debug {
 /// Supported Postgres message format types
 ///
 /// Using Text format in a message assumes a Postgres `SERVER_ENCODING` of `UTF8`
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Format {
     /// Text format (UTF-8)
     Text,
@@ -854,7 +1038,7 @@ pub enum Format {
     Binary,
 }
 
-impl<'a, T> ToSql for &'a T
+impl<T> ToSql for &T
 where
     T: ToSql,
 {
@@ -903,7 +1087,7 @@ impl<T: ToSql> ToSql for Option<T> {
     to_sql_checked!();
 }
 
-impl<'a, T: ToSql> ToSql for &'a [T] {
+impl<T: ToSql> ToSql for &[T] {
     fn to_sql(&self, ty: &Type, w: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
         let member_type = match *ty.kind() {
             Kind::Array(ref member) => member,
@@ -944,7 +1128,7 @@ impl<'a, T: ToSql> ToSql for &'a [T] {
     to_sql_checked!();
 }
 
-impl<'a> ToSql for &'a [u8] {
+impl ToSql for &[u8] {
     fn to_sql(&self, _: &Type, w: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
         types::bytea_to_sql(self, w);
         Ok(IsNull::No)
@@ -1016,7 +1200,7 @@ impl ToSql for Vec<u8> {
     to_sql_checked!();
 }
 
-impl<'a> ToSql for &'a str {
+impl ToSql for &str {
     fn to_sql(&self, ty: &Type, w: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
         match ty.name() {
             "ltree" => types::ltree_to_sql(self, w),
@@ -1156,7 +1340,7 @@ impl ToSql for IpAddr {
 }
 
 fn downcast(len: usize) -> Result<i32, Box<dyn Error + Sync + Send>> {
-    if len > i32::max_value() as usize {
+    if len > i32::MAX as usize {
         Err("value too large to transmit".into())
     } else {
         Ok(len as i32)
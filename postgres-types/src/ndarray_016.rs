@@ -0,0 +1,128 @@
+use bytes::BytesMut;
+use ndarray_016::{ArrayD, IxDyn};
+use postgres_protocol::types::{self, ArrayDimension};
+use std::borrow::Cow;
+use std::convert::TryFrom;
+use std::error::Error;
+
+use crate::{
+    downcast, ArrayShapeError, FromSql, FromSqlText, FromSqlTextOwned, IsNull, Kind, PgArray,
+    ToSql, Type,
+};
+
+impl<T> TryFrom<ArrayD<T>> for PgArray<T> {
+    type Error = ArrayShapeError;
+
+    /// Creates an array with lower bounds of 1.
+    fn try_from(array: ArrayD<T>) -> Result<PgArray<T>, ArrayShapeError> {
+        if array.ndim() == 0 {
+            return Err(ArrayShapeError("zero-dimensional arrays are not supported"));
+        }
+        if array.is_empty() {
+            return Ok(PgArray::default());
+        }
+
+        let dimensions = array
+            .shape()
+            .iter()
+            .map(|&len| {
+                Ok(ArrayDimension {
+                    len: downcast(len).map_err(|_| ArrayShapeError("array too large"))?,
+                    lower_bound: 1,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        PgArray::new(dimensions, array.into_iter().collect())
+    }
+}
+
+impl<T> TryFrom<PgArray<T>> for ArrayD<T> {
+    type Error = ArrayShapeError;
+
+    /// Lower bounds are discarded.
+    fn try_from(array: PgArray<T>) -> Result<ArrayD<T>, ArrayShapeError> {
+        let mut shape = array
+            .dimensions()
+            .iter()
+            .map(|d| d.len as usize)
+            .collect::<Vec<_>>();
+        // an array without dimensions is empty rather than a single scalar
+        if shape.is_empty() {
+            shape.push(0);
+        }
+
+        ArrayD::from_shape_vec(IxDyn(&shape), array.into_elements())
+            .map_err(|_| ArrayShapeError("element count does not match the array dimensions"))
+    }
+}
+
+impl<'a, T: FromSql<'a>> FromSql<'a> for ArrayD<T> {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        let array = PgArray::<T>::from_sql(ty, raw)?;
+        Ok(ArrayD::try_from(array)?)
+    }
+
+    fn from_text(ty: &Type, raw: Cow<'a, str>) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        let array = PgArray::<T>::from_text(ty, raw)?;
+        Ok(ArrayD::try_from(array)?)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        PgArray::<T>::accepts(ty)
+    }
+}
+
+impl<'a, T: FromSqlTextOwned> FromSqlText<'a> for ArrayD<T> {
+    fn from_sql_text(ty: &Type, raw: &'a str) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        let array = PgArray::<T>::from_sql_text(ty, raw)?;
+        Ok(ArrayD::try_from(array)?)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <PgArray<T> as FromSqlText>::accepts(ty)
+    }
+}
+
+impl<T: ToSql> ToSql for ArrayD<T> {
+    fn to_sql(&self, ty: &Type, w: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        let member_type = match *ty.kind() {
+            Kind::Array(ref member) => member,
+            _ => panic!("expected array type"),
+        };
+
+        if self.ndim() == 0 {
+            return Err(ArrayShapeError("zero-dimensional arrays are not supported").into());
+        }
+        let dimensions = if self.is_empty() {
+            vec![]
+        } else {
+            self.shape()
+                .iter()
+                .map(|&len| {
+                    Ok(ArrayDimension {
+                        len: downcast(len)?,
+                        lower_bound: 1,
+                    })
+                })
+                .collect::<Result<Vec<_>, Box<dyn Error + Sync + Send>>>()?
+        };
+
+        types::array_to_sql(
+            dimensions,
+            member_type.oid(),
+            self.iter(),
+            |e, w| match e.to_sql(member_type, w)? {
+                IsNull::No => Ok(postgres_protocol::IsNull::No),
+                IsNull::Yes => Ok(postgres_protocol::IsNull::Yes),
+            },
+            w,
+        )?;
+        Ok(IsNull::No)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        PgArray::<T>::accepts(ty)
+    }
+
+    to_sql_checked!();
+}
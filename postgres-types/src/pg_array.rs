@@ -0,0 +1,261 @@
+//! Multi-dimensional Postgres arrays.
+
+use bytes::BytesMut;
+use fallible_iterator::FallibleIterator;
+use postgres_protocol::types::{self, ArrayDimension};
+use std::borrow::Cow;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+
+use crate::text::{self, FromSqlTextOwned};
+use crate::{downcast, FromSql, FromSqlText, IsNull, Kind, ToSql, Type};
+
+/// A Postgres array of any number of dimensions.
+///
+/// Unlike `Vec<T>`, which only supports one-dimensional arrays, `PgArray` preserves the dimensions of the array along
+/// with their lower bounds. Elements are stored in row-major order. Use `Option<T>` as the element type for arrays
+/// which may contain `NULL` elements.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgArray<T> {
+    dimensions: Vec<ArrayDimension>,
+    elements: Vec<T>,
+}
+
+impl<T> PgArray<T> {
+    /// Creates a new array from its dimensions and its elements in row-major order.
+    ///
+    /// Returns an error if a dimension has a negative length, or if the number of elements does not match the
+    /// dimensions.
+    pub fn new(
+        dimensions: Vec<ArrayDimension>,
+        elements: Vec<T>,
+    ) -> Result<PgArray<T>, ArrayShapeError> {
+        let mut len = 1usize;
+        for dimension in &dimensions {
+            if dimension.len < 0 {
+                return Err(ArrayShapeError("negative dimension length"));
+            }
+            len = len
+                .checked_mul(dimension.len as usize)
+                .ok_or(ArrayShapeError("array too large"))?;
+        }
+        // an array without dimensions is empty
+        if dimensions.is_empty() {
+            len = 0;
+        }
+
+        if len != elements.len() {
+            return Err(ArrayShapeError(
+                "element count does not match the array dimensions",
+            ));
+        }
+
+        Ok(PgArray {
+            dimensions,
+            elements,
+        })
+    }
+
+    /// Creates a two-dimensional array from its rows, with lower bounds of 1.
+    ///
+    /// Returns an error if the rows are not all the same length.
+    pub fn from_nested(rows: Vec<Vec<T>>) -> Result<PgArray<T>, ArrayShapeError> {
+        let columns = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|row| row.len() != columns) {
+            return Err(ArrayShapeError("array rows have different lengths"));
+        }
+        if columns == 0 {
+            return Ok(PgArray::default());
+        }
+
+        let too_large = |_| ArrayShapeError("array too large");
+        let dimensions = vec![
+            ArrayDimension {
+                len: downcast(rows.len()).map_err(too_large)?,
+                lower_bound: 1,
+            },
+            ArrayDimension {
+                len: downcast(columns).map_err(too_large)?,
+                lower_bound: 1,
+            },
+        ];
+        PgArray::new(dimensions, rows.into_iter().flatten().collect())
+    }
+
+    /// Returns the dimensions of the array.
+    pub fn dimensions(&self) -> &[ArrayDimension] {
+        &self.dimensions
+    }
+
+    /// Returns the elements of the array in row-major order.
+    pub fn elements(&self) -> &[T] {
+        &self.elements
+    }
+
+    /// Consumes the array, returning its elements in row-major order.
+    pub fn into_elements(self) -> Vec<T> {
+        self.elements
+    }
+
+    /// Returns the element at the specified index, using the lower bounds of the array's dimensions.
+    ///
+    /// Returns `None` if the index does not have one entry per dimension or is out of bounds.
+    pub fn get(&self, index: &[i32]) -> Option<&T> {
+        if index.len() != self.dimensions.len() {
+            return None;
+        }
+
+        let mut offset = 0;
+        for (dimension, &idx) in self.dimensions.iter().zip(index) {
+            let idx = idx.checked_sub(dimension.lower_bound)?;
+            if idx < 0 || idx >= dimension.len {
+                return None;
+            }
+            offset = offset * dimension.len as usize + idx as usize;
+        }
+
+        self.elements.get(offset)
+    }
+
+    /// Converts a two-dimensional array into a `Vec` of its rows.
+    ///
+    /// Lower bounds are discarded. An empty array converts into an empty `Vec`. Returns an error if the array has any
+    /// other number of dimensions.
+    pub fn into_nested(self) -> Result<Vec<Vec<T>>, ArrayShapeError> {
+        match *self.dimensions {
+            [] => Ok(vec![]),
+            [rows, columns] => {
+                let mut elements = self.elements.into_iter();
+                Ok((0..rows.len)
+                    .map(|_| elements.by_ref().take(columns.len as usize).collect())
+                    .collect())
+            }
+            _ => Err(ArrayShapeError("array is not two-dimensional")),
+        }
+    }
+}
+
+impl<T> Default for PgArray<T> {
+    fn default() -> PgArray<T> {
+        PgArray {
+            dimensions: vec![],
+            elements: vec![],
+        }
+    }
+}
+
+impl<T> TryFrom<Vec<T>> for PgArray<T> {
+    type Error = ArrayShapeError;
+
+    /// Creates a one-dimensional array with a lower bound of 1.
+    fn try_from(elements: Vec<T>) -> Result<PgArray<T>, ArrayShapeError> {
+        if elements.is_empty() {
+            return Ok(PgArray::default());
+        }
+
+        let dimension = ArrayDimension {
+            len: downcast(elements.len()).map_err(|_| ArrayShapeError("array too large"))?,
+            lower_bound: 1,
+        };
+        PgArray::new(vec![dimension], elements)
+    }
+}
+
+impl<'a, T: FromSql<'a>> FromSql<'a> for PgArray<T> {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        let member_type = match *ty.kind() {
+            Kind::Array(ref member) => member,
+            _ => panic!("expected array type"),
+        };
+
+        let array = types::array_from_sql(raw)?;
+        let dimensions = array.dimensions().collect()?;
+        let elements = array
+            .values()
+            .map(|v| T::from_sql_nullable(member_type, v))
+            .collect()?;
+
+        Ok(PgArray::new(dimensions, elements)?)
+    }
+
+    fn from_text(ty: &Type, raw: Cow<'a, str>) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        let member_type = text::member_type(ty);
+        let (dimensions, elements) = text::array_from_cow(ty, raw)?;
+        let elements = elements
+            .into_iter()
+            .map(|e| T::from_text_nullable(member_type, e))
+            .collect::<Result<_, _>>()?;
+
+        Ok(PgArray::new(dimensions, elements)?)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        match *ty.kind() {
+            Kind::Array(ref inner) => T::accepts(inner),
+            _ => false,
+        }
+    }
+}
+
+impl<'a, T: FromSqlTextOwned> FromSqlText<'a> for PgArray<T> {
+    fn from_sql_text(ty: &Type, raw: &'a str) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        let member_type = text::member_type(ty);
+        let (dimensions, elements) = text::array_from_text(ty, raw)?;
+        let elements = elements
+            .iter()
+            .map(|e| T::from_sql_text_nullable(member_type, e.as_deref()))
+            .collect::<Result<_, _>>()?;
+
+        Ok(PgArray::new(dimensions, elements)?)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        match *ty.kind() {
+            Kind::Array(ref inner) => T::accepts(inner),
+            _ => false,
+        }
+    }
+}
+
+impl<T: ToSql> ToSql for PgArray<T> {
+    fn to_sql(&self, ty: &Type, w: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        let member_type = match *ty.kind() {
+            Kind::Array(ref member) => member,
+            _ => panic!("expected array type"),
+        };
+
+        types::array_to_sql(
+            self.dimensions.iter().cloned(),
+            member_type.oid(),
+            self.elements.iter(),
+            |e, w| match e.to_sql(member_type, w)? {
+                IsNull::No => Ok(postgres_protocol::IsNull::No),
+                IsNull::Yes => Ok(postgres_protocol::IsNull::Yes),
+            },
+            w,
+        )?;
+        Ok(IsNull::No)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        match *ty.kind() {
+            Kind::Array(ref member) => T::accepts(member),
+            _ => false,
+        }
+    }
+
+    to_sql_checked!();
+}
+
+/// An error indicating that the elements of an array do not match its shape.
+#[derive(Debug)]
+pub struct ArrayShapeError(pub(crate) &'static str);
+
+impl fmt::Display for ArrayShapeError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str(self.0)
+    }
+}
+
+impl Error for ArrayShapeError {}
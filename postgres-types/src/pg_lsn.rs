@@ -65,6 +65,7 @@ impl<'a> FromSql<'a> for PgLsn {
     }
 
     accepts!(PG_LSN);
+    from_text!();
 }
 
 impl ToSql for PgLsn {
@@ -0,0 +1,305 @@
+//! Types of the [pgvector](https://github.com/pgvector/pgvector) extension.
+
+use bytes::BytesMut;
+use fallible_iterator::FallibleIterator;
+use half_2::f16;
+use postgres_protocol::types;
+use std::error::Error;
+
+use crate::{FromSql, FromSqlText, IsNull, ToSql, Type};
+
+/// pgvector `VECTOR` type.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Vector(Vec<f32>);
+
+impl Vector {
+    /// Returns the elements of the vector.
+    pub fn as_slice(&self) -> &[f32] {
+        &self.0
+    }
+
+    /// Returns the elements of the vector as a `Vec`.
+    pub fn to_vec(&self) -> Vec<f32> {
+        self.0.clone()
+    }
+}
+
+impl From<Vec<f32>> for Vector {
+    fn from(v: Vec<f32>) -> Vector {
+        Vector(v)
+    }
+}
+
+impl From<&[f32]> for Vector {
+    fn from(v: &[f32]) -> Vector {
+        Vector(v.to_vec())
+    }
+}
+
+impl From<Vector> for Vec<f32> {
+    fn from(v: Vector) -> Vec<f32> {
+        v.0
+    }
+}
+
+impl<'a> FromSql<'a> for Vector {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        Ok(Vector(types::vector_from_sql(raw)?.collect()))
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        ty.name() == "vector"
+    }
+
+    from_text!();
+}
+
+impl<'a> FromSqlText<'a> for Vector {
+    fn from_sql_text(_: &Type, raw: &str) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        vector_from_text(raw).map(Vector)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        ty.name() == "vector"
+    }
+}
+
+impl ToSql for Vector {
+    fn to_sql(&self, _: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        types::vector_to_sql(&self.0, out)?;
+        Ok(IsNull::No)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        ty.name() == "vector"
+    }
+
+    to_sql_checked!();
+}
+
+/// pgvector `HALFVEC` type.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct HalfVector(Vec<f16>);
+
+impl HalfVector {
+    /// Creates a vector from single precision values, rounding them to half precision.
+    pub fn from_f32_slice(v: &[f32]) -> HalfVector {
+        HalfVector(v.iter().map(|&f| f16::from_f32(f)).collect())
+    }
+
+    /// Returns the elements of the vector.
+    pub fn as_slice(&self) -> &[f16] {
+        &self.0
+    }
+
+    /// Returns the elements of the vector as a `Vec`.
+    pub fn to_vec(&self) -> Vec<f16> {
+        self.0.clone()
+    }
+}
+
+impl From<Vec<f16>> for HalfVector {
+    fn from(v: Vec<f16>) -> HalfVector {
+        HalfVector(v)
+    }
+}
+
+impl From<&[f16]> for HalfVector {
+    fn from(v: &[f16]) -> HalfVector {
+        HalfVector(v.to_vec())
+    }
+}
+
+impl From<HalfVector> for Vec<f16> {
+    fn from(v: HalfVector) -> Vec<f16> {
+        v.0
+    }
+}
+
+impl<'a> FromSql<'a> for HalfVector {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        let values = types::halfvec_from_sql(raw)?;
+        Ok(HalfVector(values.map(f16::from_bits).collect()))
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        ty.name() == "halfvec"
+    }
+
+    from_text!();
+}
+
+impl<'a> FromSqlText<'a> for HalfVector {
+    fn from_sql_text(_: &Type, raw: &str) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        vector_from_text(raw).map(|v| HalfVector::from_f32_slice(&v))
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        ty.name() == "halfvec"
+    }
+}
+
+impl ToSql for HalfVector {
+    fn to_sql(&self, _: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        types::halfvec_to_sql(self.0.iter().map(|f| f.to_bits()), out)?;
+        Ok(IsNull::No)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        ty.name() == "halfvec"
+    }
+
+    to_sql_checked!();
+}
+
+/// pgvector `SPARSEVEC` type.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SparseVector {
+    dim: usize,
+    indices: Vec<u32>,
+    values: Vec<f32>,
+}
+
+impl SparseVector {
+    /// Creates a sparse vector from the zero-based indices and values of its nonzero elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `indices` and `values` have different lengths, or if any index is not less than `dim`.
+    pub fn new(dim: usize, indices: Vec<u32>, values: Vec<f32>) -> SparseVector {
+        assert_eq!(
+            indices.len(),
+            values.len(),
+            "indices and values have different lengths"
+        );
+        assert!(
+            indices.iter().all(|&i| (i as usize) < dim),
+            "index out of bounds for a vector of {} dimensions",
+            dim
+        );
+        SparseVector {
+            dim,
+            indices,
+            values,
+        }
+    }
+
+    /// Creates a sparse vector from a dense one.
+    pub fn from_dense(v: &[f32]) -> SparseVector {
+        let (indices, values) = v
+            .iter()
+            .enumerate()
+            .filter(|(_, &value)| value != 0.)
+            .map(|(i, &value)| (i as u32, value))
+            .unzip();
+        SparseVector::new(v.len(), indices, values)
+    }
+
+    /// Returns the number of dimensions of the vector.
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    /// Returns the zero-based indices of the nonzero elements of the vector.
+    pub fn indices(&self) -> &[u32] {
+        &self.indices
+    }
+
+    /// Returns the values of the nonzero elements of the vector.
+    pub fn values(&self) -> &[f32] {
+        &self.values
+    }
+
+    // like `new`, but returns an error for out of bounds indices from the server
+    fn from_parts(
+        dim: usize,
+        indices: Vec<u32>,
+        values: Vec<f32>,
+    ) -> Result<SparseVector, Box<dyn Error + Sync + Send>> {
+        if indices.iter().any(|&i| i as usize >= dim) {
+            return Err("sparsevec index out of bounds".into());
+        }
+        Ok(SparseVector::new(dim, indices, values))
+    }
+
+    /// Returns the vector in dense form.
+    pub fn to_vec(&self) -> Vec<f32> {
+        let mut v = vec![0.; self.dim];
+        for (&i, &value) in self.indices.iter().zip(&self.values) {
+            v[i as usize] = value;
+        }
+        v
+    }
+}
+
+impl<'a> FromSql<'a> for SparseVector {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        let vector = types::sparsevec_from_sql(raw)?;
+        let (indices, values) = vector.entries().fold(
+            (vec![], vec![]),
+            |(mut indices, mut values), (index, value)| {
+                indices.push(index);
+                values.push(value);
+                Ok((indices, values))
+            },
+        )?;
+        SparseVector::from_parts(vector.dim() as usize, indices, values)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        ty.name() == "sparsevec"
+    }
+
+    from_text!();
+}
+
+// the text format is `{index:value,...}/dim`, with one-based indices
+impl<'a> FromSqlText<'a> for SparseVector {
+    fn from_sql_text(_: &Type, raw: &str) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        let (entries, dim) = raw.rsplit_once('/').ok_or("invalid sparsevec")?;
+        let entries = entries
+            .strip_prefix('{')
+            .and_then(|e| e.strip_suffix('}'))
+            .ok_or("invalid sparsevec")?;
+
+        let mut indices = vec![];
+        let mut values = vec![];
+        for entry in entries.split(',').filter(|e| !e.is_empty()) {
+            let (index, value) = entry.split_once(':').ok_or("invalid sparsevec")?;
+            let index = index.trim().parse::<u32>()?;
+            indices.push(index.checked_sub(1).ok_or("invalid sparsevec index")?);
+            values.push(value.trim().parse()?);
+        }
+        SparseVector::from_parts(dim.parse()?, indices, values)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        ty.name() == "sparsevec"
+    }
+}
+
+impl ToSql for SparseVector {
+    fn to_sql(&self, _: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        types::sparsevec_to_sql(self.dim, &self.indices, &self.values, out)?;
+        Ok(IsNull::No)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        ty.name() == "sparsevec"
+    }
+
+    to_sql_checked!();
+}
+
+// the text format of both `vector` and `halfvec` is `[1,2,3]`
+fn vector_from_text(raw: &str) -> Result<Vec<f32>, Box<dyn Error + Sync + Send>> {
+    let values = raw
+        .strip_prefix('[')
+        .and_then(|v| v.strip_suffix(']'))
+        .ok_or("invalid vector")?;
+    values
+        .split(',')
+        .filter(|v| !v.is_empty())
+        .map(|v| Ok(v.trim().parse()?))
+        .collect()
+}
@@ -0,0 +1,451 @@
+//! PostGIS `geometry` and `geography` types, encoded as EWKB.
+
+use bytes::{BufMut, BytesMut};
+use geo_types_0_7::{
+    Coord, Geometry, GeometryCollection, LineString, MultiLineString, MultiPoint, MultiPolygon,
+    Point, Polygon,
+};
+use std::convert::TryFrom;
+use std::error::Error;
+use std::slice;
+
+use crate::text::hex_decode;
+use crate::{FromSql, FromSqlText, IsNull, ToSql, Type};
+
+const POINT: u32 = 1;
+const LINE_STRING: u32 = 2;
+const POLYGON: u32 = 3;
+const MULTI_POINT: u32 = 4;
+const MULTI_LINE_STRING: u32 = 5;
+const MULTI_POLYGON: u32 = 6;
+const GEOMETRY_COLLECTION: u32 = 7;
+
+const Z_FLAG: u32 = 0x8000_0000;
+const M_FLAG: u32 = 0x4000_0000;
+const SRID_FLAG: u32 = 0x2000_0000;
+
+/// A PostGIS `geometry` or `geography` value.
+///
+/// `geo-types` geometries are two-dimensional, so the Z and M values of the coordinates, if present, are stored
+/// separately, in the order the coordinates appear in the geometry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ewkb {
+    /// The geometry.
+    pub geometry: Geometry<f64>,
+    /// The spatial reference system identifier of the geometry.
+    pub srid: Option<i32>,
+    /// The Z values of the coordinates of the geometry.
+    pub z: Option<Vec<f64>>,
+    /// The M values of the coordinates of the geometry.
+    pub m: Option<Vec<f64>>,
+}
+
+impl Ewkb {
+    /// Creates a new two-dimensional value.
+    pub fn new(geometry: impl Into<Geometry<f64>>, srid: Option<i32>) -> Ewkb {
+        Ewkb {
+            geometry: geometry.into(),
+            srid,
+            z: None,
+            m: None,
+        }
+    }
+}
+
+impl<'a> FromSql<'a> for Ewkb {
+    fn from_sql(_: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        let mut reader = Reader {
+            buf: raw,
+            dimensions: None,
+            z: vec![],
+            m: vec![],
+        };
+        let (geometry, srid) = reader.read_geometry()?;
+        if !reader.buf.is_empty() {
+            return Err("invalid buffer size".into());
+        }
+
+        let (z, m) = reader.dimensions.unwrap_or((false, false));
+        Ok(Ewkb {
+            geometry,
+            srid,
+            z: if z { Some(reader.z) } else { None },
+            m: if m { Some(reader.m) } else { None },
+        })
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        matches!(ty.name(), "geometry" | "geography")
+    }
+
+    from_text!();
+}
+
+// the text format is the hex encoded EWKB
+impl<'a> FromSqlText<'a> for Ewkb {
+    fn from_sql_text(ty: &Type, raw: &'a str) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        Ewkb::from_sql(ty, &hex_decode(raw)?)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <Ewkb as FromSql>::accepts(ty)
+    }
+}
+
+impl ToSql for Ewkb {
+    fn to_sql(&self, _: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        let mut writer = Writer {
+            buf: out,
+            z: self.z.as_ref().map(|z| z.iter()),
+            m: self.m.as_ref().map(|m| m.iter()),
+        };
+        writer.write_geometry(&self.geometry, self.srid)?;
+
+        let excess = |values: Option<slice::Iter<'_, f64>>| values.map_or(0, |v| v.len()) != 0;
+        if excess(writer.z) || excess(writer.m) {
+            return Err("more Z or M values than coordinates".into());
+        }
+        Ok(IsNull::No)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        matches!(ty.name(), "geometry" | "geography")
+    }
+
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for Geometry<f64> {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        Ewkb::from_sql(ty, raw).map(|ewkb| ewkb.geometry)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <Ewkb as FromSql>::accepts(ty)
+    }
+
+    from_text!();
+}
+
+impl<'a> FromSqlText<'a> for Geometry<f64> {
+    fn from_sql_text(ty: &Type, raw: &'a str) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        Ewkb::from_sql_text(ty, raw).map(|ewkb| ewkb.geometry)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <Ewkb as FromSql>::accepts(ty)
+    }
+}
+
+impl ToSql for Geometry<f64> {
+    fn to_sql(&self, _: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        let mut writer = Writer {
+            buf: out,
+            z: None,
+            m: None,
+        };
+        writer.write_geometry(self, None)?;
+        Ok(IsNull::No)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <Ewkb as ToSql>::accepts(ty)
+    }
+
+    to_sql_checked!();
+}
+
+struct Reader<'a> {
+    buf: &'a [u8],
+    dimensions: Option<(bool, bool)>,
+    z: Vec<f64>,
+    m: Vec<f64>,
+}
+
+impl<'a> Reader<'a> {
+    fn read_bytes<const N: usize>(&mut self) -> Result<[u8; N], Box<dyn Error + Sync + Send>> {
+        if self.buf.len() < N {
+            return Err("unexpected EOF".into());
+        }
+        let (bytes, rest) = self.buf.split_at(N);
+        self.buf = rest;
+        Ok(<[u8; N]>::try_from(bytes).unwrap())
+    }
+
+    fn read_u32(&mut self, little_endian: bool) -> Result<u32, Box<dyn Error + Sync + Send>> {
+        let bytes = self.read_bytes()?;
+        Ok(if little_endian {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        })
+    }
+
+    fn read_f64(&mut self, little_endian: bool) -> Result<f64, Box<dyn Error + Sync + Send>> {
+        let bytes = self.read_bytes()?;
+        Ok(if little_endian {
+            f64::from_le_bytes(bytes)
+        } else {
+            f64::from_be_bytes(bytes)
+        })
+    }
+
+    fn read_geometry(
+        &mut self,
+    ) -> Result<(Geometry<f64>, Option<i32>), Box<dyn Error + Sync + Send>> {
+        let little_endian = match self.read_bytes::<1>()?[0] {
+            0 => false,
+            1 => true,
+            _ => return Err("invalid byte order".into()),
+        };
+
+        let type_ = self.read_u32(little_endian)?;
+        let mut z = type_ & Z_FLAG != 0;
+        let mut m = type_ & M_FLAG != 0;
+        // ISO WKB encodes dimensions in the type code instead of flags
+        let mut base = type_ & 0x0fff_ffff;
+        match base / 1000 {
+            0 => {}
+            1 => z = true,
+            2 => m = true,
+            3 => {
+                z = true;
+                m = true;
+            }
+            _ => return Err("invalid geometry type".into()),
+        }
+        base %= 1000;
+
+        match self.dimensions {
+            Some(dimensions) if dimensions != (z, m) => {
+                return Err("mixed coordinate dimensions".into())
+            }
+            _ => self.dimensions = Some((z, m)),
+        }
+
+        let srid = if type_ & SRID_FLAG != 0 {
+            Some(self.read_u32(little_endian)? as i32)
+        } else {
+            None
+        };
+
+        let geometry = match base {
+            POINT => Geometry::Point(Point(self.read_coord(little_endian)?)),
+            LINE_STRING => Geometry::LineString(self.read_line_string(little_endian)?),
+            POLYGON => Geometry::Polygon(self.read_polygon(little_endian)?),
+            MULTI_POINT => Geometry::MultiPoint(MultiPoint(self.read_children(
+                little_endian,
+                |g| match g {
+                    Geometry::Point(p) => Some(p),
+                    _ => None,
+                },
+            )?)),
+            MULTI_LINE_STRING => Geometry::MultiLineString(MultiLineString(self.read_children(
+                little_endian,
+                |g| match g {
+                    Geometry::LineString(l) => Some(l),
+                    _ => None,
+                },
+            )?)),
+            MULTI_POLYGON => Geometry::MultiPolygon(MultiPolygon(self.read_children(
+                little_endian,
+                |g| match g {
+                    Geometry::Polygon(p) => Some(p),
+                    _ => None,
+                },
+            )?)),
+            GEOMETRY_COLLECTION => Geometry::GeometryCollection(GeometryCollection(
+                self.read_children(little_endian, Some)?,
+            )),
+            _ => return Err(format!("unsupported geometry type {}", base).into()),
+        };
+
+        Ok((geometry, srid))
+    }
+
+    fn read_children<T>(
+        &mut self,
+        little_endian: bool,
+        f: impl Fn(Geometry<f64>) -> Option<T>,
+    ) -> Result<Vec<T>, Box<dyn Error + Sync + Send>> {
+        let len = self.read_u32(little_endian)?;
+        (0..len)
+            .map(|_| {
+                let (geometry, _) = self.read_geometry()?;
+                f(geometry).ok_or_else(|| "unexpected geometry type in collection".into())
+            })
+            .collect()
+    }
+
+    fn read_polygon(
+        &mut self,
+        little_endian: bool,
+    ) -> Result<Polygon<f64>, Box<dyn Error + Sync + Send>> {
+        let len = self.read_u32(little_endian)?;
+        let mut rings = (0..len)
+            .map(|_| self.read_line_string(little_endian))
+            .collect::<Result<Vec<_>, _>>()?;
+        if rings.is_empty() {
+            return Ok(Polygon::new(LineString(vec![]), vec![]));
+        }
+        let exterior = rings.remove(0);
+        Ok(Polygon::new(exterior, rings))
+    }
+
+    fn read_line_string(
+        &mut self,
+        little_endian: bool,
+    ) -> Result<LineString<f64>, Box<dyn Error + Sync + Send>> {
+        let len = self.read_u32(little_endian)?;
+        (0..len)
+            .map(|_| self.read_coord(little_endian))
+            .collect::<Result<_, _>>()
+            .map(LineString)
+    }
+
+    fn read_coord(
+        &mut self,
+        little_endian: bool,
+    ) -> Result<Coord<f64>, Box<dyn Error + Sync + Send>> {
+        let x = self.read_f64(little_endian)?;
+        let y = self.read_f64(little_endian)?;
+        let (z, m) = self.dimensions.unwrap_or((false, false));
+        if z {
+            let z = self.read_f64(little_endian)?;
+            self.z.push(z);
+        }
+        if m {
+            let m = self.read_f64(little_endian)?;
+            self.m.push(m);
+        }
+        Ok(Coord { x, y })
+    }
+}
+
+struct Writer<'a, 'b> {
+    buf: &'a mut BytesMut,
+    z: Option<slice::Iter<'b, f64>>,
+    m: Option<slice::Iter<'b, f64>>,
+}
+
+impl<'a, 'b> Writer<'a, 'b> {
+    fn write_header(&mut self, base: u32, srid: Option<i32>) {
+        let mut type_ = base;
+        if self.z.is_some() {
+            type_ |= Z_FLAG;
+        }
+        if self.m.is_some() {
+            type_ |= M_FLAG;
+        }
+        if srid.is_some() {
+            type_ |= SRID_FLAG;
+        }
+
+        // little endian
+        self.buf.put_u8(1);
+        self.buf.put_u32_le(type_);
+        if let Some(srid) = srid {
+            self.buf.put_i32_le(srid);
+        }
+    }
+
+    fn write_geometry(
+        &mut self,
+        geometry: &Geometry<f64>,
+        srid: Option<i32>,
+    ) -> Result<(), Box<dyn Error + Sync + Send>> {
+        match geometry {
+            Geometry::Point(point) => {
+                self.write_header(POINT, srid);
+                self.write_coord(&point.0)?;
+            }
+            Geometry::Line(line) => {
+                self.write_header(LINE_STRING, srid);
+                self.write_coords(&[line.start, line.end])?;
+            }
+            Geometry::LineString(line_string) => {
+                self.write_header(LINE_STRING, srid);
+                self.write_coords(&line_string.0)?;
+            }
+            Geometry::Polygon(polygon) => self.write_polygon(polygon, srid)?,
+            Geometry::Rect(rect) => self.write_polygon(&rect.to_polygon(), srid)?,
+            Geometry::Triangle(triangle) => self.write_polygon(&triangle.to_polygon(), srid)?,
+            Geometry::MultiPoint(multi_point) => {
+                self.write_header(MULTI_POINT, srid);
+                self.write_len(multi_point.0.len())?;
+                for point in &multi_point.0 {
+                    self.write_geometry(&Geometry::Point(*point), None)?;
+                }
+            }
+            Geometry::MultiLineString(multi_line_string) => {
+                self.write_header(MULTI_LINE_STRING, srid);
+                self.write_len(multi_line_string.0.len())?;
+                for line_string in &multi_line_string.0 {
+                    self.write_header(LINE_STRING, None);
+                    self.write_coords(&line_string.0)?;
+                }
+            }
+            Geometry::MultiPolygon(multi_polygon) => {
+                self.write_header(MULTI_POLYGON, srid);
+                self.write_len(multi_polygon.0.len())?;
+                for polygon in &multi_polygon.0 {
+                    self.write_polygon(polygon, None)?;
+                }
+            }
+            Geometry::GeometryCollection(collection) => {
+                self.write_header(GEOMETRY_COLLECTION, srid);
+                self.write_len(collection.0.len())?;
+                for geometry in &collection.0 {
+                    self.write_geometry(geometry, None)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn write_polygon(
+        &mut self,
+        polygon: &Polygon<f64>,
+        srid: Option<i32>,
+    ) -> Result<(), Box<dyn Error + Sync + Send>> {
+        self.write_header(POLYGON, srid);
+        let rings = Some(polygon.exterior())
+            .filter(|exterior| !exterior.0.is_empty())
+            .into_iter()
+            .chain(polygon.interiors())
+            .collect::<Vec<_>>();
+        self.write_len(rings.len())?;
+        for ring in rings {
+            self.write_coords(&ring.0)?;
+        }
+        Ok(())
+    }
+
+    fn write_len(&mut self, len: usize) -> Result<(), Box<dyn Error + Sync + Send>> {
+        let len = u32::try_from(len).map_err(|_| "value too large to transmit")?;
+        self.buf.put_u32_le(len);
+        Ok(())
+    }
+
+    fn write_coords(&mut self, coords: &[Coord<f64>]) -> Result<(), Box<dyn Error + Sync + Send>> {
+        self.write_len(coords.len())?;
+        for coord in coords {
+            self.write_coord(coord)?;
+        }
+        Ok(())
+    }
+
+    fn write_coord(&mut self, coord: &Coord<f64>) -> Result<(), Box<dyn Error + Sync + Send>> {
+        self.buf.put_f64_le(coord.x);
+        self.buf.put_f64_le(coord.y);
+        if let Some(z) = &mut self.z {
+            let z = z.next().ok_or("fewer Z values than coordinates")?;
+            self.buf.put_f64_le(*z);
+        }
+        if let Some(m) = &mut self.m {
+            let m = m.next().ok_or("fewer M values than coordinates")?;
+            self.buf.put_f64_le(*m);
+        }
+        Ok(())
+    }
+}
@@ -1,7 +1,8 @@
-use crate::{FromSql, IsNull, ToSql, Type};
+use crate::{text, FromSql, FromSqlText, IsNull, ToSql, Type};
 use bytes::{BufMut, BytesMut};
 use serde_1::{Deserialize, Serialize};
 use serde_json_1::Value;
+use std::borrow::Cow;
 use std::error::Error;
 use std::fmt::Debug;
 use std::io::Read;
@@ -28,6 +29,24 @@ where
             .map_err(Into::into)
     }
 
+    fn from_text(ty: &Type, raw: Cow<'a, str>) -> Result<Json<T>, Box<dyn Error + Sync + Send>> {
+        text::borrowed_from_text(ty, raw)
+    }
+
+    accepts!(JSON, JSONB);
+}
+
+// unlike the binary format, the text format of JSONB has no version prefix
+impl<'a, T> FromSqlText<'a> for Json<T>
+where
+    T: Deserialize<'a>,
+{
+    fn from_sql_text(_: &Type, raw: &'a str) -> Result<Json<T>, Box<dyn Error + Sync + Send>> {
+        serde_json_1::de::from_str(raw)
+            .map(Json)
+            .map_err(Into::into)
+    }
+
     accepts!(JSON, JSONB);
 }
 
@@ -56,6 +75,15 @@ impl<'a> FromSql<'a> for Value {
         Json::<Value>::from_sql(ty, raw).map(|json| json.0)
     }
 
+    accepts!(JSON, JSONB);
+    from_text!();
+}
+
+impl<'a> FromSqlText<'a> for Value {
+    fn from_sql_text(ty: &Type, raw: &str) -> Result<Value, Box<dyn Error + Sync + Send>> {
+        Json::<Value>::from_sql_text(ty, raw).map(|json| json.0)
+    }
+
     accepts!(JSON, JSONB);
 }
 
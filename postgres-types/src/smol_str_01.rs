@@ -2,7 +2,7 @@ use bytes::BytesMut;
 use smol_str_01::SmolStr;
 use std::error::Error;
 
-use crate::{FromSql, IsNull, ToSql, Type};
+use crate::{FromSql, FromSqlText, IsNull, ToSql, Type};
 
 impl<'a> FromSql<'a> for SmolStr {
     fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<SmolStr, Box<dyn Error + Sync + Send>> {
@@ -12,6 +12,18 @@ impl<'a> FromSql<'a> for SmolStr {
     fn accepts(ty: &Type) -> bool {
         <&str as FromSql>::accepts(ty)
     }
+
+    from_text!();
+}
+
+impl<'a> FromSqlText<'a> for SmolStr {
+    fn from_sql_text(_: &Type, raw: &'a str) -> Result<SmolStr, Box<dyn Error + Sync + Send>> {
+        Ok(SmolStr::from(raw))
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <&str as FromSql>::accepts(ty)
+    }
 }
 
 impl ToSql for SmolStr {
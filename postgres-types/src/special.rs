@@ -1,9 +1,9 @@
 use bytes::BytesMut;
 use postgres_protocol::types;
+use std::borrow::Cow;
 use std::error::Error;
-use std::{i32, i64};
 
-use crate::{FromSql, IsNull, ToSql, Type};
+use crate::{FromSql, FromSqlText, IsNull, ToSql, Type};
 
 /// A wrapper that can be used to represent infinity with `Type::Date` types.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -25,6 +25,28 @@ impl<'a, T: FromSql<'a>> FromSql<'a> for Date<T> {
         }
     }
 
+    fn from_text(ty: &Type, raw: Cow<'a, str>) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        match &*raw {
+            "infinity" => Ok(Date::PosInfinity),
+            "-infinity" => Ok(Date::NegInfinity),
+            _ => T::from_text(ty, raw).map(Date::Value),
+        }
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        *ty == Type::DATE && T::accepts(ty)
+    }
+}
+
+impl<'a, T: FromSqlText<'a>> FromSqlText<'a> for Date<T> {
+    fn from_sql_text(ty: &Type, raw: &'a str) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        match raw {
+            "infinity" => Ok(Date::PosInfinity),
+            "-infinity" => Ok(Date::NegInfinity),
+            _ => T::from_sql_text(ty, raw).map(Date::Value),
+        }
+    }
+
     fn accepts(ty: &Type) -> bool {
         *ty == Type::DATE && T::accepts(ty)
     }
@@ -74,6 +96,28 @@ impl<'a, T: FromSql<'a>> FromSql<'a> for Timestamp<T> {
         }
     }
 
+    fn from_text(ty: &Type, raw: Cow<'a, str>) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        match &*raw {
+            "infinity" => Ok(Timestamp::PosInfinity),
+            "-infinity" => Ok(Timestamp::NegInfinity),
+            _ => T::from_text(ty, raw).map(Timestamp::Value),
+        }
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        matches!(*ty, Type::TIMESTAMP | Type::TIMESTAMPTZ if T::accepts(ty))
+    }
+}
+
+impl<'a, T: FromSqlText<'a>> FromSqlText<'a> for Timestamp<T> {
+    fn from_sql_text(ty: &Type, raw: &'a str) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        match raw {
+            "infinity" => Ok(Timestamp::PosInfinity),
+            "-infinity" => Ok(Timestamp::NegInfinity),
+            _ => T::from_sql_text(ty, raw).map(Timestamp::Value),
+        }
+    }
+
     fn accepts(ty: &Type) -> bool {
         matches!(*ty, Type::TIMESTAMP | Type::TIMESTAMPTZ if T::accepts(ty))
     }
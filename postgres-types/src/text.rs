@@ -0,0 +1,825 @@
+//! Decoding of Postgres values from their text format.
+
+use bytes::BytesMut;
+use postgres_protocol::types::{self, ArrayDimension};
+use std::any::type_name;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::hash::BuildHasher;
+use std::net::IpAddr;
+use std::str;
+use std::time::SystemTime;
+
+use crate::{FromSql, FromSqlOwned, Kind, PgLsn, Type, WasNull};
+
+/// A trait for types that can be created from the text format of a Postgres value.
+///
+/// The text format is what the simple query protocol returns for every value. `FromSqlText` is implemented for the
+/// same types as [`FromSql`], with the exception of borrowed byte slices.
+///
+/// The text format of some types depends on the session's settings. Dates and timestamps are expected in the `ISO`
+/// `DateStyle`, and intervals in the `postgres` `IntervalStyle`, which are the server defaults. `BYTEA` values may use
+/// either the `hex` or `escape` output format.
+///
+/// # Arrays
+///
+/// `FromSqlText` is implemented for `Vec<T>`, `Box<[T]>`, `[T; N]` and `PgArray<T>` where `T` implements
+/// `FromSqlTextOwned`, since quoted array elements cannot be borrowed from the input.
+pub trait FromSqlText<'a>: Sized {
+    /// Creates a new value of this type from the text format of a value of the specified Postgres `Type`.
+    ///
+    /// The caller of this method is responsible for ensuring that this type
+    /// is compatible with the Postgres `Type`.
+    fn from_sql_text(ty: &Type, raw: &'a str) -> Result<Self, Box<dyn Error + Sync + Send>>;
+
+    /// Creates a new value of this type from a `NULL` SQL value.
+    ///
+    /// The caller of this method is responsible for ensuring that this type
+    /// is compatible with the Postgres `Type`.
+    ///
+    /// The default implementation returns `Err(Box::new(WasNull))`.
+    #[allow(unused_variables)]
+    fn from_sql_text_null(ty: &Type) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        Err(Box::new(WasNull))
+    }
+
+    /// A convenience function that delegates to `from_sql_text` and `from_sql_text_null` depending on the value of
+    /// `raw`.
+    fn from_sql_text_nullable(
+        ty: &Type,
+        raw: Option<&'a str>,
+    ) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        match raw {
+            Some(raw) => Self::from_sql_text(ty, raw),
+            None => Self::from_sql_text_null(ty),
+        }
+    }
+
+    /// Determines if a value of this type can be created from the specified
+    /// Postgres `Type`.
+    fn accepts(ty: &Type) -> bool;
+}
+
+/// A trait for types which can be created from the text format of a Postgres value without borrowing any data.
+///
+/// This is primarily useful for trait bounds on functions.
+pub trait FromSqlTextOwned: for<'a> FromSqlText<'a> {}
+
+impl<T> FromSqlTextOwned for T where T: for<'a> FromSqlText<'a> {}
+
+impl<'a, T: FromSqlText<'a>> FromSqlText<'a> for Option<T> {
+    fn from_sql_text(ty: &Type, raw: &'a str) -> Result<Option<T>, Box<dyn Error + Sync + Send>> {
+        T::from_sql_text(ty, raw).map(Some)
+    }
+
+    fn from_sql_text_null(_: &Type) -> Result<Option<T>, Box<dyn Error + Sync + Send>> {
+        Ok(None)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        T::accepts(ty)
+    }
+}
+
+impl<'a, T: FromSqlTextOwned> FromSqlText<'a> for Vec<T> {
+    fn from_sql_text(ty: &Type, raw: &'a str) -> Result<Vec<T>, Box<dyn Error + Sync + Send>> {
+        let member_type = member_type(ty);
+        let (dimensions, elements) = array_from_text(ty, raw)?;
+        if dimensions.len() > 1 {
+            return Err("array contains too many dimensions".into());
+        }
+
+        elements
+            .iter()
+            .map(|e| T::from_sql_text_nullable(member_type, e.as_deref()))
+            .collect()
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        match *ty.kind() {
+            Kind::Array(ref inner) => T::accepts(inner),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(feature = "array-impls")]
+impl<'a, T: FromSqlTextOwned, const N: usize> FromSqlText<'a> for [T; N] {
+    fn from_sql_text(ty: &Type, raw: &'a str) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        let values = Vec::<T>::from_sql_text(ty, raw)?;
+        let len = values.len();
+        let mut values = values.into_iter();
+        let out = array_init::try_array_init(|i| {
+            values
+                .next()
+                .ok_or_else(|| -> Box<dyn Error + Sync + Send> {
+                    format!("too few elements in array (expected {}, got {})", N, i).into()
+                })
+        })?;
+        if len > N {
+            return Err(format!(
+                "excess elements in array (expected {}, got more than that)",
+                N,
+            )
+            .into());
+        }
+
+        Ok(out)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        Vec::<T>::accepts(ty)
+    }
+}
+
+impl<'a, T: FromSqlTextOwned> FromSqlText<'a> for Box<[T]> {
+    fn from_sql_text(ty: &Type, raw: &'a str) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        Vec::<T>::from_sql_text(ty, raw).map(Vec::into_boxed_slice)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        Vec::<T>::accepts(ty)
+    }
+}
+
+impl<'a> FromSqlText<'a> for Vec<u8> {
+    fn from_sql_text(_: &Type, raw: &'a str) -> Result<Vec<u8>, Box<dyn Error + Sync + Send>> {
+        match raw.strip_prefix("\\x") {
+            Some(hex) => hex_decode(hex),
+            None => bytea_unescape(raw),
+        }
+    }
+
+    accepts!(BYTEA);
+}
+
+impl<'a> FromSqlText<'a> for String {
+    fn from_sql_text(_: &Type, raw: &'a str) -> Result<String, Box<dyn Error + Sync + Send>> {
+        Ok(raw.to_string())
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <&str as FromSql>::accepts(ty)
+    }
+}
+
+impl<'a> FromSqlText<'a> for Box<str> {
+    fn from_sql_text(_: &Type, raw: &'a str) -> Result<Box<str>, Box<dyn Error + Sync + Send>> {
+        Ok(raw.into())
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <&str as FromSql>::accepts(ty)
+    }
+}
+
+impl<'a> FromSqlText<'a> for &'a str {
+    fn from_sql_text(_: &Type, raw: &'a str) -> Result<&'a str, Box<dyn Error + Sync + Send>> {
+        Ok(raw)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <&str as FromSql>::accepts(ty)
+    }
+}
+
+impl<'a> FromSqlText<'a> for bool {
+    fn from_sql_text(_: &Type, raw: &'a str) -> Result<bool, Box<dyn Error + Sync + Send>> {
+        match raw {
+            "t" => Ok(true),
+            "f" => Ok(false),
+            _ => Err("invalid boolean".into()),
+        }
+    }
+
+    accepts!(BOOL);
+}
+
+impl<'a> FromSqlText<'a> for i8 {
+    fn from_sql_text(_: &Type, raw: &'a str) -> Result<i8, Box<dyn Error + Sync + Send>> {
+        // bytes outside of the ASCII range are written as octal escapes
+        let byte = match raw.as_bytes() {
+            [] => 0,
+            [b] if b.is_ascii() => *b,
+            [b'\\', digits @ ..] if digits.len() == 3 => u8::from_str_radix(&raw[1..], 8)?,
+            _ => return Err("invalid \"char\"".into()),
+        };
+        Ok(byte as i8)
+    }
+
+    accepts!(CHAR);
+}
+
+macro_rules! simple_from_text {
+    ($t:ty, $($expected:ident),+) => {
+        impl<'a> FromSqlText<'a> for $t {
+            fn from_sql_text(_: &Type, raw: &'a str) -> Result<$t, Box<dyn Error + Sync + Send>> {
+                Ok(raw.parse()?)
+            }
+
+            accepts!($($expected),+);
+        }
+    }
+}
+
+simple_from_text!(i16, INT2);
+simple_from_text!(i32, INT4);
+simple_from_text!(u32, OID);
+simple_from_text!(i64, INT8);
+simple_from_text!(f32, FLOAT4);
+simple_from_text!(f64, FLOAT8);
+
+impl<'a, S> FromSqlText<'a> for HashMap<String, Option<String>, S>
+where
+    S: Default + BuildHasher,
+{
+    fn from_sql_text(
+        _: &Type,
+        raw: &'a str,
+    ) -> Result<HashMap<String, Option<String>, S>, Box<dyn Error + Sync + Send>> {
+        let mut cursor = Cursor::new(raw);
+        let mut map = HashMap::default();
+
+        cursor.skip_whitespace();
+        while !cursor.is_empty() {
+            let key = cursor.quoted(b'"')?;
+            cursor.skip_whitespace();
+            cursor.expect(b'=')?;
+            cursor.expect(b'>')?;
+            cursor.skip_whitespace();
+            let value = if cursor.peek() == Some(b'"') {
+                Some(cursor.quoted(b'"')?.into_owned())
+            } else if cursor.take_while(|b| b.is_ascii_alphabetic()) == "NULL" {
+                None
+            } else {
+                return Err("invalid hstore value".into());
+            };
+            map.insert(key.into_owned(), value);
+
+            cursor.skip_whitespace();
+            if !cursor.is_empty() {
+                cursor.expect(b',')?;
+                cursor.skip_whitespace();
+            }
+        }
+
+        Ok(map)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        ty.name() == "hstore"
+    }
+}
+
+impl<'a> FromSqlText<'a> for SystemTime {
+    fn from_sql_text(ty: &Type, raw: &'a str) -> Result<SystemTime, Box<dyn Error + Sync + Send>> {
+        let time = timestamp_from_text(raw)?;
+        from_binary(ty, |buf| types::timestamp_to_sql(time, buf))
+    }
+
+    accepts!(TIMESTAMP, TIMESTAMPTZ);
+}
+
+impl<'a> FromSqlText<'a> for IpAddr {
+    fn from_sql_text(_: &Type, raw: &'a str) -> Result<IpAddr, Box<dyn Error + Sync + Send>> {
+        // the netmask is only written if it doesn't cover the entire address
+        let addr = raw.split('/').next().unwrap_or_default();
+        Ok(addr.parse()?)
+    }
+
+    accepts!(INET);
+}
+
+impl<'a> FromSqlText<'a> for PgLsn {
+    fn from_sql_text(_: &Type, raw: &'a str) -> Result<PgLsn, Box<dyn Error + Sync + Send>> {
+        raw.parse().map_err(|_| "invalid LSN".into())
+    }
+
+    accepts!(PG_LSN);
+}
+
+/// Decodes a value from the binary format it is serialized into by `encode`.
+///
+/// This allows types whose text format is parsed into the same representation as their binary format to share the
+/// range checks of their `FromSql` implementation.
+pub(crate) fn from_binary<T>(
+    ty: &Type,
+    encode: impl FnOnce(&mut BytesMut),
+) -> Result<T, Box<dyn Error + Sync + Send>>
+where
+    T: FromSqlOwned,
+{
+    let mut buf = BytesMut::new();
+    encode(&mut buf);
+    T::from_sql(ty, &buf)
+}
+
+/// Decodes a value through its `FromSqlText` implementation, for use by `FromSql::from_text`.
+pub(crate) fn from_text<T>(ty: &Type, raw: &str) -> Result<T, Box<dyn Error + Sync + Send>>
+where
+    T: FromSqlTextOwned,
+{
+    T::from_sql_text(ty, raw)
+}
+
+/// Decodes a value which borrows from its text format, which is only possible if it wasn't unescaped.
+pub(crate) fn borrowed_from_text<'a, T>(
+    ty: &Type,
+    raw: Cow<'a, str>,
+) -> Result<T, Box<dyn Error + Sync + Send>>
+where
+    T: FromSqlText<'a>,
+{
+    match raw {
+        Cow::Borrowed(raw) => T::from_sql_text(ty, raw),
+        Cow::Owned(_) => Err(format!(
+            "cannot borrow `{}` from an escaped text value",
+            type_name::<T>()
+        )
+        .into()),
+    }
+}
+
+static UNKNOWN: Type = Type::UNKNOWN;
+
+/// Returns the element type of an array type, or `UNKNOWN` if the type isn't known to be an array.
+pub(crate) fn member_type(ty: &Type) -> &Type {
+    match *ty.kind() {
+        Kind::Array(ref member) => member,
+        _ => &UNKNOWN,
+    }
+}
+
+/// The elements of an array in its text format, in row-major order, along with its dimensions.
+pub(crate) type TextArray<'a> = (Vec<ArrayDimension>, Vec<Option<Cow<'a, str>>>);
+
+/// Parses an array literal like `array_from_text`, owning the elements if the literal itself is owned.
+pub(crate) fn array_from_cow<'a>(
+    ty: &Type,
+    raw: Cow<'a, str>,
+) -> Result<TextArray<'a>, Box<dyn Error + Sync + Send>> {
+    match raw {
+        Cow::Borrowed(raw) => array_from_text(ty, raw),
+        Cow::Owned(raw) => {
+            let (dimensions, elements) = array_from_text(ty, &raw)?;
+            let elements = elements
+                .into_iter()
+                .map(|e| e.map(|e| Cow::Owned(e.into_owned())))
+                .collect();
+            Ok((dimensions, elements))
+        }
+    }
+}
+
+/// Parses an array literal of the specified array type, such as `{{1,2},{3,NULL}}` or `[0:1]={"a","b"}`.
+pub(crate) fn array_from_text<'a>(
+    ty: &Type,
+    raw: &'a str,
+) -> Result<TextArray<'a>, Box<dyn Error + Sync + Send>> {
+    // the vector types are written as a space separated list
+    if matches!(*ty, Type::INT2_VECTOR | Type::OID_VECTOR) {
+        let elements = raw
+            .split_whitespace()
+            .map(|e| Some(Cow::Borrowed(e)))
+            .collect::<Vec<_>>();
+        if elements.is_empty() {
+            return Ok((vec![], vec![]));
+        }
+        let dimension = ArrayDimension {
+            len: i32::try_from(elements.len()).map_err(|_| "array too large")?,
+            lower_bound: 0,
+        };
+        return Ok((vec![dimension], elements));
+    }
+
+    // box is the only built in type which doesn't use a comma
+    let delimiter = if *member_type(ty) == Type::BOX {
+        b';'
+    } else {
+        b','
+    };
+    let mut cursor = Cursor::new(raw);
+
+    let mut bounds = vec![];
+    if cursor.peek() == Some(b'[') {
+        while cursor.eat(b'[') {
+            let lower = cursor.integer()?;
+            cursor.expect(b':')?;
+            let upper = cursor.integer()?;
+            cursor.expect(b']')?;
+            bounds.push((lower, upper));
+        }
+        cursor.expect(b'=')?;
+    }
+
+    let mut parser = ArrayParser {
+        cursor,
+        delimiter,
+        lens: vec![],
+        leaf_depth: None,
+        elements: vec![],
+    };
+    parser.cursor.skip_whitespace();
+    parser.level(0)?;
+    parser.cursor.skip_whitespace();
+    if !parser.cursor.is_empty() {
+        return Err("malformed array: junk after closing brace".into());
+    }
+
+    if parser.elements.is_empty() {
+        return Ok((vec![], vec![]));
+    }
+
+    let dimensions = if bounds.is_empty() {
+        parser
+            .lens
+            .iter()
+            .map(|&len| ArrayDimension {
+                len: len.unwrap_or(0),
+                lower_bound: 1,
+            })
+            .collect()
+    } else {
+        if bounds.len() != parser.lens.len() {
+            return Err("malformed array: dimensions do not match the array bounds".into());
+        }
+        bounds
+            .iter()
+            .zip(&parser.lens)
+            .map(|(&(lower, upper), &len)| {
+                let len = len.unwrap_or(0);
+                if i64::from(upper) - i64::from(lower) + 1 != i64::from(len) {
+                    return Err("malformed array: dimensions do not match the array bounds".into());
+                }
+                Ok(ArrayDimension {
+                    len,
+                    lower_bound: lower,
+                })
+            })
+            .collect::<Result<_, Box<dyn Error + Sync + Send>>>()?
+    };
+
+    Ok((dimensions, parser.elements))
+}
+
+struct ArrayParser<'a> {
+    cursor: Cursor<'a>,
+    delimiter: u8,
+    lens: Vec<Option<i32>>,
+    leaf_depth: Option<usize>,
+    elements: Vec<Option<Cow<'a, str>>>,
+}
+
+impl<'a> ArrayParser<'a> {
+    fn level(&mut self, depth: usize) -> Result<(), Box<dyn Error + Sync + Send>> {
+        self.cursor.expect(b'{')?;
+        self.cursor.skip_whitespace();
+        if self.lens.len() == depth {
+            self.lens.push(None);
+        }
+
+        let mut len = 0;
+        if !self.cursor.eat(b'}') {
+            loop {
+                self.cursor.skip_whitespace();
+                if self.cursor.peek() == Some(b'{') {
+                    self.level(depth + 1)?;
+                } else {
+                    if *self.leaf_depth.get_or_insert(depth) != depth {
+                        return Err("malformed array: inconsistent nesting".into());
+                    }
+                    let element = self.element()?;
+                    self.elements.push(element);
+                }
+                len += 1;
+
+                self.cursor.skip_whitespace();
+                if self.cursor.eat(b'}') {
+                    break;
+                }
+                self.cursor.expect(self.delimiter)?;
+            }
+        }
+
+        match self.lens[depth] {
+            Some(expected) if expected != len => {
+                Err("malformed array: sub-arrays have different lengths".into())
+            }
+            _ => {
+                self.lens[depth] = Some(len);
+                Ok(())
+            }
+        }
+    }
+
+    fn element(&mut self) -> Result<Option<Cow<'a, str>>, Box<dyn Error + Sync + Send>> {
+        if self.cursor.peek() == Some(b'"') {
+            return self.cursor.quoted(b'"').map(Some);
+        }
+
+        let delimiter = self.delimiter;
+        let element = self
+            .cursor
+            .unquoted(|b| b == delimiter || b == b'}')?
+            .unwrap_or(Cow::Borrowed(""));
+        let element = match element {
+            Cow::Borrowed(e) => Cow::Borrowed(e.trim_end()),
+            Cow::Owned(e) => Cow::Owned(e.trim_end().to_string()),
+        };
+
+        if element.is_empty() {
+            return Err("malformed array: missing element".into());
+        }
+        if element.eq_ignore_ascii_case("NULL") {
+            return Ok(None);
+        }
+        Ok(Some(element))
+    }
+}
+
+/// Decodes a hexadecimal string.
+pub(crate) fn hex_decode(hex: &str) -> Result<Vec<u8>, Box<dyn Error + Sync + Send>> {
+    let bytes = hex.as_bytes().chunks_exact(2);
+    if !bytes.remainder().is_empty() {
+        return Err("invalid hex string: odd length".into());
+    }
+
+    bytes
+        .map(|b| {
+            str::from_utf8(b)
+                .ok()
+                .and_then(|b| u8::from_str_radix(b, 16).ok())
+                .ok_or_else(|| "invalid hex string".into())
+        })
+        .collect()
+}
+
+fn bytea_unescape(raw: &str) -> Result<Vec<u8>, Box<dyn Error + Sync + Send>> {
+    let mut out = Vec::with_capacity(raw.len());
+    let mut bytes = raw.as_bytes();
+    while let Some((&b, rest)) = bytes.split_first() {
+        if b != b'\\' {
+            out.push(b);
+            bytes = rest;
+            continue;
+        }
+
+        match rest {
+            [b'\\', rest @ ..] => {
+                out.push(b'\\');
+                bytes = rest;
+            }
+            [a @ b'0'..=b'3', b @ b'0'..=b'7', c @ b'0'..=b'7', rest @ ..] => {
+                out.push(((a - b'0') << 6) | ((b - b'0') << 3) | (c - b'0'));
+                bytes = rest;
+            }
+            _ => return Err("invalid bytea escape".into()),
+        }
+    }
+    Ok(out)
+}
+
+const USEC_PER_SEC: i64 = 1_000_000;
+const USEC_PER_DAY: i64 = 86_400 * USEC_PER_SEC;
+
+/// Parses a `DATE` in the `ISO` `DateStyle` into the number of days since January 1st, 2000.
+///
+/// Infinite dates are mapped to the values used by the binary format.
+#[cfg(any(
+    feature = "with-chrono-0_4",
+    feature = "with-jiff-0_1",
+    feature = "with-time-0_2",
+    feature = "with-time-0_3"
+))]
+pub(crate) fn date_from_text(raw: &str) -> Result<i32, Box<dyn Error + Sync + Send>> {
+    match raw {
+        "infinity" => return Ok(i32::MAX),
+        "-infinity" => return Ok(i32::MIN),
+        _ => {}
+    }
+
+    let (raw, bc) = match raw.strip_suffix(" BC") {
+        Some(raw) => (raw, true),
+        None => (raw, false),
+    };
+    let days = days_from_text(raw, bc)?;
+    i32::try_from(days).map_err(|_| "value too large to decode".into())
+}
+
+/// Parses a `TIME` into the number of microseconds since midnight.
+pub(crate) fn time_from_text(raw: &str) -> Result<i64, Box<dyn Error + Sync + Send>> {
+    let mut parts = raw.splitn(3, ':');
+    let mut next = || parts.next().ok_or("invalid time");
+    let hours = next()?.parse::<i64>()?;
+    let minutes = next()?.parse::<i64>()?;
+    let seconds = next()?;
+
+    let (seconds, fraction) = match seconds.split_once('.') {
+        Some((seconds, fraction)) => (seconds, fraction),
+        None => (seconds, ""),
+    };
+    if fraction.len() > 6 || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+        return Err("invalid time".into());
+    }
+    let micros = if fraction.is_empty() {
+        0
+    } else {
+        fraction.parse::<i64>()? * 10i64.pow(6 - fraction.len() as u32)
+    };
+
+    Ok(((hours * 60 + minutes) * 60 + seconds.parse::<i64>()?) * USEC_PER_SEC + micros)
+}
+
+/// Parses a `TIMESTAMP` or `TIMESTAMPTZ` in the `ISO` `DateStyle` into the number of microseconds since midnight,
+/// January 1st, 2000 UTC.
+///
+/// Infinite timestamps are mapped to the values used by the binary format.
+pub(crate) fn timestamp_from_text(raw: &str) -> Result<i64, Box<dyn Error + Sync + Send>> {
+    match raw {
+        "infinity" => return Ok(i64::MAX),
+        "-infinity" => return Ok(i64::MIN),
+        _ => {}
+    }
+
+    let (raw, bc) = match raw.strip_suffix(" BC") {
+        Some(raw) => (raw, true),
+        None => (raw, false),
+    };
+    let (date, time) = raw.split_once(' ').ok_or("invalid timestamp")?;
+
+    // a TIMESTAMPTZ is written in the session's time zone, with its UTC offset
+    let (time, offset) = match time.find(['+', '-']) {
+        Some(i) => {
+            let (time, offset) = time.split_at(i);
+            let sign = if offset.starts_with('-') { -1 } else { 1 };
+            let mut seconds = 0;
+            for (i, part) in offset[1..].split(':').enumerate() {
+                if i > 2 {
+                    return Err("invalid time zone offset".into());
+                }
+                seconds += part.parse::<i64>()? * 60i64.pow(2 - i as u32);
+            }
+            (time, sign * seconds * USEC_PER_SEC)
+        }
+        None => (time, 0),
+    };
+
+    let days = days_from_text(date, bc)?;
+    let time = time_from_text(time)?;
+    days.checked_mul(USEC_PER_DAY)
+        .and_then(|t| t.checked_add(time))
+        .and_then(|t| t.checked_sub(offset))
+        .ok_or_else(|| "value too large to decode".into())
+}
+
+fn days_from_text(raw: &str, bc: bool) -> Result<i64, Box<dyn Error + Sync + Send>> {
+    let mut parts = raw.splitn(3, '-');
+    let mut next = || parts.next().ok_or("invalid date");
+    let year = next()?.parse::<i64>()?;
+    let month = next()?.parse::<i64>()?;
+    let day = next()?.parse::<i64>()?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return Err("invalid date".into());
+    }
+
+    // there is no year 0, so 1 BC is year 0 of the proleptic Gregorian calendar
+    let year = if bc { 1 - year } else { year };
+
+    // http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let unix_days = era * 146_097 + day_of_era - 719_468;
+
+    // 2000-01-01 is 10957 days after 1970-01-01
+    Ok(unix_days - 10_957)
+}
+
+/// A cursor over the text format of a value.
+pub(crate) struct Cursor<'a> {
+    raw: &'a str,
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub(crate) fn new(raw: &'a str) -> Cursor<'a> {
+        Cursor { raw, pos: 0 }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.pos == self.raw.len()
+    }
+
+    pub(crate) fn peek(&self) -> Option<u8> {
+        self.raw.as_bytes().get(self.pos).copied()
+    }
+
+    /// Consumes the next byte if it is `b`.
+    pub(crate) fn eat(&mut self, b: u8) -> bool {
+        if self.peek() == Some(b) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub(crate) fn expect(&mut self, b: u8) -> Result<(), Box<dyn Error + Sync + Send>> {
+        if self.eat(b) {
+            Ok(())
+        } else {
+            Err(format!("expected `{}` at offset {}", b as char, self.pos).into())
+        }
+    }
+
+    pub(crate) fn skip_whitespace(&mut self) {
+        self.take_while(|b| b.is_ascii_whitespace());
+    }
+
+    /// Consumes bytes as long as `f` returns `true`.
+    ///
+    /// `f` must only match ASCII bytes so that the input is split at a character boundary.
+    pub(crate) fn take_while(&mut self, f: impl Fn(u8) -> bool) -> &'a str {
+        let start = self.pos;
+        while matches!(self.peek(), Some(b) if f(b)) {
+            self.pos += 1;
+        }
+        &self.raw[start..self.pos]
+    }
+
+    pub(crate) fn integer(&mut self) -> Result<i32, Box<dyn Error + Sync + Send>> {
+        let start = self.pos;
+        self.eat(b'-');
+        self.take_while(|b| b.is_ascii_digit());
+        Ok(self.raw[start..self.pos].parse()?)
+    }
+
+    /// Consumes a string enclosed in `quote`.
+    ///
+    /// Within the string, a backslash escapes the following character, and a doubled quote represents a single one.
+    pub(crate) fn quoted(
+        &mut self,
+        quote: u8,
+    ) -> Result<Cow<'a, str>, Box<dyn Error + Sync + Send>> {
+        self.expect(quote)?;
+        let mut value = self.unquoted(|b| b == quote)?.unwrap_or(Cow::Borrowed(""));
+        self.expect(quote)?;
+
+        while self.eat(quote) {
+            let value = value.to_mut();
+            value.push(quote as char);
+            if let Some(rest) = self.unquoted(|b| b == quote)? {
+                value.push_str(&rest);
+            }
+            self.expect(quote)?;
+        }
+
+        Ok(value)
+    }
+
+    /// Consumes characters until `end` matches an unescaped byte or the input ends, processing backslash escapes.
+    ///
+    /// Returns `None` if no characters were consumed.
+    fn unquoted(
+        &mut self,
+        end: impl Fn(u8) -> bool,
+    ) -> Result<Option<Cow<'a, str>>, Box<dyn Error + Sync + Send>> {
+        let start = self.pos;
+        let mut owned: Option<String> = None;
+        let mut chunk_start = start;
+
+        while let Some(b) = self.peek() {
+            if end(b) {
+                break;
+            }
+            if b == b'\\' {
+                let escaped = self.raw[self.pos + 1..]
+                    .chars()
+                    .next()
+                    .ok_or("unexpected end of input after `\\`")?;
+                let s = owned.get_or_insert_with(String::new);
+                s.push_str(&self.raw[chunk_start..self.pos]);
+                s.push(escaped);
+                self.pos += 1 + escaped.len_utf8();
+                chunk_start = self.pos;
+            } else {
+                self.pos += 1;
+            }
+        }
+
+        if self.pos == start {
+            return Ok(None);
+        }
+        Ok(Some(match owned {
+            Some(mut s) => {
+                s.push_str(&self.raw[chunk_start..self.pos]);
+                Cow::Owned(s)
+            }
+            None => Cow::Borrowed(&self.raw[start..self.pos]),
+        }))
+    }
+}
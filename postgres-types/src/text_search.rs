@@ -0,0 +1,517 @@
+//! Full text search types.
+
+use bytes::BytesMut;
+use fallible_iterator::FallibleIterator;
+use postgres_protocol::types::{self, TsQueryItem, TsQueryItems, TsVectorPosition};
+use std::error::Error;
+use std::fmt;
+
+use crate::text::Cursor;
+use crate::{FromSql, FromSqlText, IsNull, ToSql, Type};
+
+/// The weight of a lexeme.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum TsWeight {
+    /// The `A` weight, the highest.
+    A,
+    /// The `B` weight.
+    B,
+    /// The `C` weight.
+    C,
+    /// The `D` weight, the lowest and the default.
+    D,
+}
+
+impl TsWeight {
+    const ALL: [TsWeight; 4] = [TsWeight::A, TsWeight::B, TsWeight::C, TsWeight::D];
+
+    fn from_position(weight: u8) -> TsWeight {
+        TsWeight::ALL[3 - usize::from(weight & 3)]
+    }
+
+    fn to_position(self) -> u8 {
+        3 - self as u8
+    }
+
+    fn mask(self) -> u8 {
+        1 << self.to_position()
+    }
+
+    fn from_letter(letter: u8) -> Option<TsWeight> {
+        match letter {
+            b'A' => Some(TsWeight::A),
+            b'B' => Some(TsWeight::B),
+            b'C' => Some(TsWeight::C),
+            b'D' => Some(TsWeight::D),
+            _ => None,
+        }
+    }
+
+    fn letter(self) -> char {
+        match self {
+            TsWeight::A => 'A',
+            TsWeight::B => 'B',
+            TsWeight::C => 'C',
+            TsWeight::D => 'D',
+        }
+    }
+}
+
+/// The position of a lexeme in a document.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct TsPosition {
+    /// The position of the lexeme, between 1 and 16383.
+    pub position: u16,
+    /// The weight of the lexeme at this position.
+    pub weight: TsWeight,
+}
+
+impl TsPosition {
+    /// Creates a new position with the default weight.
+    pub fn new(position: u16) -> TsPosition {
+        TsPosition {
+            position,
+            weight: TsWeight::D,
+        }
+    }
+}
+
+/// A lexeme of a `TsVector`, along with its positions in the document.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct TsLexeme {
+    /// The lexeme.
+    pub lexeme: String,
+    /// The positions of the lexeme, which may be empty.
+    pub positions: Vec<TsPosition>,
+}
+
+impl TsLexeme {
+    /// Creates a new lexeme.
+    pub fn new(lexeme: impl Into<String>, positions: Vec<TsPosition>) -> TsLexeme {
+        TsLexeme {
+            lexeme: lexeme.into(),
+            positions,
+        }
+    }
+}
+
+/// Postgres `TSVECTOR` type.
+///
+/// Postgres requires lexemes to be unique and sorted, and the positions of each lexeme to be sorted. Values read from
+/// the database always satisfy this.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct TsVector {
+    /// The lexemes of the document.
+    pub lexemes: Vec<TsLexeme>,
+}
+
+impl TsVector {
+    /// Creates a new `TsVector`.
+    pub fn new(lexemes: Vec<TsLexeme>) -> TsVector {
+        TsVector { lexemes }
+    }
+}
+
+impl fmt::Display for TsVector {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, lexeme) in self.lexemes.iter().enumerate() {
+            if i > 0 {
+                fmt.write_str(" ")?;
+            }
+            write_lexeme(&lexeme.lexeme, fmt)?;
+            for (j, position) in lexeme.positions.iter().enumerate() {
+                let separator = if j == 0 { ':' } else { ',' };
+                write!(fmt, "{}{}", separator, position.position)?;
+                if position.weight != TsWeight::D {
+                    write!(fmt, "{}", position.weight.letter())?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a> FromSql<'a> for TsVector {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        let lexemes = types::tsvector_from_sql(raw)?
+            .map(|l| {
+                let positions = l
+                    .positions()
+                    .map(|p| TsPosition {
+                        position: p.position,
+                        weight: TsWeight::from_position(p.weight),
+                    })
+                    .collect();
+                Ok(TsLexeme::new(l.lexeme(), positions))
+            })
+            .collect()?;
+        Ok(TsVector::new(lexemes))
+    }
+
+    accepts!(TS_VECTOR);
+    from_text!();
+}
+
+impl<'a> FromSqlText<'a> for TsVector {
+    fn from_sql_text(_: &Type, raw: &str) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        let mut cursor = Cursor::new(raw);
+        let mut lexemes = vec![];
+
+        cursor.skip_whitespace();
+        while !cursor.is_empty() {
+            let lexeme = cursor.quoted(b'\'')?;
+            let mut positions = vec![];
+            if cursor.eat(b':') {
+                loop {
+                    let position = cursor.take_while(|b| b.is_ascii_digit()).parse()?;
+                    let weight = match cursor.peek().and_then(TsWeight::from_letter) {
+                        Some(weight) => {
+                            cursor.eat(weight.letter() as u8);
+                            weight
+                        }
+                        None => TsWeight::D,
+                    };
+                    positions.push(TsPosition { position, weight });
+                    if !cursor.eat(b',') {
+                        break;
+                    }
+                }
+            }
+            lexemes.push(TsLexeme::new(lexeme, positions));
+            cursor.skip_whitespace();
+        }
+
+        Ok(TsVector::new(lexemes))
+    }
+
+    accepts!(TS_VECTOR);
+}
+
+impl ToSql for TsVector {
+    fn to_sql(&self, _: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        let lexemes = self.lexemes.iter().map(|l| {
+            let positions = l.positions.iter().map(|p| TsVectorPosition {
+                position: p.position,
+                weight: p.weight.to_position(),
+            });
+            (&*l.lexeme, positions)
+        });
+        types::tsvector_to_sql(lexemes, out)?;
+        Ok(IsNull::No)
+    }
+
+    accepts!(TS_VECTOR);
+    to_sql_checked!();
+}
+
+/// A lexeme to match in a `TsQuery`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct TsQueryOperand {
+    /// The lexeme.
+    pub lexeme: String,
+    /// The weights the lexeme must have. An empty list matches any weight.
+    pub weights: Vec<TsWeight>,
+    /// Whether the lexeme matches as a prefix.
+    pub prefix: bool,
+}
+
+impl TsQueryOperand {
+    /// Creates a new operand matching the lexeme with any weight.
+    pub fn new(lexeme: impl Into<String>) -> TsQueryOperand {
+        TsQueryOperand {
+            lexeme: lexeme.into(),
+            weights: vec![],
+            prefix: false,
+        }
+    }
+
+    fn weight_mask(&self) -> u8 {
+        self.weights.iter().fold(0, |mask, w| mask | w.mask())
+    }
+}
+
+/// Postgres `TSQUERY` type.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TsQuery {
+    /// A query without lexemes.
+    ///
+    /// Postgres produces these from queries consisting only of stop words, but does not accept them as binary
+    /// parameters, so `ToSql` returns an error for them.
+    Empty,
+    /// A lexeme.
+    Operand(TsQueryOperand),
+    /// `!query`
+    Not(Box<TsQuery>),
+    /// `left & right`
+    And(Box<TsQuery>, Box<TsQuery>),
+    /// `left | right`
+    Or(Box<TsQuery>, Box<TsQuery>),
+    /// `left <N> right`, matching `right` `N` positions after `left`.
+    Phrase(Box<TsQuery>, Box<TsQuery>, u16),
+}
+
+impl TsQuery {
+    fn priority(&self) -> i32 {
+        match self {
+            TsQuery::Empty | TsQuery::Operand(_) => 0,
+            TsQuery::Not(_) => 4,
+            TsQuery::And(_, _) => 2,
+            TsQuery::Or(_, _) => 1,
+            TsQuery::Phrase(_, _, _) => 3,
+        }
+    }
+
+    fn items<'a>(
+        &'a self,
+        items: &mut Vec<TsQueryItem<'a>>,
+    ) -> Result<(), Box<dyn Error + Sync + Send>> {
+        let (left, right) = match self {
+            TsQuery::Empty => return Err("empty tsquery".into()),
+            TsQuery::Operand(operand) => {
+                items.push(TsQueryItem::Operand {
+                    lexeme: &operand.lexeme,
+                    weight: operand.weight_mask(),
+                    prefix: operand.prefix,
+                });
+                return Ok(());
+            }
+            TsQuery::Not(query) => {
+                items.push(TsQueryItem::Not);
+                return query.items(items);
+            }
+            TsQuery::And(left, right) => {
+                items.push(TsQueryItem::And);
+                (left, right)
+            }
+            TsQuery::Or(left, right) => {
+                items.push(TsQueryItem::Or);
+                (left, right)
+            }
+            TsQuery::Phrase(left, right, distance) => {
+                items.push(TsQueryItem::Phrase {
+                    distance: *distance,
+                });
+                (left, right)
+            }
+        };
+        // Postgres stores the right operand of binary operators first
+        right.items(items)?;
+        left.items(items)
+    }
+
+    fn from_items(items: &mut TsQueryItems<'_>) -> Result<TsQuery, Box<dyn Error + Sync + Send>> {
+        let item = items.next()?.ok_or("unexpected end of tsquery")?;
+        let query = match item {
+            TsQueryItem::Operand {
+                lexeme,
+                weight,
+                prefix,
+            } => TsQuery::Operand(TsQueryOperand {
+                lexeme: lexeme.to_string(),
+                weights: TsWeight::ALL
+                    .iter()
+                    .cloned()
+                    .filter(|w| weight & w.mask() != 0)
+                    .collect(),
+                prefix,
+            }),
+            TsQueryItem::Not => TsQuery::Not(Box::new(TsQuery::from_items(items)?)),
+            TsQueryItem::And | TsQueryItem::Or | TsQueryItem::Phrase { .. } => {
+                let right = Box::new(TsQuery::from_items(items)?);
+                let left = Box::new(TsQuery::from_items(items)?);
+                match item {
+                    TsQueryItem::And => TsQuery::And(left, right),
+                    TsQueryItem::Or => TsQuery::Or(left, right),
+                    TsQueryItem::Phrase { distance } => TsQuery::Phrase(left, right, distance),
+                    TsQueryItem::Operand { .. } | TsQueryItem::Not => unreachable!(),
+                }
+            }
+        };
+        Ok(query)
+    }
+
+    // mirrors the parenthesization of Postgres's own output function
+    fn fmt_infix(
+        &self,
+        fmt: &mut fmt::Formatter<'_>,
+        parent_priority: i32,
+        right_phrase: bool,
+    ) -> fmt::Result {
+        let priority = self.priority();
+        let (left, right, op) = match self {
+            TsQuery::Empty => return Ok(()),
+            TsQuery::Operand(operand) => {
+                write_lexeme(&operand.lexeme, fmt)?;
+                if operand.prefix || !operand.weights.is_empty() {
+                    fmt.write_str(":")?;
+                    if operand.prefix {
+                        fmt.write_str("*")?;
+                    }
+                    let mask = operand.weight_mask();
+                    for weight in TsWeight::ALL.iter().filter(|w| mask & w.mask() != 0) {
+                        write!(fmt, "{}", weight.letter())?;
+                    }
+                }
+                return Ok(());
+            }
+            TsQuery::Not(query) => {
+                let parens = priority < parent_priority;
+                if parens {
+                    fmt.write_str("( ")?;
+                }
+                fmt.write_str("!")?;
+                query.fmt_infix(fmt, priority, false)?;
+                if parens {
+                    fmt.write_str(" )")?;
+                }
+                return Ok(());
+            }
+            TsQuery::And(left, right) => (left, right, "&".to_string()),
+            TsQuery::Or(left, right) => (left, right, "|".to_string()),
+            TsQuery::Phrase(left, right, 1) => (left, right, "<->".to_string()),
+            TsQuery::Phrase(left, right, distance) => (left, right, format!("<{}>", distance)),
+        };
+
+        let phrase = matches!(self, TsQuery::Phrase(_, _, _));
+        let parens = priority < parent_priority || (phrase && right_phrase);
+        if parens {
+            fmt.write_str("( ")?;
+        }
+        left.fmt_infix(fmt, priority, false)?;
+        write!(fmt, " {} ", op)?;
+        right.fmt_infix(fmt, priority, phrase)?;
+        if parens {
+            fmt.write_str(" )")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for TsQuery {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_infix(fmt, -1, false)
+    }
+}
+
+impl<'a> FromSql<'a> for TsQuery {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        let mut items = types::tsquery_from_sql(raw)?;
+        if items.size_hint().0 == 0 {
+            return Ok(TsQuery::Empty);
+        }
+
+        let query = TsQuery::from_items(&mut items)?;
+        if items.next()?.is_some() {
+            return Err("invalid tsquery: excess items".into());
+        }
+        Ok(query)
+    }
+
+    accepts!(TSQUERY);
+    from_text!();
+}
+
+impl<'a> FromSqlText<'a> for TsQuery {
+    fn from_sql_text(_: &Type, raw: &str) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        let mut parser = TsQueryParser(Cursor::new(raw));
+        parser.0.skip_whitespace();
+        if parser.0.is_empty() {
+            return Ok(TsQuery::Empty);
+        }
+
+        let query = parser.or()?;
+        parser.0.skip_whitespace();
+        if !parser.0.is_empty() {
+            return Err("invalid tsquery: junk after query".into());
+        }
+        Ok(query)
+    }
+
+    accepts!(TSQUERY);
+}
+
+impl ToSql for TsQuery {
+    fn to_sql(&self, _: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        let mut items = vec![];
+        self.items(&mut items)?;
+        types::tsquery_to_sql(items, out)?;
+        Ok(IsNull::No)
+    }
+
+    accepts!(TSQUERY);
+    to_sql_checked!();
+}
+
+fn write_lexeme(lexeme: &str, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fmt.write_str("'")?;
+    for c in lexeme.chars() {
+        match c {
+            '\'' => fmt.write_str("''")?,
+            '\\' => fmt.write_str("\\\\")?,
+            c => write!(fmt, "{}", c)?,
+        }
+    }
+    fmt.write_str("'")
+}
+
+// parses the infix text format, in which `!` binds tightest, followed by the phrase operators, `&` and `|`
+struct TsQueryParser<'a>(Cursor<'a>);
+
+impl<'a> TsQueryParser<'a> {
+    fn operator(&mut self, op: u8) -> bool {
+        self.0.skip_whitespace();
+        self.0.eat(op)
+    }
+
+    fn or(&mut self) -> Result<TsQuery, Box<dyn Error + Sync + Send>> {
+        let mut query = self.and()?;
+        while self.operator(b'|') {
+            query = TsQuery::Or(Box::new(query), Box::new(self.and()?));
+        }
+        Ok(query)
+    }
+
+    fn and(&mut self) -> Result<TsQuery, Box<dyn Error + Sync + Send>> {
+        let mut query = self.phrase()?;
+        while self.operator(b'&') {
+            query = TsQuery::And(Box::new(query), Box::new(self.phrase()?));
+        }
+        Ok(query)
+    }
+
+    fn phrase(&mut self) -> Result<TsQuery, Box<dyn Error + Sync + Send>> {
+        let mut query = self.not()?;
+        while self.operator(b'<') {
+            let distance = if self.0.eat(b'-') {
+                1
+            } else {
+                self.0.take_while(|b| b.is_ascii_digit()).parse()?
+            };
+            self.0.expect(b'>')?;
+            query = TsQuery::Phrase(Box::new(query), Box::new(self.not()?), distance);
+        }
+        Ok(query)
+    }
+
+    fn not(&mut self) -> Result<TsQuery, Box<dyn Error + Sync + Send>> {
+        if self.operator(b'!') {
+            return Ok(TsQuery::Not(Box::new(self.not()?)));
+        }
+        if self.0.eat(b'(') {
+            let query = self.or()?;
+            self.0.skip_whitespace();
+            self.0.expect(b')')?;
+            return Ok(query);
+        }
+
+        let mut operand = TsQueryOperand::new(self.0.quoted(b'\'')?);
+        if self.0.eat(b':') {
+            operand.prefix = self.0.eat(b'*');
+            while let Some(weight) = self.0.peek().and_then(TsWeight::from_letter) {
+                operand.weights.push(weight);
+                self.0.eat(weight.letter() as u8);
+            }
+        }
+        Ok(TsQuery::Operand(operand))
+    }
+}
@@ -4,7 +4,8 @@ use std::convert::TryFrom;
 use std::error::Error;
 use time_02::{date, time, Date, Duration, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
 
-use crate::{FromSql, IsNull, ToSql, Type};
+use crate::text;
+use crate::{FromSql, FromSqlText, IsNull, ToSql, Type};
 
 #[rustfmt::skip]
 const fn base() -> PrimitiveDateTime {
@@ -17,6 +18,19 @@ impl<'a> FromSql<'a> for PrimitiveDateTime {
         Ok(base() + Duration::microseconds(t))
     }
 
+    accepts!(TIMESTAMP);
+    from_text!();
+}
+
+impl<'a> FromSqlText<'a> for PrimitiveDateTime {
+    fn from_sql_text(
+        ty: &Type,
+        raw: &str,
+    ) -> Result<PrimitiveDateTime, Box<dyn Error + Sync + Send>> {
+        let value = text::timestamp_from_text(raw)?;
+        text::from_binary(ty, |buf| types::timestamp_to_sql(value, buf))
+    }
+
     accepts!(TIMESTAMP);
 }
 
@@ -40,6 +54,16 @@ impl<'a> FromSql<'a> for OffsetDateTime {
         Ok(primitive.assume_utc())
     }
 
+    accepts!(TIMESTAMPTZ);
+    from_text!();
+}
+
+impl<'a> FromSqlText<'a> for OffsetDateTime {
+    fn from_sql_text(ty: &Type, raw: &str) -> Result<OffsetDateTime, Box<dyn Error + Sync + Send>> {
+        let value = text::timestamp_from_text(raw)?;
+        text::from_binary(ty, |buf| types::timestamp_to_sql(value, buf))
+    }
+
     accepts!(TIMESTAMPTZ);
 }
 
@@ -66,13 +90,23 @@ impl<'a> FromSql<'a> for Date {
         Ok(base().date() + Duration::days(i64::from(jd)))
     }
 
+    accepts!(DATE);
+    from_text!();
+}
+
+impl<'a> FromSqlText<'a> for Date {
+    fn from_sql_text(ty: &Type, raw: &str) -> Result<Date, Box<dyn Error + Sync + Send>> {
+        let value = text::date_from_text(raw)?;
+        text::from_binary(ty, |buf| types::date_to_sql(value, buf))
+    }
+
     accepts!(DATE);
 }
 
 impl ToSql for Date {
     fn to_sql(&self, _: &Type, w: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
         let jd = (*self - base().date()).whole_days();
-        if jd > i64::from(i32::max_value()) || jd < i64::from(i32::min_value()) {
+        if jd > i64::from(i32::MAX) || jd < i64::from(i32::MIN) {
             return Err("value too large to transmit".into());
         }
 
@@ -90,6 +124,16 @@ impl<'a> FromSql<'a> for Time {
         Ok(time!(00:00:00) + Duration::microseconds(usec))
     }
 
+    accepts!(TIME);
+    from_text!();
+}
+
+impl<'a> FromSqlText<'a> for Time {
+    fn from_sql_text(ty: &Type, raw: &str) -> Result<Time, Box<dyn Error + Sync + Send>> {
+        let value = text::time_from_text(raw)?;
+        text::from_binary(ty, |buf| types::time_to_sql(value, buf))
+    }
+
     accepts!(TIME);
 }
 
@@ -3,7 +3,7 @@ use postgres_protocol::types;
 use std::error::Error;
 use uuid_08::Uuid;
 
-use crate::{FromSql, IsNull, ToSql, Type};
+use crate::{FromSql, FromSqlText, IsNull, ToSql, Type};
 
 impl<'a> FromSql<'a> for Uuid {
     fn from_sql(_: &Type, raw: &[u8]) -> Result<Uuid, Box<dyn Error + Sync + Send>> {
@@ -11,6 +11,15 @@ impl<'a> FromSql<'a> for Uuid {
         Ok(Uuid::from_bytes(bytes))
     }
 
+    accepts!(UUID);
+    from_text!();
+}
+
+impl<'a> FromSqlText<'a> for Uuid {
+    fn from_sql_text(_: &Type, raw: &str) -> Result<Uuid, Box<dyn Error + Sync + Send>> {
+        Ok(Uuid::parse_str(raw)?)
+    }
+
     accepts!(UUID);
 }
 
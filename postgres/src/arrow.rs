@@ -0,0 +1,6 @@
+//! Conversions between query results and Apache Arrow record batches.
+//!
+//! Requires the `arrow` Cargo feature.
+
+#[doc(inline)]
+pub use tokio_postgres::arrow::{RecordBatch, RecordBatchBuilder, SchemaRef};
@@ -2,10 +2,12 @@
 
 use crate::connection::ConnectionRef;
 use crate::types::{BorrowToSql, ToSql, Type};
-use crate::{CopyInWriter, CopyOutReader, Error};
+use crate::{CopyInWriter, CopyOutReader, Error, GenericClient};
 use fallible_iterator::FallibleIterator;
 use futures_util::StreamExt;
 use std::pin::Pin;
+#[cfg(feature = "arrow")]
+use tokio_postgres::arrow::RecordBatch;
 #[doc(inline)]
 pub use tokio_postgres::binary_copy::BinaryCopyOutRow;
 use tokio_postgres::binary_copy::{self, BinaryCopyOutStream};
@@ -32,6 +34,49 @@ impl<'a> BinaryCopyInWriter<'a> {
         }
     }
 
+    /// Starts a binary copy into the specified columns of a table, looking up their types from the database.
+    ///
+    /// See `tokio_postgres::binary_copy::BinaryCopyInWriter::for_table` for details.
+    pub fn for_table<C>(
+        client: &'a mut C,
+        table: &str,
+        columns: &[&str],
+    ) -> Result<BinaryCopyInWriter<'a>, Error>
+    where
+        C: GenericClient,
+    {
+        let (mut connection, client) = client.parts();
+        let sink = connection.block_on(binary_copy::BinaryCopyInWriter::for_table(
+            client, table, columns,
+        ))?;
+
+        Ok(BinaryCopyInWriter {
+            connection,
+            sink: Box::pin(sink),
+        })
+    }
+
+    /// Returns the number of rows written so far.
+    pub fn rows_written(&self) -> u64 {
+        self.sink.rows_written()
+    }
+
+    /// Returns the number of bytes of copy data sent to the connection so far.
+    ///
+    /// Rows are buffered internally, so this may lag behind the rows written.
+    pub fn bytes_sent(&self) -> u64 {
+        self.sink.bytes_sent()
+    }
+
+    /// Sets a callback which is invoked with the total number of rows and bytes sent each time buffered rows are sent
+    /// to the connection.
+    pub fn set_progress_callback<F>(&mut self, callback: F)
+    where
+        F: FnMut(u64, u64) + Send + Sync + 'static,
+    {
+        self.sink.as_mut().set_progress_callback(callback);
+    }
+
     /// Writes a single row.
     ///
     /// # Panics
@@ -56,6 +101,19 @@ impl<'a> BinaryCopyInWriter<'a> {
             .block_on(self.sink.as_mut().write_raw(values))
     }
 
+    /// Writes each row of an Arrow record batch.
+    ///
+    /// Requires the `arrow` Cargo feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of columns in the batch does not match the number expected.
+    #[cfg(feature = "arrow")]
+    pub fn write_record_batch(&mut self, batch: &RecordBatch) -> Result<(), Error> {
+        self.connection
+            .block_on(self.sink.as_mut().write_record_batch(batch))
+    }
+
     /// Completes the copy, returning the number of rows added.
     ///
     /// This method *must* be used to complete the copy process. If it is not, the copy will be aborted.
@@ -0,0 +1,64 @@
+//! Utilities for bulk loading rows into a table.
+
+use crate::connection::ConnectionRef;
+use crate::types::BorrowToSql;
+use crate::Error;
+use futures_util::stream;
+use tokio_postgres::bulk_load;
+#[doc(inline)]
+pub use tokio_postgres::bulk_load::{ConflictAction, LoadCounts};
+
+/// A builder for bulk loads into a table.
+///
+/// Rows are written via binary `COPY` into a temporary table, which is then merged into the target table with an
+/// `INSERT ... ON CONFLICT` statement. Returned by the [`Transaction::bulk_loader`] method.
+///
+/// [`Transaction::bulk_loader`]: crate::Transaction::bulk_loader
+pub struct BulkLoader<'a> {
+    connection: ConnectionRef<'a>,
+    loader: bulk_load::BulkLoader<'a>,
+}
+
+impl<'a> BulkLoader<'a> {
+    pub(crate) fn new(
+        connection: ConnectionRef<'a>,
+        loader: bulk_load::BulkLoader<'a>,
+    ) -> BulkLoader<'a> {
+        BulkLoader { connection, loader }
+    }
+
+    /// Sets the columns to load, in the order their values appear in each row.
+    ///
+    /// Defaults to all columns of the table which can be written to, in table order. This excludes generated columns
+    /// and identity columns declared `GENERATED ALWAYS`.
+    pub fn columns(mut self, columns: &[&str]) -> Self {
+        self.loader = self.loader.columns(columns);
+        self
+    }
+
+    /// Sets the action taken when a loaded row conflicts with an existing row.
+    ///
+    /// Defaults to `ConflictAction::Update`.
+    pub fn on_conflict(mut self, conflict_action: ConflictAction) -> Self {
+        self.loader = self.loader.on_conflict(conflict_action);
+        self
+    }
+
+    /// Loads the rows of an iterator into the table.
+    ///
+    /// See `tokio_postgres::bulk_load::BulkLoader::load` for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of values in a row does not match the number of columns loaded.
+    pub fn load<I, R, P>(mut self, rows: I) -> Result<LoadCounts, Error>
+    where
+        I: IntoIterator<Item = R>,
+        R: IntoIterator<Item = P>,
+        R::IntoIter: ExactSizeIterator,
+        P: BorrowToSql,
+    {
+        self.connection
+            .block_on(self.loader.load(stream::iter(rows)))
+    }
+}
@@ -1,13 +1,14 @@
-use crate::connection::Connection;
+use crate::connection::{Connection, ConnectionRef};
 use crate::{
-    CancelToken, Config, CopyInWriter, CopyOutReader, Notifications, RowIter, Statement,
-    ToStatement, Transaction, TransactionBuilder,
+    CancelToken, Config, CopyInWriter, CopyOutReader, CursorIter, Notifications, RowIter,
+    Statement, ToStatement, Transaction, TransactionBuilder,
 };
+use bytes::Bytes;
 use std::task::Poll;
 use std::time::Duration;
 use tokio_postgres::tls::{MakeTlsConnect, TlsConnect};
-use tokio_postgres::types::{BorrowToSql, ToSql, Type};
-use tokio_postgres::{Error, Row, SimpleQueryMessage, Socket};
+use tokio_postgres::types::{BorrowToSql, Format, Oid, ToSql, Type};
+use tokio_postgres::{Column, Error, PreparedXact, Row, SimpleQueryMessage, Socket, TypeRegistry};
 
 /// A synchronous PostgreSQL client.
 pub struct Client {
@@ -26,6 +27,10 @@ impl Client {
         Client { connection, client }
     }
 
+    pub(crate) fn parts(&mut self) -> (ConnectionRef<'_>, &tokio_postgres::Client) {
+        (self.connection.as_ref(), &self.client)
+    }
+
     /// A convenience function which parses a configuration string into a `Config` and then connects to the database.
     ///
     /// See the documentation for [`Config`] for information about the connection syntax.
@@ -192,6 +197,68 @@ impl Client {
             .block_on(self.client.query_opt(query, params))
     }
 
+    /// Executes a statement with named parameters, returning the resulting rows.
+    ///
+    /// Parameters are specified by `:name`, and their values are matched to the placeholders by name. Values which are
+    /// not referenced by the query are ignored. See [`NamedStatement`] for details of the placeholder syntax.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use postgres::{Client, NoTls};
+    ///
+    /// # fn main() -> Result<(), postgres::Error> {
+    /// let mut client = Client::connect("host=localhost user=postgres", NoTls)?;
+    ///
+    /// let id = 1i32;
+    /// for row in client.query_named("SELECT name FROM users WHERE id = :id", &[("id", &id)])? {
+    ///     let name: &str = row.get(0);
+    ///     println!("name: {}", name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`NamedStatement`]: crate::NamedStatement
+    pub fn query_named(
+        &mut self,
+        query: &str,
+        params: &[(&str, &(dyn ToSql + Sync))],
+    ) -> Result<Vec<Row>, Error> {
+        self.connection
+            .block_on(self.client.query_named(query, params))
+    }
+
+    /// Like `execute`, but with named parameters.
+    pub fn execute_named(
+        &mut self,
+        query: &str,
+        params: &[(&str, &(dyn ToSql + Sync))],
+    ) -> Result<u64, Error> {
+        self.connection
+            .block_on(self.client.execute_named(query, params))
+    }
+
+    /// Like `query_one`, but with named parameters.
+    pub fn query_one_named(
+        &mut self,
+        query: &str,
+        params: &[(&str, &(dyn ToSql + Sync))],
+    ) -> Result<Row, Error> {
+        self.connection
+            .block_on(self.client.query_one_named(query, params))
+    }
+
+    /// Like `query_opt`, but with named parameters.
+    pub fn query_opt_named(
+        &mut self,
+        query: &str,
+        params: &[(&str, &(dyn ToSql + Sync))],
+    ) -> Result<Option<Row>, Error> {
+        self.connection
+            .block_on(self.client.query_opt_named(query, params))
+    }
+
     /// A maximally-flexible version of `query`.
     ///
     /// It takes an iterator of parameters rather than a slice, and returns an iterator of rows rather than collecting
@@ -257,6 +324,29 @@ impl Client {
         Ok(RowIter::new(self.connection.as_ref(), stream))
     }
 
+    /// Like `query_raw`, but allows the format of each result column to be selected.
+    ///
+    /// Columns requested in `Format::Text` are decoded by `Row::get` with `FromSql::from_text`, and can also be read
+    /// with `Row::get_text`.
+    pub fn query_raw_with_formats<T, P, I, F>(
+        &mut self,
+        query: &T,
+        params: I,
+        formats: F,
+    ) -> Result<RowIter<'_>, Error>
+    where
+        T: ?Sized + ToStatement,
+        P: BorrowToSql,
+        I: IntoIterator<Item = P>,
+        I::IntoIter: ExactSizeIterator,
+        F: FnMut(&Column) -> Format,
+    {
+        let stream = self
+            .connection
+            .block_on(self.client.query_raw_with_formats(query, params, formats))?;
+        Ok(RowIter::new(self.connection.as_ref(), stream))
+    }
+
     /// Creates a new prepared statement.
     ///
     /// Prepared statements can be executed repeatedly, and may contain query parameters (indicated by `$1`, `$2`, etc),
@@ -422,6 +512,64 @@ impl Client {
         self.connection.block_on(self.client.batch_execute(query))
     }
 
+    /// Commits a transaction prepared for two-phase commit with `Transaction::prepare_transaction`.
+    ///
+    /// The transaction may have been prepared by any connection to the same database.
+    pub fn commit_prepared(&mut self, gid: &str) -> Result<(), Error> {
+        self.connection.block_on(self.client.commit_prepared(gid))
+    }
+
+    /// Rolls back a transaction prepared for two-phase commit with `Transaction::prepare_transaction`.
+    ///
+    /// The transaction may have been prepared by any connection to the same database.
+    pub fn rollback_prepared(&mut self, gid: &str) -> Result<(), Error> {
+        self.connection.block_on(self.client.rollback_prepared(gid))
+    }
+
+    /// Returns the transactions currently prepared for two-phase commit, in the order they were prepared.
+    ///
+    /// This includes transactions prepared in all databases of the server.
+    pub fn list_prepared(&mut self) -> Result<Vec<PreparedXact>, Error> {
+        self.connection.block_on(self.client.list_prepared())
+    }
+
+    /// Declares a `WITH HOLD` cursor for a query, returning an iterator which fetches its rows in batches of
+    /// `fetch_size`.
+    ///
+    /// Unlike `Transaction::cursor`, the cursor is not tied to a transaction. The server materializes the entire result
+    /// of the query when the declaring transaction commits. The cursor is closed when the iterator is dropped. If
+    /// `fetch_size` is 0 or negative, all rows will be fetched at once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of parameters provided does not match the number expected.
+    pub fn cursor_with_hold(
+        &mut self,
+        query: &str,
+        params: &[&(dyn ToSql + Sync)],
+        fetch_size: i32,
+    ) -> Result<CursorIter<'_>, Error> {
+        let stream = self
+            .connection
+            .block_on(self.client.cursor_with_hold(query, params, fetch_size))?;
+        Ok(CursorIter::new(self.connection.as_ref(), stream))
+    }
+
+    /// Calls a server function by OID using the fast-path function call protocol, returning its raw result.
+    ///
+    /// Each argument is paired with the type of the corresponding function parameter, which is used to serialize it.
+    /// This avoids the parse and bind round trips of a prepared statement, but the server performs no type checking,
+    /// so the types must match the function's signature exactly. The result is returned in the binary format, or
+    /// `None` if the function returned `NULL`.
+    pub fn function_call(
+        &mut self,
+        function: Oid,
+        args: &[(&(dyn ToSql + Sync), Type)],
+    ) -> Result<Option<Bytes>, Error> {
+        self.connection
+            .block_on(self.client.function_call(function, args))
+    }
+
     /// Begins a new database transaction.
     ///
     /// The transaction will roll back by default - use the `commit` method to commit it.
@@ -526,10 +674,23 @@ impl Client {
     /// When user-defined types are used in a query, the client loads their definitions from the database and caches
     /// them for the lifetime of the client. If those definitions are changed in the database, this method can be used
     /// to flush the local cache and allow the new, updated definitions to be loaded.
+    ///
+    /// If the client was configured with a shared [`TypeRegistry`](crate::TypeRegistry), the cache of every client sharing it is cleared.
     pub fn clear_type_cache(&self) {
         self.client.clear_type_cache();
     }
 
+    /// Loads the definitions of the named types into a registry.
+    ///
+    /// Like `tokio_postgres::TypeRegistry::load`.
+    pub fn load_types<I, S>(&mut self, registry: &TypeRegistry, names: I) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.connection.block_on(registry.load(&self.client, names))
+    }
+
     /// Determines if the client's connection has already closed.
     ///
     /// If this returns `true`, the client is no longer usable.
@@ -15,7 +15,7 @@ use tokio::runtime;
 pub use tokio_postgres::config::{ChannelBinding, Host, SslMode, TargetSessionAttrs};
 use tokio_postgres::error::DbError;
 use tokio_postgres::tls::{MakeTlsConnect, TlsConnect};
-use tokio_postgres::{Error, Socket};
+use tokio_postgres::{Error, Socket, TypeRegistry};
 
 /// Connection configuration.
 ///
@@ -34,30 +34,30 @@ use tokio_postgres::{Error, Socket};
 /// * `options` - Command line options used to configure the server.
 /// * `application_name` - Sets the `application_name` parameter on the server.
 /// * `sslmode` - Controls usage of TLS. If set to `disable`, TLS will not be used. If set to `prefer`, TLS will be used
-///     if available, but not used otherwise. If set to `require`, TLS will be forced to be used. Defaults to `prefer`.
+///   if available, but not used otherwise. If set to `require`, TLS will be forced to be used. Defaults to `prefer`.
 /// * `host` - The host to connect to. On Unix platforms, if the host starts with a `/` character it is treated as the
-///     path to the directory containing Unix domain sockets. Otherwise, it is treated as a hostname. Multiple hosts
-///     can be specified, separated by commas. Each host will be tried in turn when connecting. Required if connecting
-///     with the `connect` method.
+///   path to the directory containing Unix domain sockets. Otherwise, it is treated as a hostname. Multiple hosts
+///   can be specified, separated by commas. Each host will be tried in turn when connecting. Required if connecting
+///   with the `connect` method.
 /// * `port` - The port to connect to. Multiple ports can be specified, separated by commas. The number of ports must be
-///     either 1, in which case it will be used for all hosts, or the same as the number of hosts. Defaults to 5432 if
-///     omitted or the empty string.
+///   either 1, in which case it will be used for all hosts, or the same as the number of hosts. Defaults to 5432 if
+///   omitted or the empty string.
 /// * `connect_timeout` - The time limit in seconds applied to each socket-level connection attempt. Note that hostnames
-///     can resolve to multiple IP addresses, and this limit is applied to each address. Defaults to no timeout.
+///   can resolve to multiple IP addresses, and this limit is applied to each address. Defaults to no timeout.
 /// * `tcp_user_timeout` - The time limit that transmitted data may remain unacknowledged before a connection is forcibly closed.
-///     This is ignored for Unix domain socket connections. It is only supported on systems where TCP_USER_TIMEOUT is available
-///     and will default to the system default if omitted or set to 0; on other systems, it has no effect.
+///   This is ignored for Unix domain socket connections. It is only supported on systems where TCP_USER_TIMEOUT is available
+///   and will default to the system default if omitted or set to 0; on other systems, it has no effect.
 /// * `keepalives` - Controls the use of TCP keepalive. A value of 0 disables keepalive and nonzero integers enable it.
-///     This option is ignored when connecting with Unix sockets. Defaults to on.
+///   This option is ignored when connecting with Unix sockets. Defaults to on.
 /// * `keepalives_idle` - The number of seconds of inactivity after which a keepalive message is sent to the server.
-///     This option is ignored when connecting with Unix sockets. Defaults to 2 hours.
+///   This option is ignored when connecting with Unix sockets. Defaults to 2 hours.
 /// * `keepalives_interval` - The time interval between TCP keepalive probes.
-///     This option is ignored when connecting with Unix sockets.
+///   This option is ignored when connecting with Unix sockets.
 /// * `keepalives_retries` - The maximum number of TCP keepalive probes that will be sent before dropping a connection.
-///     This option is ignored when connecting with Unix sockets.
+///   This option is ignored when connecting with Unix sockets.
 /// * `target_session_attrs` - Specifies requirements of the session. If set to `read-write`, the client will check that
-///     the `transaction_read_write` session parameter is set to `on`. This can be used to connect to the primary server
-///     in a database cluster as opposed to the secondary read-only mirrors. Defaults to `all`.
+///   the `transaction_read_write` session parameter is set to `on`. This can be used to connect to the primary server
+///   in a database cluster as opposed to the secondary read-only mirrors. Defaults to `all`.
 ///
 /// ## Examples
 ///
@@ -378,6 +378,25 @@ impl Config {
         self
     }
 
+    /// Sets the registry used to cache the definitions of custom types.
+    ///
+    /// Every connection created from this configuration shares the registry, so a type loaded by one of them doesn't
+    /// need to be loaded again by the others. By default, each connection has its own cache.
+    ///
+    /// Type definitions are keyed by OID, and OIDs are only meaningful within a single database. The registry must
+    /// therefore only be shared between connections to the same database. In particular, it should not be set on a
+    /// configuration with multiple hosts unless they are replicas of the same cluster.
+    pub fn type_registry(&mut self, type_registry: TypeRegistry) -> &mut Config {
+        self.config.type_registry(type_registry);
+        self
+    }
+
+    /// Gets the registry used to cache the definitions of custom types, if one has been configured with the
+    /// `type_registry` method.
+    pub fn get_type_registry(&self) -> Option<&TypeRegistry> {
+        self.config.get_type_registry()
+    }
+
     /// Opens a connection to a PostgreSQL database.
     pub fn connect<T>(&self, tls: T) -> Result<Client, Error>
     where
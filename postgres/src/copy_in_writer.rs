@@ -24,6 +24,22 @@ impl<'a> CopyInWriter<'a> {
         }
     }
 
+    /// Returns the number of bytes of copy data sent to the connection so far.
+    ///
+    /// Data is buffered internally, so this may lag behind the amount of data written until it is flushed.
+    pub fn bytes_sent(&self) -> u64 {
+        self.sink.get_ref().bytes_sent()
+    }
+
+    /// Sets a callback which is invoked with the total number of bytes sent each time copy data is sent to the
+    /// connection.
+    pub fn set_progress_callback<F>(&mut self, callback: F)
+    where
+        F: FnMut(u64) + Send + Sync + 'static,
+    {
+        self.sink.pinned().set_progress_callback(callback);
+    }
+
     /// Completes the copy, returning the number of rows written.
     ///
     /// If this is not called, the copy will be aborted.
@@ -20,6 +20,18 @@ impl<'a> CopyOutReader<'a> {
             cur: Bytes::new(),
         }
     }
+
+    /// Returns the number of bytes of copy data received so far.
+    pub fn bytes_received(&self) -> u64 {
+        self.stream.get_ref().bytes_received()
+    }
+
+    /// Returns the number of rows received so far.
+    ///
+    /// See `tokio_postgres::CopyOutStream::rows_received` for details.
+    pub fn rows_received(&self) -> u64 {
+        self.stream.get_ref().rows_received()
+    }
 }
 
 impl Read for CopyOutReader<'_> {
@@ -0,0 +1,30 @@
+use crate::connection::ConnectionRef;
+use fallible_iterator::FallibleIterator;
+use futures_util::StreamExt;
+use tokio_postgres::{CursorStream, Error, Row};
+
+/// The iterator returned by `cursor` and `cursor_with_hold`.
+pub struct CursorIter<'a> {
+    connection: ConnectionRef<'a>,
+    it: CursorStream<'a>,
+}
+
+impl<'a> CursorIter<'a> {
+    pub(crate) fn new(connection: ConnectionRef<'a>, stream: CursorStream<'a>) -> CursorIter<'a> {
+        CursorIter {
+            connection,
+            it: stream,
+        }
+    }
+}
+
+impl FallibleIterator for CursorIter<'_> {
+    type Item = Row;
+    type Error = Error;
+
+    fn next(&mut self) -> Result<Option<Row>, Error> {
+        let it = &mut self.it;
+        self.connection
+            .block_on(async { it.next().await.transpose() })
+    }
+}
@@ -1,11 +1,16 @@
+use crate::connection::ConnectionRef;
 use crate::types::{BorrowToSql, ToSql, Type};
 use crate::{
     Client, CopyInWriter, CopyOutReader, Error, Row, RowIter, SimpleQueryMessage, Statement,
     ToStatement, Transaction,
 };
 
-mod private {
-    pub trait Sealed {}
+pub(crate) mod private {
+    use crate::connection::ConnectionRef;
+
+    pub trait Sealed {
+        fn parts(&mut self) -> (ConnectionRef<'_>, &tokio_postgres::Client);
+    }
 }
 
 /// A trait allowing abstraction over connections and transactions.
@@ -44,6 +49,34 @@ pub trait GenericClient: private::Sealed {
         I: IntoIterator<Item = P>,
         I::IntoIter: ExactSizeIterator;
 
+    /// Like `Client::execute_named`.
+    fn execute_named(
+        &mut self,
+        query: &str,
+        params: &[(&str, &(dyn ToSql + Sync))],
+    ) -> Result<u64, Error>;
+
+    /// Like `Client::query_named`.
+    fn query_named(
+        &mut self,
+        query: &str,
+        params: &[(&str, &(dyn ToSql + Sync))],
+    ) -> Result<Vec<Row>, Error>;
+
+    /// Like `Client::query_one_named`.
+    fn query_one_named(
+        &mut self,
+        query: &str,
+        params: &[(&str, &(dyn ToSql + Sync))],
+    ) -> Result<Row, Error>;
+
+    /// Like `Client::query_opt_named`.
+    fn query_opt_named(
+        &mut self,
+        query: &str,
+        params: &[(&str, &(dyn ToSql + Sync))],
+    ) -> Result<Option<Row>, Error>;
+
     /// Like `Client::prepare`.
     fn prepare(&mut self, query: &str) -> Result<Statement, Error>;
 
@@ -70,7 +103,11 @@ pub trait GenericClient: private::Sealed {
     fn transaction(&mut self) -> Result<Transaction<'_>, Error>;
 }
 
-impl private::Sealed for Client {}
+impl private::Sealed for Client {
+    fn parts(&mut self) -> (ConnectionRef<'_>, &tokio_postgres::Client) {
+        self.parts()
+    }
+}
 
 impl GenericClient for Client {
     fn execute<T>(&mut self, query: &T, params: &[&(dyn ToSql + Sync)]) -> Result<u64, Error>
@@ -115,6 +152,38 @@ impl GenericClient for Client {
         self.query_raw(query, params)
     }
 
+    fn execute_named(
+        &mut self,
+        query: &str,
+        params: &[(&str, &(dyn ToSql + Sync))],
+    ) -> Result<u64, Error> {
+        self.execute_named(query, params)
+    }
+
+    fn query_named(
+        &mut self,
+        query: &str,
+        params: &[(&str, &(dyn ToSql + Sync))],
+    ) -> Result<Vec<Row>, Error> {
+        self.query_named(query, params)
+    }
+
+    fn query_one_named(
+        &mut self,
+        query: &str,
+        params: &[(&str, &(dyn ToSql + Sync))],
+    ) -> Result<Row, Error> {
+        self.query_one_named(query, params)
+    }
+
+    fn query_opt_named(
+        &mut self,
+        query: &str,
+        params: &[(&str, &(dyn ToSql + Sync))],
+    ) -> Result<Option<Row>, Error> {
+        self.query_opt_named(query, params)
+    }
+
     fn prepare(&mut self, query: &str) -> Result<Statement, Error> {
         self.prepare(query)
     }
@@ -150,7 +219,11 @@ impl GenericClient for Client {
     }
 }
 
-impl private::Sealed for Transaction<'_> {}
+impl private::Sealed for Transaction<'_> {
+    fn parts(&mut self) -> (ConnectionRef<'_>, &tokio_postgres::Client) {
+        self.parts()
+    }
+}
 
 impl GenericClient for Transaction<'_> {
     fn execute<T>(&mut self, query: &T, params: &[&(dyn ToSql + Sync)]) -> Result<u64, Error>
@@ -195,6 +268,38 @@ impl GenericClient for Transaction<'_> {
         self.query_raw(query, params)
     }
 
+    fn execute_named(
+        &mut self,
+        query: &str,
+        params: &[(&str, &(dyn ToSql + Sync))],
+    ) -> Result<u64, Error> {
+        self.execute_named(query, params)
+    }
+
+    fn query_named(
+        &mut self,
+        query: &str,
+        params: &[(&str, &(dyn ToSql + Sync))],
+    ) -> Result<Vec<Row>, Error> {
+        self.query_named(query, params)
+    }
+
+    fn query_one_named(
+        &mut self,
+        query: &str,
+        params: &[(&str, &(dyn ToSql + Sync))],
+    ) -> Result<Row, Error> {
+        self.query_one_named(query, params)
+    }
+
+    fn query_opt_named(
+        &mut self,
+        query: &str,
+        params: &[(&str, &(dyn ToSql + Sync))],
+    ) -> Result<Option<Row>, Error> {
+        self.query_opt_named(query, params)
+    }
+
     fn prepare(&mut self, query: &str) -> Result<Statement, Error> {
         self.prepare(query)
     }
@@ -0,0 +1,79 @@
+//! Utilities for working with PostgreSQL large objects.
+
+use crate::connection::ConnectionRef;
+use crate::types::Oid;
+use crate::Error;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use tokio_postgres::large_object;
+#[doc(inline)]
+pub use tokio_postgres::large_object::Mode;
+
+/// An open PostgreSQL large object.
+///
+/// Large objects can only be accessed within a transaction, and the handle is closed automatically when the
+/// transaction ends. Returned by the [`Transaction::open_large_object`] method.
+///
+/// [`Transaction::open_large_object`]: crate::Transaction::open_large_object
+pub struct LargeObject<'a> {
+    connection: ConnectionRef<'a>,
+    object: large_object::LargeObject<'a>,
+}
+
+impl<'a> LargeObject<'a> {
+    pub(crate) fn new(
+        connection: ConnectionRef<'a>,
+        object: large_object::LargeObject<'a>,
+    ) -> LargeObject<'a> {
+        LargeObject { connection, object }
+    }
+
+    /// Returns the OID of the large object.
+    pub fn oid(&self) -> Oid {
+        self.object.oid()
+    }
+
+    /// Returns the current position of the object.
+    pub fn tell(&mut self) -> Result<u64, Error> {
+        self.connection.block_on(self.object.tell())
+    }
+
+    /// Truncates or extends the object to the specified length.
+    ///
+    /// The current position of the object is not changed.
+    pub fn truncate(&mut self, len: u64) -> Result<(), Error> {
+        self.connection.block_on(self.object.truncate(len))
+    }
+
+    /// Consumes the handle, closing the large object.
+    pub fn close(mut self) -> Result<(), Error> {
+        self.connection.block_on(self.object.close())
+    }
+}
+
+impl Read for LargeObject<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.connection
+            .block_on(self.object.read(buf))
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+    }
+}
+
+impl Write for LargeObject<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.connection
+            .block_on(self.object.write(buf))
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Seek for LargeObject<'_> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.connection
+            .block_on(self.object.seek(pos))
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+    }
+}
@@ -13,6 +13,10 @@ impl<T> LazyPin<T> {
         }
     }
 
+    pub fn get_ref(&self) -> &T {
+        &self.value
+    }
+
     pub fn pinned(&mut self) -> Pin<&mut T> {
         self.pinned = true;
         unsafe { Pin::new_unchecked(&mut *self.value) }
@@ -53,12 +53,18 @@
 //!
 //! | Feature | Description | Extra dependencies | Default |
 //! | ------- | ----------- | ------------------ | ------- |
+//! | `serde` | Enable deserializing rows into `serde` types. | [serde](https://crates.io/crates/serde) 1.0, [serde_json](https://crates.io/crates/serde_json) 1.0 | no |
+//! | `arrow` | Enable conversions between rows and Apache Arrow record batches. | [arrow-array](https://crates.io/crates/arrow-array) 53, [arrow-schema](https://crates.io/crates/arrow-schema) 53 | no |
+//! | `pgvector` | Enable support for the types of the `pgvector` extension. | [half](https://crates.io/crates/half) 2 | no |
+//! | `postgis` | Enable support for the `geometry` and `geography` types of the PostGIS extension as `geo-types` geometries. | [geo-types](https://crates.io/crates/geo-types/0.7.0) 0.7 | no |
 //! | `with-bit-vec-0_6` | Enable support for the `bit-vec` crate. | [bit-vec](https://crates.io/crates/bit-vec) 0.6 | no |
 //! | `with-chrono-0_4` | Enable support for the `chrono` crate. | [chrono](https://crates.io/crates/chrono) 0.4 | no |
 //! | `with-eui48-0_4` | Enable support for the 0.4 version of the `eui48` crate. | [eui48](https://crates.io/crates/eui48) 0.4 | no |
 //! | `with-eui48-1` | Enable support for the 1.0 version of the `eui48` crate. | [eui48](https://crates.io/crates/eui48) 1.0 | no |
 //! | `with-geo-types-0_6` | Enable support for the 0.6 version of the `geo-types` crate. | [geo-types](https://crates.io/crates/geo-types/0.6.0) 0.6 | no |
 //! | `with-geo-types-0_7` | Enable support for the 0.7 version of the `geo-types` crate. | [geo-types](https://crates.io/crates/geo-types/0.7.0) 0.7 | no |
+//! | `with-jiff-0_1` | Enable support for the 0.1 version of the `jiff` crate. | [jiff](https://crates.io/crates/jiff/0.1.0) 0.1 | no |
+//! | `with-ndarray-0_16` | Enable support for the 0.16 version of the `ndarray` crate. | [ndarray](https://crates.io/crates/ndarray/0.16.0) 0.16 | no |
 //! | `with-serde_json-1` | Enable support for the `serde_json` crate. | [serde_json](https://crates.io/crates/serde_json) 1.0 | no |
 //! | `with-uuid-0_8` | Enable support for the `uuid` crate. | [uuid](https://crates.io/crates/uuid) 0.8 | no |
 //! | `with-uuid-1` | Enable support for the `uuid` crate. | [uuid](https://crates.io/crates/uuid) 1.0 | no |
@@ -68,8 +74,8 @@
 
 pub use fallible_iterator;
 pub use tokio_postgres::{
-    error, row, tls, types, Column, IsolationLevel, Notification, Portal, SimpleQueryMessage,
-    Socket, Statement, ToStatement,
+    error, row, tls, types, Column, IsolationLevel, NamedStatement, Notification, Portal,
+    PreparedXact, SimpleQueryMessage, Socket, Statement, ToStatement, TypeRegistry,
 };
 
 pub use crate::cancel_token::CancelToken;
@@ -77,6 +83,7 @@ pub use crate::client::*;
 pub use crate::config::Config;
 pub use crate::copy_in_writer::CopyInWriter;
 pub use crate::copy_out_reader::CopyOutReader;
+pub use crate::cursor_iter::CursorIter;
 #[doc(no_inline)]
 pub use crate::error::Error;
 pub use crate::generic_client::GenericClient;
@@ -90,17 +97,24 @@ pub use crate::tls::NoTls;
 pub use crate::transaction::*;
 pub use crate::transaction_builder::TransactionBuilder;
 
+#[cfg(feature = "arrow")]
+pub mod arrow;
 pub mod binary_copy;
+pub mod bulk_load;
 mod cancel_token;
 mod client;
 pub mod config;
 mod connection;
 mod copy_in_writer;
 mod copy_out_reader;
+mod cursor_iter;
 mod generic_client;
+pub mod large_object;
 mod lazy_pin;
+pub mod macros;
 pub mod notifications;
 mod row_iter;
+pub mod text_copy;
 mod transaction;
 mod transaction_builder;
 
@@ -0,0 +1,115 @@
+//! Support for compile-time checked queries.
+//!
+//! The [`query!`] macro prepares a statement while the crate using it is compiled, and checks the number and types of
+//! its parameters against the Rust expressions passed to it. It evaluates to a [`Query`] which deserializes the rows it
+//! returns into a `Record` struct generated from the statement's columns. As with `tokio_postgres`, the macro is
+//! implemented by the `postgres-macros` crate, which must be a dependency of the crate using it. See the documentation of `tokio_postgres::macros` for details of
+//! database access, offline metadata, and type mappings.
+//!
+//! [`query!`]: crate::query!
+//!
+//! # Examples
+//!
+//! ```ignore
+//! # fn main() -> Result<(), postgres::Error> {
+//! # let mut client = postgres::Client::connect("host=localhost user=postgres", postgres::NoTls)?;
+//! let id = 1i32;
+//! let user = postgres::query!("SELECT id, name FROM users WHERE id = $1", id).fetch_one(&mut client)?;
+//! println!("{}: {}", user.id, user.name);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::types::ToSql;
+use crate::{Error, GenericClient, Row};
+
+#[doc(hidden)]
+pub use tokio_postgres::macros::{check_param, Array, ParamType};
+
+/// Creates a [`Query`] checked against the database at compile time.
+///
+/// Takes a SQL string literal followed by an expression for each of its parameters. See the [module
+/// documentation](crate::macros) for details.
+#[macro_export]
+macro_rules! query {
+    ($($args:tt)*) => {
+        ::postgres_macros::query!($crate, $($args)*)
+    };
+}
+
+/// A statement checked at compile time, along with its parameters.
+///
+/// Created by the [`query!`] macro.
+///
+/// [`query!`]: crate::query!
+pub struct Query<'a, R>(tokio_postgres::macros::Query<'a, R>);
+
+impl<'a, R> Query<'a, R> {
+    #[doc(hidden)]
+    pub fn new(
+        sql: &'static str,
+        params: Vec<Box<dyn ToSql + Sync + 'a>>,
+        map: fn(&Row) -> Result<R, Error>,
+    ) -> Query<'a, R> {
+        Query(tokio_postgres::macros::Query::new(sql, params, map))
+    }
+
+    /// Returns the SQL of the statement.
+    pub fn sql(&self) -> &'static str {
+        self.0.sql()
+    }
+
+    /// Returns the parameters of the statement.
+    pub fn params(&self) -> &[Box<dyn ToSql + Sync + 'a>] {
+        self.0.params()
+    }
+
+    /// Deserializes a row returned by the statement.
+    pub fn map_row(&self, row: &Row) -> Result<R, Error> {
+        self.0.map_row(row)
+    }
+
+    /// Executes the statement, returning the resulting records.
+    pub fn fetch_all<C>(&self, client: &mut C) -> Result<Vec<R>, Error>
+    where
+        C: GenericClient,
+    {
+        client
+            .query(self.sql(), &self.0.param_refs())?
+            .iter()
+            .map(|row| self.map_row(row))
+            .collect()
+    }
+
+    /// Executes the statement, returning the single resulting record.
+    ///
+    /// Returns an error if the statement does not return exactly one row.
+    pub fn fetch_one<C>(&self, client: &mut C) -> Result<R, Error>
+    where
+        C: GenericClient,
+    {
+        let row = client.query_one(self.sql(), &self.0.param_refs())?;
+        self.map_row(&row)
+    }
+
+    /// Executes the statement, returning zero or one resulting records.
+    ///
+    /// Returns an error if the statement returns more than one row.
+    pub fn fetch_optional<C>(&self, client: &mut C) -> Result<Option<R>, Error>
+    where
+        C: GenericClient,
+    {
+        match client.query_opt(self.sql(), &self.0.param_refs())? {
+            Some(row) => self.map_row(&row).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Executes the statement, returning the number of rows modified.
+    pub fn execute<C>(&self, client: &mut C) -> Result<u64, Error>
+    where
+        C: GenericClient,
+    {
+        client.execute(self.sql(), &self.0.param_refs())
+    }
+}
@@ -1,14 +1,17 @@
-use std::io::{Read, Write};
+use std::io::{Read, Seek, SeekFrom, Write};
 use std::str::FromStr;
 use std::sync::mpsc;
 use std::thread;
 use std::time::Duration;
 use tokio_postgres::error::SqlState;
-use tokio_postgres::types::Type;
+use tokio_postgres::types::{Format, ToSql, Type};
 use tokio_postgres::NoTls;
 
 use super::*;
 use crate::binary_copy::{BinaryCopyInWriter, BinaryCopyOutIter};
+use crate::bulk_load::ConflictAction;
+use crate::large_object::Mode;
+use crate::text_copy::{CsvCopyInWriter, CsvCopyOutIter, CsvOptions};
 use fallible_iterator::FallibleIterator;
 
 #[test]
@@ -252,6 +255,22 @@ fn copy_in() {
     assert_eq!(rows[1].get::<_, &str>(1), "timothy");
 }
 
+#[test]
+fn copy_in_error_row() {
+    let mut client = Client::connect("host=localhost port=5433 user=postgres", NoTls).unwrap();
+
+    client
+        .simple_query("CREATE TEMPORARY TABLE foo (id INT, name TEXT)")
+        .unwrap();
+
+    let mut writer = client.copy_in("COPY foo FROM stdin").unwrap();
+    writer.write_all(b"1\tsteven\nx\ttimothy\n").unwrap();
+    writer.flush().unwrap();
+    assert_eq!(writer.bytes_sent(), 19);
+    let err = writer.finish().unwrap_err();
+    assert_eq!(err.copy_error_row(), Some(2));
+}
+
 #[test]
 fn copy_in_abort() {
     let mut client = Client::connect("host=localhost port=5433 user=postgres", NoTls).unwrap();
@@ -296,6 +315,58 @@ fn binary_copy_in() {
     assert_eq!(rows[1].get::<_, &str>(1), "timothy");
 }
 
+#[test]
+fn binary_copy_in_for_table() {
+    let mut client = Client::connect("host=localhost port=5433 user=postgres", NoTls).unwrap();
+
+    client
+        .simple_query("CREATE TEMPORARY TABLE foo (id INT, name TEXT)")
+        .unwrap();
+
+    let mut transaction = client.transaction().unwrap();
+    let mut writer = BinaryCopyInWriter::for_table(&mut transaction, "foo", &[]).unwrap();
+    writer.write(&[&1i32, &"steven"]).unwrap();
+    assert!(writer.write(&[&"timothy", &2i32]).is_err());
+    writer.write(&[&2i32, &"timothy"]).unwrap();
+    writer.finish().unwrap();
+    transaction.commit().unwrap();
+
+    let rows = client
+        .query("SELECT id, name FROM foo ORDER BY id", &[])
+        .unwrap();
+
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[1].get::<_, &str>(1), "timothy");
+}
+
+#[test]
+fn csv_copy() {
+    let mut client = Client::connect("host=localhost port=5433 user=postgres", NoTls).unwrap();
+
+    client
+        .simple_query("CREATE TEMPORARY TABLE foo (id INT, name TEXT)")
+        .unwrap();
+
+    let writer = client.copy_in("COPY foo FROM stdin CSV").unwrap();
+    let mut writer = CsvCopyInWriter::new(writer, &[Type::INT4, Type::TEXT], CsvOptions::new());
+    writer.write(&[&1i32, &"steven, \"the\" man"]).unwrap();
+    writer.write(&[&2i32, &None::<&str>]).unwrap();
+    writer.finish().unwrap();
+
+    let reader = client
+        .copy_out("COPY (SELECT id, name FROM foo ORDER BY id) TO stdout CSV")
+        .unwrap();
+    let rows = CsvCopyOutIter::new(reader, CsvOptions::new())
+        .collect::<Vec<_>>()
+        .unwrap();
+
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].parse::<i32>(0).unwrap(), Some(1));
+    assert_eq!(rows[0].get(1), Some("steven, \"the\" man"));
+    assert_eq!(rows[1].parse::<i32>(0).unwrap(), Some(2));
+    assert_eq!(rows[1].get(1), None);
+}
+
 #[test]
 fn copy_out() {
     let mut client = Client::connect("host=localhost port=5433 user=postgres", NoTls).unwrap();
@@ -310,6 +381,8 @@ fn copy_out() {
     let mut reader = client.copy_out("COPY foo (id, name) TO STDOUT").unwrap();
     let mut s = String::new();
     reader.read_to_string(&mut s).unwrap();
+    assert_eq!(reader.bytes_received(), 19);
+    assert_eq!(reader.rows_received(), 2);
     drop(reader);
 
     assert_eq!(s, "1\tsteven\n2\ttimothy\n");
@@ -317,6 +390,86 @@ fn copy_out() {
     client.simple_query("SELECT 1").unwrap();
 }
 
+#[test]
+fn bulk_load() {
+    let mut client = Client::connect("host=localhost port=5433 user=postgres", NoTls).unwrap();
+
+    client
+        .batch_execute(
+            "CREATE TEMPORARY TABLE foo (id INT PRIMARY KEY, name TEXT);
+            INSERT INTO foo (id, name) VALUES (1, 'alice');",
+        )
+        .unwrap();
+
+    let mut transaction = client.transaction().unwrap();
+    let counts = transaction
+        .bulk_loader("foo", &["id"])
+        .load(vec![
+            [&1i32 as &(dyn ToSql + Sync), &"anna"],
+            [&2i32, &"bob"],
+        ])
+        .unwrap();
+    assert_eq!(counts.inserted(), 1);
+    assert_eq!(counts.updated(), 1);
+
+    let counts = transaction
+        .bulk_loader("foo", &[])
+        .on_conflict(ConflictAction::Nothing)
+        .load(vec![[&2i32 as &(dyn ToSql + Sync), &"bill"]])
+        .unwrap();
+    assert_eq!(counts.skipped(), 1);
+    transaction.commit().unwrap();
+
+    let rows = client
+        .query("SELECT id, name FROM foo ORDER BY id", &[])
+        .unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].get::<_, &str>(1), "anna");
+    assert_eq!(rows[1].get::<_, &str>(1), "bob");
+}
+
+#[test]
+fn cursor() {
+    let mut client = Client::connect("host=localhost port=5433 user=postgres", NoTls).unwrap();
+
+    let rows = client
+        .cursor_with_hold("SELECT generate_series(1, 5)", &[], 2)
+        .unwrap()
+        .map(|row| Ok(row.get::<_, i32>(0)))
+        .collect::<Vec<_>>()
+        .unwrap();
+    assert_eq!(rows, [1, 2, 3, 4, 5]);
+
+    let mut transaction = client.transaction().unwrap();
+    let rows = transaction
+        .cursor("SELECT generate_series(1, $1)", &[&3i32], 2)
+        .unwrap()
+        .collect::<Vec<_>>()
+        .unwrap();
+    assert_eq!(rows.len(), 3);
+}
+
+#[test]
+fn large_object() {
+    let mut client = Client::connect("host=localhost port=5433 user=postgres", NoTls).unwrap();
+
+    let mut transaction = client.transaction().unwrap();
+    let oid = transaction.create_large_object().unwrap();
+
+    let mut object = transaction.open_large_object(oid, Mode::ReadWrite).unwrap();
+    object.write_all(b"hello world").unwrap();
+    object.seek(SeekFrom::Start(6)).unwrap();
+    let mut s = String::new();
+    object.read_to_string(&mut s).unwrap();
+    assert_eq!(s, "world");
+    object.truncate(5).unwrap();
+    assert_eq!(object.seek(SeekFrom::End(0)).unwrap(), 5);
+    object.close().unwrap();
+
+    transaction.unlink_large_object(oid).unwrap();
+    transaction.commit().unwrap();
+}
+
 #[test]
 fn binary_copy_out() {
     let mut client = Client::connect("host=localhost port=5433 user=postgres", NoTls).unwrap();
@@ -370,6 +523,33 @@ fn portal() {
     assert_eq!(rows[0].get::<_, i32>(0), 3);
 }
 
+#[test]
+fn portal_with_formats() {
+    let mut client = Client::connect("host=localhost port=5433 user=postgres", NoTls).unwrap();
+
+    let mut transaction = client.transaction().unwrap();
+
+    let portal = transaction
+        .bind_raw_with_formats(
+            "SELECT i, i::TEXT AS t FROM generate_series(1, 3) i",
+            &[] as &[i32],
+            |column| match column.name() {
+                "i" => Format::Binary,
+                _ => Format::Text,
+            },
+        )
+        .unwrap();
+
+    let rows = transaction.query_portal(&portal, 2).unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].get::<_, i32>(0), 1);
+    assert_eq!(rows[1].get::<_, &str>(1), "2");
+
+    let rows = transaction.query_portal(&portal, 2).unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].get_text::<_, &str>(1), "3");
+}
+
 #[test]
 fn cancel_query() {
     let mut client = Client::connect("host=localhost port=5433 user=postgres", NoTls).unwrap();
@@ -508,3 +688,76 @@ fn check_send() {
     is_send::<Statement>();
     is_send::<Transaction<'_>>();
 }
+
+#[test]
+fn query_named() {
+    let mut client = Client::connect("host=localhost port=5433 user=postgres", NoTls).unwrap();
+
+    client
+        .batch_execute("CREATE TEMPORARY TABLE foo (id INT, name TEXT)")
+        .unwrap();
+
+    let mut transaction = client.transaction().unwrap();
+    transaction
+        .execute_named(
+            "INSERT INTO foo (id, name) VALUES (:id, :name), (:id + 1, :name || '2')",
+            &[("id", &1i32), ("name", &"alice")],
+        )
+        .unwrap();
+    transaction.commit().unwrap();
+
+    let rows = client
+        .query_named(
+            "SELECT name FROM foo WHERE id >= :id ORDER BY id",
+            &[("id", &1i32)],
+        )
+        .unwrap();
+    let names = rows.iter().map(|r| r.get(0)).collect::<Vec<&str>>();
+    assert_eq!(names, ["alice", "alice2"]);
+
+    let row = GenericClient::query_one_named(
+        &mut client,
+        "SELECT name FROM foo WHERE id = :id",
+        &[("id", &2i32)],
+    )
+    .unwrap();
+    assert_eq!(row.get::<_, &str>(0), "alice2");
+
+    let err = client
+        .query_opt_named("SELECT :missing::INT", &[])
+        .unwrap_err();
+    assert_eq!(err.to_string(), "missing parameter `missing`");
+}
+
+#[test]
+fn two_phase_commit() {
+    let mut client = Client::connect("host=localhost port=5433 user=postgres", NoTls).unwrap();
+    let mut other = Client::connect("host=localhost port=5433 user=postgres", NoTls).unwrap();
+
+    client
+        .batch_execute(
+            "DROP TABLE IF EXISTS sync_two_phase_commit_test; \
+             CREATE TABLE sync_two_phase_commit_test (i INT)",
+        )
+        .unwrap();
+
+    let gid = "postgres two_phase_commit";
+    let mut transaction = client.transaction().unwrap();
+    transaction
+        .execute("INSERT INTO sync_two_phase_commit_test VALUES (1)", &[])
+        .unwrap();
+    let xact = transaction.prepare_transaction(gid).unwrap();
+    assert_eq!(xact.gid(), gid);
+    assert!(other.list_prepared().unwrap().contains(&xact));
+
+    other.commit_prepared(gid).unwrap();
+    let row = client
+        .query_one("SELECT count(*) FROM sync_two_phase_commit_test", &[])
+        .unwrap();
+    assert_eq!(row.get::<_, i64>(0), 1);
+
+    client.rollback_prepared(gid).unwrap_err();
+    client
+        .batch_execute("DROP TABLE sync_two_phase_commit_test")
+        .unwrap();
+}
@@ -0,0 +1,158 @@
+//! Utilities for working with the PostgreSQL text and CSV copy formats.
+
+use crate::connection::ConnectionRef;
+use crate::types::{BorrowToSql, ToSql, Type};
+use crate::{CopyInWriter, CopyOutReader, Error};
+use fallible_iterator::FallibleIterator;
+use futures_util::StreamExt;
+use std::pin::Pin;
+use tokio_postgres::text_copy::{self, CsvCopyOutStream, TextCopyOutStream};
+#[doc(inline)]
+pub use tokio_postgres::text_copy::{CsvOptions, TextCopyOutRow, TextOptions};
+
+macro_rules! copy_in_writer {
+    ($(#[$attr:meta])* $name:ident, $options:ident) => {
+        $(#[$attr])*
+        pub struct $name<'a> {
+            connection: ConnectionRef<'a>,
+            sink: Pin<Box<text_copy::$name>>,
+        }
+
+        impl<'a> $name<'a> {
+            /// Creates a new writer which will write rows of the provided types.
+            pub fn new(writer: CopyInWriter<'a>, types: &[Type], options: $options) -> $name<'a> {
+                let stream = writer
+                    .sink
+                    .into_unpinned()
+                    .expect("writer has already been written to");
+
+                $name {
+                    connection: writer.connection,
+                    sink: Box::pin(text_copy::$name::new(stream, types, options)),
+                }
+            }
+
+            /// Writes a single row.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the number of values provided does not match the number expected.
+            pub fn write(&mut self, values: &[&(dyn ToSql + Sync)]) -> Result<(), Error> {
+                self.connection.block_on(self.sink.as_mut().write(values))
+            }
+
+            /// A maximally-flexible version of `write`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the number of values provided does not match the number expected.
+            pub fn write_raw<P, I>(&mut self, values: I) -> Result<(), Error>
+            where
+                P: BorrowToSql,
+                I: IntoIterator<Item = P>,
+                I::IntoIter: ExactSizeIterator,
+            {
+                self.connection
+                    .block_on(self.sink.as_mut().write_raw(values))
+            }
+
+            /// Writes a single row of values already in their PostgreSQL text representation, with `None`
+            /// representing a null value.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the number of values provided does not match the number expected.
+            pub fn write_text(&mut self, fields: &[Option<&str>]) -> Result<(), Error> {
+                self.connection
+                    .block_on(self.sink.as_mut().write_text(fields))
+            }
+
+            /// Writes a header line containing the provided column names.
+            ///
+            /// This should only be used when the `COPY` statement has the `HEADER` option, and must be called
+            /// before any rows are written.
+            pub fn write_header(&mut self, names: &[&str]) -> Result<(), Error> {
+                self.connection
+                    .block_on(self.sink.as_mut().write_header(names))
+            }
+
+            /// Completes the copy, returning the number of rows added.
+            ///
+            /// This method *must* be used to complete the copy process. If it is not, the copy will be aborted.
+            pub fn finish(mut self) -> Result<u64, Error> {
+                self.connection.block_on(self.sink.as_mut().finish())
+            }
+        }
+    };
+}
+
+copy_in_writer!(
+    /// A type which serializes rows into the PostgreSQL text copy format.
+    ///
+    /// The copy *must* be explicitly completed via the `finish` method. If it is not, the copy will be aborted.
+    TextCopyInWriter,
+    TextOptions
+);
+
+copy_in_writer!(
+    /// A type which serializes rows into the PostgreSQL CSV copy format.
+    ///
+    /// The copy *must* be explicitly completed via the `finish` method. If it is not, the copy will be aborted.
+    CsvCopyInWriter,
+    CsvOptions
+);
+
+macro_rules! copy_out_iter {
+    ($(#[$attr:meta])* $name:ident, $stream:ident, $options:ident) => {
+        $(#[$attr])*
+        pub struct $name<'a> {
+            connection: ConnectionRef<'a>,
+            stream: Pin<Box<$stream>>,
+        }
+
+        impl<'a> $name<'a> {
+            /// Creates a new iterator from a raw copy out reader.
+            pub fn new(reader: CopyOutReader<'a>, options: $options) -> $name<'a> {
+                let stream = reader
+                    .stream
+                    .into_unpinned()
+                    .expect("reader has already been read from");
+
+                $name {
+                    connection: reader.connection,
+                    stream: Box::pin($stream::new(stream, options)),
+                }
+            }
+
+            /// Returns the header line, if the iterator's options specify one and it has been read.
+            pub fn header(&self) -> Option<&TextCopyOutRow> {
+                self.stream.header()
+            }
+        }
+
+        impl FallibleIterator for $name<'_> {
+            type Item = TextCopyOutRow;
+            type Error = Error;
+
+            fn next(&mut self) -> Result<Option<TextCopyOutRow>, Error> {
+                let stream = &mut self.stream;
+                self.connection
+                    .block_on(async { stream.next().await.transpose() })
+            }
+        }
+    };
+}
+
+copy_out_iter!(
+    /// An iterator of rows parsed from the PostgreSQL text copy format.
+    TextCopyOutIter,
+    TextCopyOutStream,
+    TextOptions
+);
+
+copy_out_iter!(
+    /// An iterator of rows parsed from the PostgreSQL CSV copy format.
+    CsvCopyOutIter,
+    CsvCopyOutStream,
+    CsvOptions
+);
@@ -1,7 +1,12 @@
+use crate::bulk_load::BulkLoader;
 use crate::connection::ConnectionRef;
-use crate::{CancelToken, CopyInWriter, CopyOutReader, Portal, RowIter, Statement, ToStatement};
-use tokio_postgres::types::{BorrowToSql, ToSql, Type};
-use tokio_postgres::{Error, Row, SimpleQueryMessage};
+use crate::large_object::{LargeObject, Mode};
+use crate::{
+    CancelToken, CopyInWriter, CopyOutReader, CursorIter, Portal, RowIter, Statement, ToStatement,
+};
+use bytes::Bytes;
+use tokio_postgres::types::{BorrowToSql, Format, Oid, ToSql, Type};
+use tokio_postgres::{Column, Error, PreparedXact, Row, SimpleQueryMessage};
 
 /// A representation of a PostgreSQL database transaction.
 ///
@@ -31,6 +36,13 @@ impl<'a> Transaction<'a> {
         }
     }
 
+    pub(crate) fn parts(&mut self) -> (ConnectionRef<'_>, &tokio_postgres::Client) {
+        (
+            self.connection.as_ref(),
+            self.transaction.as_ref().unwrap().client(),
+        )
+    }
+
     /// Consumes the transaction, committing all changes made within it.
     pub fn commit(mut self) -> Result<(), Error> {
         self.connection
@@ -45,6 +57,19 @@ impl<'a> Transaction<'a> {
             .block_on(self.transaction.take().unwrap().rollback())
     }
 
+    /// Consumes the transaction, preparing it for two-phase commit with the specified global identifier.
+    ///
+    /// The transaction must later be completed with `Client::commit_prepared` or `Client::rollback_prepared`, possibly
+    /// from a different connection. If preparation fails, the transaction is rolled back.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called on a nested transaction.
+    pub fn prepare_transaction(mut self, gid: &str) -> Result<PreparedXact, Error> {
+        self.connection
+            .block_on(self.transaction.take().unwrap().prepare_transaction(gid))
+    }
+
     /// Like `Client::prepare`.
     pub fn prepare(&mut self, query: &str) -> Result<Statement, Error> {
         self.connection
@@ -101,6 +126,62 @@ impl<'a> Transaction<'a> {
             .block_on(self.transaction.as_ref().unwrap().query_opt(query, params))
     }
 
+    /// Like `Client::execute_named`.
+    pub fn execute_named(
+        &mut self,
+        query: &str,
+        params: &[(&str, &(dyn ToSql + Sync))],
+    ) -> Result<u64, Error> {
+        self.connection.block_on(
+            self.transaction
+                .as_ref()
+                .unwrap()
+                .execute_named(query, params),
+        )
+    }
+
+    /// Like `Client::query_named`.
+    pub fn query_named(
+        &mut self,
+        query: &str,
+        params: &[(&str, &(dyn ToSql + Sync))],
+    ) -> Result<Vec<Row>, Error> {
+        self.connection.block_on(
+            self.transaction
+                .as_ref()
+                .unwrap()
+                .query_named(query, params),
+        )
+    }
+
+    /// Like `Client::query_one_named`.
+    pub fn query_one_named(
+        &mut self,
+        query: &str,
+        params: &[(&str, &(dyn ToSql + Sync))],
+    ) -> Result<Row, Error> {
+        self.connection.block_on(
+            self.transaction
+                .as_ref()
+                .unwrap()
+                .query_one_named(query, params),
+        )
+    }
+
+    /// Like `Client::query_opt_named`.
+    pub fn query_opt_named(
+        &mut self,
+        query: &str,
+        params: &[(&str, &(dyn ToSql + Sync))],
+    ) -> Result<Option<Row>, Error> {
+        self.connection.block_on(
+            self.transaction
+                .as_ref()
+                .unwrap()
+                .query_opt_named(query, params),
+        )
+    }
+
     /// Like `Client::query_raw`.
     pub fn query_raw<T, P, I>(&mut self, query: &T, params: I) -> Result<RowIter<'_>, Error>
     where
@@ -115,6 +196,48 @@ impl<'a> Transaction<'a> {
         Ok(RowIter::new(self.connection.as_ref(), stream))
     }
 
+    /// Like `Client::query_raw_with_formats`.
+    pub fn query_raw_with_formats<T, P, I, F>(
+        &mut self,
+        query: &T,
+        params: I,
+        formats: F,
+    ) -> Result<RowIter<'_>, Error>
+    where
+        T: ?Sized + ToStatement,
+        P: BorrowToSql,
+        I: IntoIterator<Item = P>,
+        I::IntoIter: ExactSizeIterator,
+        F: FnMut(&Column) -> Format,
+    {
+        let stream = self.connection.block_on(
+            self.transaction
+                .as_ref()
+                .unwrap()
+                .query_raw_with_formats(query, params, formats),
+        )?;
+        Ok(RowIter::new(self.connection.as_ref(), stream))
+    }
+
+    /// Like `tokio_postgres::Transaction::cursor`.
+    pub fn cursor<T>(
+        &mut self,
+        query: &T,
+        params: &[&(dyn ToSql + Sync)],
+        fetch_size: i32,
+    ) -> Result<CursorIter<'_>, Error>
+    where
+        T: ?Sized + ToStatement,
+    {
+        let stream = self.connection.block_on(
+            self.transaction
+                .as_ref()
+                .unwrap()
+                .cursor(query, params, fetch_size),
+        )?;
+        Ok(CursorIter::new(self.connection.as_ref(), stream))
+    }
+
     /// Binds parameters to a statement, creating a "portal".
     ///
     /// Portals can be used with the `query_portal` method to page through the results of a query without being forced
@@ -133,6 +256,28 @@ impl<'a> Transaction<'a> {
             .block_on(self.transaction.as_ref().unwrap().bind(query, params))
     }
 
+    /// Like `tokio_postgres::Transaction::bind_raw_with_formats`.
+    pub fn bind_raw_with_formats<T, P, I, F>(
+        &mut self,
+        query: &T,
+        params: I,
+        formats: F,
+    ) -> Result<Portal, Error>
+    where
+        T: ?Sized + ToStatement,
+        P: BorrowToSql,
+        I: IntoIterator<Item = P>,
+        I::IntoIter: ExactSizeIterator,
+        F: FnMut(&Column) -> Format,
+    {
+        self.connection.block_on(
+            self.transaction
+                .as_ref()
+                .unwrap()
+                .bind_raw_with_formats(query, params, formats),
+        )
+    }
+
     /// Continues execution of a portal, returning the next set of rows.
     ///
     /// Unlike `query`, portals can be incrementally evaluated by limiting the number of rows returned in each call to
@@ -195,6 +340,54 @@ impl<'a> Transaction<'a> {
             .block_on(self.transaction.as_ref().unwrap().batch_execute(query))
     }
 
+    /// Like `Client::function_call`.
+    pub fn function_call(
+        &mut self,
+        function: Oid,
+        args: &[(&(dyn ToSql + Sync), Type)],
+    ) -> Result<Option<Bytes>, Error> {
+        self.connection.block_on(
+            self.transaction
+                .as_ref()
+                .unwrap()
+                .function_call(function, args),
+        )
+    }
+
+    /// Like `tokio_postgres::Transaction::create_large_object`.
+    pub fn create_large_object(&mut self) -> Result<Oid, Error> {
+        self.connection
+            .block_on(self.transaction.as_ref().unwrap().create_large_object())
+    }
+
+    /// Like `tokio_postgres::Transaction::open_large_object`.
+    pub fn open_large_object(&mut self, oid: Oid, mode: Mode) -> Result<LargeObject<'_>, Error> {
+        let object = self.connection.block_on(
+            self.transaction
+                .as_ref()
+                .unwrap()
+                .open_large_object(oid, mode),
+        )?;
+        Ok(LargeObject::new(self.connection.as_ref(), object))
+    }
+
+    /// Like `tokio_postgres::Transaction::unlink_large_object`.
+    pub fn unlink_large_object(&mut self, oid: Oid) -> Result<(), Error> {
+        self.connection
+            .block_on(self.transaction.as_ref().unwrap().unlink_large_object(oid))
+    }
+
+    /// Like `tokio_postgres::Transaction::bulk_loader`.
+    pub fn bulk_loader(&mut self, table: &str, key_columns: &[&str]) -> BulkLoader<'_> {
+        BulkLoader::new(
+            self.connection.as_ref(),
+            self.transaction
+                .as_ref()
+                .unwrap()
+                .bulk_loader(table, key_columns),
+        )
+    }
+
     /// Like `Client::cancel_token`.
     pub fn cancel_token(&self) -> CancelToken {
         CancelToken::new(self.transaction.as_ref().unwrap().cancel_token())
@@ -0,0 +1,624 @@
+//! Conversions between query results and Apache Arrow record batches.
+//!
+//! Requires the `arrow` Cargo feature.
+//!
+//! Values are decoded directly from their binary representation into Arrow array builders. The following Postgres types
+//! are supported, along with domains over them:
+//!
+//! | Postgres type                                  | Arrow type                          |
+//! |------------------------------------------------|-------------------------------------|
+//! | `BOOL`                                         | `Boolean`                           |
+//! | `"char"`                                       | `Int8`                              |
+//! | `INT2`                                         | `Int16`                             |
+//! | `INT4`                                         | `Int32`                             |
+//! | `INT8`                                         | `Int64`                             |
+//! | `OID`                                          | `UInt32`                            |
+//! | `FLOAT4`                                       | `Float32`                           |
+//! | `FLOAT8`                                       | `Float64`                           |
+//! | `TEXT`, `VARCHAR`, `CHAR(n)`, `NAME`, `JSON`, `JSONB`, `XML`, enums | `Utf8`           |
+//! | `BYTEA`                                        | `Binary`                            |
+//! | `UUID`                                         | `FixedSizeBinary(16)`               |
+//! | `DATE`                                         | `Date32`                            |
+//! | `TIMESTAMP`                                    | `Timestamp(Microsecond, None)`      |
+//! | `TIMESTAMPTZ`                                  | `Timestamp(Microsecond, Some("UTC"))` |
+//! | `TIME`                                         | `Time64(Microsecond)`               |
+//!
+//! When writing record batches, `LargeUtf8` and `LargeBinary` arrays are also accepted, as are timestamps of any unit.
+
+use crate::binary_copy::{BinaryCopyOutRow, BinaryCopyOutStream};
+use crate::query::RowStream;
+use crate::types::{Format, IsNull, Kind, ToSql, Type};
+use crate::{Column, Error, Row};
+use arrow_array_53::builder::{
+    BinaryBuilder, BooleanBuilder, Date32Builder, FixedSizeBinaryBuilder, Float32Builder,
+    Float64Builder, Int16Builder, Int32Builder, Int64Builder, Int8Builder, StringBuilder,
+    Time64MicrosecondBuilder, TimestampMicrosecondBuilder, UInt32Builder,
+};
+use arrow_array_53::cast::AsArray;
+use arrow_array_53::types::{
+    Date32Type, Float32Type, Float64Type, Int16Type, Int32Type, Int64Type, Int8Type,
+    Time64MicrosecondType, TimestampMicrosecondType, TimestampMillisecondType,
+    TimestampNanosecondType, TimestampSecondType, UInt32Type,
+};
+use arrow_array_53::{Array, ArrayRef};
+use arrow_schema_53::{DataType, Field, Schema, TimeUnit};
+use bytes::{BufMut, BytesMut};
+use futures_util::{ready, Stream};
+use pin_project_lite::pin_project;
+use postgres_protocol::types;
+use postgres_types::to_sql_checked;
+use std::convert::TryInto;
+use std::error;
+use std::pin::Pin;
+use std::str;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+#[doc(no_inline)]
+pub use arrow_array_53::RecordBatch;
+#[doc(no_inline)]
+pub use arrow_schema_53::SchemaRef;
+
+// days and microseconds between the Unix and Postgres epochs
+const EPOCH_DAYS: i32 = 10_957;
+const EPOCH_MICROS: i64 = 946_684_800_000_000;
+
+enum ColumnBuilder {
+    Bool(BooleanBuilder),
+    Char(Int8Builder),
+    Int2(Int16Builder),
+    Int4(Int32Builder),
+    Int8(Int64Builder),
+    Oid(UInt32Builder),
+    Float4(Float32Builder),
+    Float8(Float64Builder),
+    Text(StringBuilder),
+    Jsonb(StringBuilder),
+    Bytea(BinaryBuilder),
+    Uuid(FixedSizeBinaryBuilder),
+    Date(Date32Builder),
+    Timestamp(TimestampMicrosecondBuilder),
+    Time(Time64MicrosecondBuilder),
+}
+
+impl ColumnBuilder {
+    fn new(type_: &Type) -> Option<ColumnBuilder> {
+        match type_.kind() {
+            Kind::Enum(_) => return Some(ColumnBuilder::Text(StringBuilder::new())),
+            Kind::Domain(base) => return ColumnBuilder::new(base),
+            _ => {}
+        }
+
+        let builder = match *type_ {
+            Type::BOOL => ColumnBuilder::Bool(BooleanBuilder::new()),
+            Type::CHAR => ColumnBuilder::Char(Int8Builder::new()),
+            Type::INT2 => ColumnBuilder::Int2(Int16Builder::new()),
+            Type::INT4 => ColumnBuilder::Int4(Int32Builder::new()),
+            Type::INT8 => ColumnBuilder::Int8(Int64Builder::new()),
+            Type::OID => ColumnBuilder::Oid(UInt32Builder::new()),
+            Type::FLOAT4 => ColumnBuilder::Float4(Float32Builder::new()),
+            Type::FLOAT8 => ColumnBuilder::Float8(Float64Builder::new()),
+            Type::TEXT
+            | Type::VARCHAR
+            | Type::BPCHAR
+            | Type::NAME
+            | Type::UNKNOWN
+            | Type::JSON
+            | Type::XML => ColumnBuilder::Text(StringBuilder::new()),
+            Type::JSONB => ColumnBuilder::Jsonb(StringBuilder::new()),
+            Type::BYTEA => ColumnBuilder::Bytea(BinaryBuilder::new()),
+            Type::UUID => ColumnBuilder::Uuid(FixedSizeBinaryBuilder::new(16)),
+            Type::DATE => ColumnBuilder::Date(Date32Builder::new()),
+            Type::TIMESTAMP => ColumnBuilder::Timestamp(TimestampMicrosecondBuilder::new()),
+            Type::TIMESTAMPTZ => {
+                ColumnBuilder::Timestamp(TimestampMicrosecondBuilder::new().with_timezone("UTC"))
+            }
+            Type::TIME => ColumnBuilder::Time(Time64MicrosecondBuilder::new()),
+            _ => return None,
+        };
+        Some(builder)
+    }
+
+    fn append_null(&mut self) {
+        match self {
+            ColumnBuilder::Bool(b) => b.append_null(),
+            ColumnBuilder::Char(b) => b.append_null(),
+            ColumnBuilder::Int2(b) => b.append_null(),
+            ColumnBuilder::Int4(b) => b.append_null(),
+            ColumnBuilder::Int8(b) => b.append_null(),
+            ColumnBuilder::Oid(b) => b.append_null(),
+            ColumnBuilder::Float4(b) => b.append_null(),
+            ColumnBuilder::Float8(b) => b.append_null(),
+            ColumnBuilder::Text(b) | ColumnBuilder::Jsonb(b) => b.append_null(),
+            ColumnBuilder::Bytea(b) => b.append_null(),
+            ColumnBuilder::Uuid(b) => b.append_null(),
+            ColumnBuilder::Date(b) => b.append_null(),
+            ColumnBuilder::Timestamp(b) => b.append_null(),
+            ColumnBuilder::Time(b) => b.append_null(),
+        }
+    }
+
+    fn append(&mut self, raw: Option<&[u8]>) -> Result<(), Box<dyn error::Error + Sync + Send>> {
+        let raw = match raw {
+            Some(raw) => raw,
+            None => {
+                self.append_null();
+                return Ok(());
+            }
+        };
+
+        match self {
+            ColumnBuilder::Bool(b) => b.append_value(types::bool_from_sql(raw)?),
+            ColumnBuilder::Char(b) => b.append_value(types::char_from_sql(raw)?),
+            ColumnBuilder::Int2(b) => b.append_value(types::int2_from_sql(raw)?),
+            ColumnBuilder::Int4(b) => b.append_value(types::int4_from_sql(raw)?),
+            ColumnBuilder::Int8(b) => b.append_value(types::int8_from_sql(raw)?),
+            ColumnBuilder::Oid(b) => b.append_value(types::oid_from_sql(raw)?),
+            ColumnBuilder::Float4(b) => b.append_value(types::float4_from_sql(raw)?),
+            ColumnBuilder::Float8(b) => b.append_value(types::float8_from_sql(raw)?),
+            ColumnBuilder::Text(b) => b.append_value(types::text_from_sql(raw)?),
+            ColumnBuilder::Jsonb(b) => match raw.split_first() {
+                Some((1, json)) => b.append_value(str::from_utf8(json)?),
+                _ => return Err("unsupported JSONB encoding version".into()),
+            },
+            ColumnBuilder::Bytea(b) => b.append_value(types::bytea_from_sql(raw)),
+            ColumnBuilder::Uuid(b) => b.append_value(types::uuid_from_sql(raw)?)?,
+            ColumnBuilder::Date(b) => b.append_value(
+                types::date_from_sql(raw)?
+                    .checked_add(EPOCH_DAYS)
+                    .ok_or("value too large to decode")?,
+            ),
+            ColumnBuilder::Timestamp(b) => b.append_value(
+                types::timestamp_from_sql(raw)?
+                    .checked_add(EPOCH_MICROS)
+                    .ok_or("value too large to decode")?,
+            ),
+            ColumnBuilder::Time(b) => b.append_value(types::time_from_sql(raw)?),
+        }
+
+        Ok(())
+    }
+
+    fn finish(&mut self) -> ArrayRef {
+        match self {
+            ColumnBuilder::Bool(b) => Arc::new(b.finish()),
+            ColumnBuilder::Char(b) => Arc::new(b.finish()),
+            ColumnBuilder::Int2(b) => Arc::new(b.finish()),
+            ColumnBuilder::Int4(b) => Arc::new(b.finish()),
+            ColumnBuilder::Int8(b) => Arc::new(b.finish()),
+            ColumnBuilder::Oid(b) => Arc::new(b.finish()),
+            ColumnBuilder::Float4(b) => Arc::new(b.finish()),
+            ColumnBuilder::Float8(b) => Arc::new(b.finish()),
+            ColumnBuilder::Text(b) | ColumnBuilder::Jsonb(b) => Arc::new(b.finish()),
+            ColumnBuilder::Bytea(b) => Arc::new(b.finish()),
+            ColumnBuilder::Uuid(b) => Arc::new(b.finish()),
+            ColumnBuilder::Date(b) => Arc::new(b.finish()),
+            ColumnBuilder::Timestamp(b) => Arc::new(b.finish()),
+            ColumnBuilder::Time(b) => Arc::new(b.finish()),
+        }
+    }
+}
+
+/// A builder which accumulates rows into Arrow record batches.
+pub struct RecordBatchBuilder {
+    schema: SchemaRef,
+    columns: Vec<ColumnBuilder>,
+    len: usize,
+}
+
+impl RecordBatchBuilder {
+    /// Creates a new builder for rows with the provided columns.
+    ///
+    /// Returns an error if a column's type is not supported.
+    pub fn new(columns: &[Column]) -> Result<RecordBatchBuilder, Error> {
+        let names = columns.iter().map(Column::name).collect::<Vec<_>>();
+        let types = columns
+            .iter()
+            .map(|c| c.type_().clone())
+            .collect::<Vec<_>>();
+        RecordBatchBuilder::from_types(&names, &types)
+    }
+
+    /// Creates a new builder for rows with the provided column names and types.
+    ///
+    /// Returns an error if a column's type is not supported.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of names does not match the number of types.
+    pub fn from_types(names: &[&str], types: &[Type]) -> Result<RecordBatchBuilder, Error> {
+        assert!(
+            names.len() == types.len(),
+            "expected {} names but got {}",
+            types.len(),
+            names.len(),
+        );
+
+        let mut fields = vec![];
+        let mut columns = vec![];
+        for (i, (name, type_)) in names.iter().zip(types).enumerate() {
+            let mut column = ColumnBuilder::new(type_).ok_or_else(|| {
+                Error::from_sql(
+                    format!("type {} cannot be converted to Arrow", type_).into(),
+                    i,
+                )
+            })?;
+            fields.push(Field::new(*name, column.finish().data_type().clone(), true));
+            columns.push(column);
+        }
+
+        Ok(RecordBatchBuilder {
+            schema: Arc::new(Schema::new(fields)),
+            columns,
+            len: 0,
+        })
+    }
+
+    /// Returns the schema of the record batches produced by the builder.
+    pub fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    /// Returns the number of rows appended since the last batch was finished.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Determines if no rows have been appended since the last batch was finished.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Appends a row returned by a query.
+    ///
+    /// If an error is returned, the remaining values of the row are appended as nulls. Values returned in the text
+    /// format cannot be appended.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the row does not have the number of columns expected.
+    pub fn append_row(&mut self, row: &Row) -> Result<(), Error> {
+        self.append(row.len(), |i| match row.format(i) {
+            Some(Format::Text) => Err("value was returned in text format".into()),
+            _ => Ok(row.col_buffer(i)),
+        })
+    }
+
+    /// Appends a row parsed from a binary copy out stream.
+    ///
+    /// If an error is returned, the remaining values of the row are appended as nulls.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the row does not have the number of columns expected.
+    pub fn append_copy_row(&mut self, row: &BinaryCopyOutRow) -> Result<(), Error> {
+        self.append(row.types().len(), |i| Ok(row.col_buffer(i)))
+    }
+
+    fn append<'a, F>(&mut self, len: usize, mut value: F) -> Result<(), Error>
+    where
+        F: FnMut(usize) -> Result<Option<&'a [u8]>, Box<dyn error::Error + Sync + Send>>,
+    {
+        assert!(
+            len == self.columns.len(),
+            "expected {} values but got {}",
+            self.columns.len(),
+            len,
+        );
+
+        self.len += 1;
+        let mut columns = self.columns.iter_mut().enumerate();
+        for (i, column) in &mut columns {
+            if let Err(e) = value(i).and_then(|raw| column.append(raw)) {
+                column.append_null();
+                columns.for_each(|(_, column)| column.append_null());
+                return Err(Error::from_sql(e, i));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns a record batch of the rows appended since the last batch was finished, and resets the builder.
+    pub fn finish(&mut self) -> RecordBatch {
+        self.len = 0;
+        let columns = self.columns.iter_mut().map(ColumnBuilder::finish).collect();
+        RecordBatch::try_new(self.schema.clone(), columns).expect("columns should match the schema")
+    }
+}
+
+fn poll_batch<S, T, F>(
+    mut stream: Pin<&mut S>,
+    cx: &mut Context<'_>,
+    builder: &mut RecordBatchBuilder,
+    batch_size: usize,
+    done: &mut bool,
+    mut append: F,
+) -> Poll<Option<Result<RecordBatch, Error>>>
+where
+    S: Stream<Item = Result<T, Error>>,
+    F: FnMut(&mut RecordBatchBuilder, &T) -> Result<(), Error>,
+{
+    if *done {
+        return Poll::Ready(None);
+    }
+
+    loop {
+        match ready!(stream.as_mut().poll_next(cx)) {
+            Some(Ok(row)) => {
+                append(builder, &row)?;
+                if builder.len() >= batch_size {
+                    return Poll::Ready(Some(Ok(builder.finish())));
+                }
+            }
+            Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+            None => {
+                *done = true;
+                if builder.is_empty() {
+                    return Poll::Ready(None);
+                }
+                return Poll::Ready(Some(Ok(builder.finish())));
+            }
+        }
+    }
+}
+
+pin_project! {
+    /// A stream of record batches built from the rows of a query.
+    pub struct RecordBatchStream {
+        #[pin]
+        rows: RowStream,
+        builder: RecordBatchBuilder,
+        batch_size: usize,
+        done: bool,
+    }
+}
+
+impl RecordBatchStream {
+    /// Creates a stream of record batches of up to `batch_size` rows from a stream of query rows.
+    ///
+    /// The schema is taken from the columns of the query. Returns an error if a column's type is not supported.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `batch_size` is 0.
+    pub fn new(rows: RowStream, batch_size: usize) -> Result<RecordBatchStream, Error> {
+        assert!(batch_size > 0, "batch size must be positive");
+
+        let builder = RecordBatchBuilder::new(rows.statement().columns())?;
+        Ok(RecordBatchStream {
+            rows,
+            builder,
+            batch_size,
+            done: false,
+        })
+    }
+
+    /// Returns the schema of the record batches.
+    pub fn schema(&self) -> SchemaRef {
+        self.builder.schema()
+    }
+}
+
+impl Stream for RecordBatchStream {
+    type Item = Result<RecordBatch, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        poll_batch(
+            this.rows,
+            cx,
+            this.builder,
+            *this.batch_size,
+            this.done,
+            RecordBatchBuilder::append_row,
+        )
+    }
+}
+
+pin_project! {
+    /// A stream of record batches built from the rows of a binary copy out stream.
+    pub struct BinaryCopyRecordBatchStream {
+        #[pin]
+        stream: BinaryCopyOutStream,
+        builder: RecordBatchBuilder,
+        batch_size: usize,
+        done: bool,
+    }
+}
+
+impl BinaryCopyRecordBatchStream {
+    /// Creates a stream of record batches of up to `batch_size` rows from a binary copy out stream.
+    ///
+    /// The schema is built from the provided column names and the types of the stream. Returns an error if a column's
+    /// type is not supported.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `batch_size` is 0, or if the number of names does not match the number of columns.
+    pub fn new(
+        stream: BinaryCopyOutStream,
+        names: &[&str],
+        batch_size: usize,
+    ) -> Result<BinaryCopyRecordBatchStream, Error> {
+        assert!(batch_size > 0, "batch size must be positive");
+
+        let builder = RecordBatchBuilder::from_types(names, stream.types())?;
+        Ok(BinaryCopyRecordBatchStream {
+            stream,
+            builder,
+            batch_size,
+            done: false,
+        })
+    }
+
+    /// Returns the schema of the record batches.
+    pub fn schema(&self) -> SchemaRef {
+        self.builder.schema()
+    }
+}
+
+impl Stream for BinaryCopyRecordBatchStream {
+    type Item = Result<RecordBatch, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        poll_batch(
+            this.stream,
+            cx,
+            this.builder,
+            *this.batch_size,
+            this.done,
+            RecordBatchBuilder::append_copy_row,
+        )
+    }
+}
+
+/// A single value of an Arrow array, serialized according to the Postgres type it is written as.
+#[derive(Debug)]
+pub(crate) struct ArrowValue<'a> {
+    array: &'a dyn Array,
+    idx: usize,
+}
+
+impl<'a> ArrowValue<'a> {
+    pub(crate) fn new(array: &'a dyn Array, idx: usize) -> ArrowValue<'a> {
+        ArrowValue { array, idx }
+    }
+}
+
+impl ToSql for ArrowValue<'_> {
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn error::Error + Sync + Send>> {
+        if self.array.is_null(self.idx) {
+            return Ok(IsNull::Yes);
+        }
+
+        encode(self.array, self.idx, ty, out)?;
+        Ok(IsNull::No)
+    }
+
+    fn accepts(_: &Type) -> bool {
+        true
+    }
+
+    to_sql_checked!();
+}
+
+fn encode(
+    array: &dyn Array,
+    idx: usize,
+    type_: &Type,
+    out: &mut BytesMut,
+) -> Result<(), Box<dyn error::Error + Sync + Send>> {
+    match type_.kind() {
+        Kind::Enum(_) => return encode(array, idx, &Type::TEXT, out),
+        Kind::Domain(base) => return encode(array, idx, base, out),
+        _ => {}
+    }
+
+    match (type_, array.data_type()) {
+        (&Type::BOOL, DataType::Boolean) => types::bool_to_sql(array.as_boolean().value(idx), out),
+        (&Type::CHAR, DataType::Int8) => {
+            types::char_to_sql(array.as_primitive::<Int8Type>().value(idx), out)
+        }
+        (&Type::INT2, DataType::Int16) => {
+            types::int2_to_sql(array.as_primitive::<Int16Type>().value(idx), out)
+        }
+        (&Type::INT4, DataType::Int32) => {
+            types::int4_to_sql(array.as_primitive::<Int32Type>().value(idx), out)
+        }
+        (&Type::INT8, DataType::Int64) => {
+            types::int8_to_sql(array.as_primitive::<Int64Type>().value(idx), out)
+        }
+        (&Type::OID, DataType::UInt32) => {
+            types::oid_to_sql(array.as_primitive::<UInt32Type>().value(idx), out)
+        }
+        (&Type::FLOAT4, DataType::Float32) => {
+            types::float4_to_sql(array.as_primitive::<Float32Type>().value(idx), out)
+        }
+        (&Type::FLOAT8, DataType::Float64) => {
+            types::float8_to_sql(array.as_primitive::<Float64Type>().value(idx), out)
+        }
+        (
+            &Type::TEXT
+            | &Type::VARCHAR
+            | &Type::BPCHAR
+            | &Type::NAME
+            | &Type::UNKNOWN
+            | &Type::JSON
+            | &Type::XML
+            | &Type::JSONB,
+            DataType::Utf8 | DataType::LargeUtf8,
+        ) => {
+            let value = match array.data_type() {
+                DataType::Utf8 => array.as_string::<i32>().value(idx),
+                _ => array.as_string::<i64>().value(idx),
+            };
+            if *type_ == Type::JSONB {
+                out.put_u8(1);
+            }
+            types::text_to_sql(value, out);
+        }
+        (&Type::BYTEA, DataType::Binary) => {
+            types::bytea_to_sql(array.as_binary::<i32>().value(idx), out)
+        }
+        (&Type::BYTEA, DataType::LargeBinary) => {
+            types::bytea_to_sql(array.as_binary::<i64>().value(idx), out)
+        }
+        (&Type::UUID, DataType::FixedSizeBinary(16)) => {
+            let value = array.as_fixed_size_binary().value(idx);
+            types::uuid_to_sql(value.try_into()?, out)
+        }
+        (&Type::DATE, DataType::Date32) => {
+            let value = array.as_primitive::<Date32Type>().value(idx);
+            types::date_to_sql(
+                value
+                    .checked_sub(EPOCH_DAYS)
+                    .ok_or("value too large to transmit")?,
+                out,
+            );
+        }
+        (&Type::TIMESTAMP, DataType::Timestamp(unit, None))
+        | (&Type::TIMESTAMPTZ, DataType::Timestamp(unit, Some(_))) => {
+            let micros = match unit {
+                TimeUnit::Second => array
+                    .as_primitive::<TimestampSecondType>()
+                    .value(idx)
+                    .checked_mul(1_000_000),
+                TimeUnit::Millisecond => array
+                    .as_primitive::<TimestampMillisecondType>()
+                    .value(idx)
+                    .checked_mul(1_000),
+                TimeUnit::Microsecond => {
+                    Some(array.as_primitive::<TimestampMicrosecondType>().value(idx))
+                }
+                TimeUnit::Nanosecond => {
+                    Some(array.as_primitive::<TimestampNanosecondType>().value(idx) / 1_000)
+                }
+            };
+            types::timestamp_to_sql(
+                micros
+                    .and_then(|v| v.checked_sub(EPOCH_MICROS))
+                    .ok_or("value too large to transmit")?,
+                out,
+            );
+        }
+        (&Type::TIME, DataType::Time64(TimeUnit::Microsecond)) => types::time_to_sql(
+            array.as_primitive::<Time64MicrosecondType>().value(idx),
+            out,
+        ),
+        (type_, data_type) => {
+            return Err(format!(
+                "cannot convert Arrow type {} to Postgres type {}",
+                data_type, type_
+            )
+            .into())
+        }
+    }
+
+    Ok(())
+}
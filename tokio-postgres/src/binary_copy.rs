@@ -1,11 +1,12 @@
 //! Utilities for working with the PostgreSQL binary copy format.
 
-use crate::types::{FromSql, IsNull, ToSql, Type, WrongType};
-use crate::{slice_iter, CopyInSink, CopyOutStream, Error};
+use crate::types::{FromSql, IsNull, Kind, Oid, ToSql, Type, WrongType};
+use crate::{prepare, slice_iter, CopyInSink, CopyOutStream, Error, GenericClient};
 use byteorder::{BigEndian, ByteOrder};
 use bytes::{Buf, BufMut, Bytes, BytesMut};
 use futures_util::{ready, SinkExt, Stream};
 use pin_project_lite::pin_project;
+use postgres_protocol::escape::escape_identifier;
 use postgres_types::BorrowToSql;
 use std::convert::TryFrom;
 use std::io;
@@ -27,6 +28,9 @@ pin_project! {
         sink: CopyInSink<Bytes>,
         types: Vec<Type>,
         buf: BytesMut,
+        row_buf: BytesMut,
+        rows_written: u64,
+        progress: Option<Box<dyn FnMut(u64, u64) + Send + Sync>>,
     }
 }
 
@@ -42,9 +46,98 @@ impl BinaryCopyInWriter {
             sink,
             types: types.to_vec(),
             buf,
+            row_buf: BytesMut::new(),
+            rows_written: 0,
+            progress: None,
         }
     }
 
+    /// Returns the number of rows written so far.
+    pub fn rows_written(&self) -> u64 {
+        self.rows_written
+    }
+
+    /// Returns the number of bytes of copy data sent to the connection so far.
+    ///
+    /// Rows are buffered internally, so this may lag behind the rows written.
+    pub fn bytes_sent(&self) -> u64 {
+        self.sink.bytes_sent()
+    }
+
+    /// Sets a callback which is invoked with the total number of rows and bytes sent each time buffered rows are sent
+    /// to the connection.
+    pub fn set_progress_callback<F>(self: Pin<&mut Self>, callback: F)
+    where
+        F: FnMut(u64, u64) + Send + Sync + 'static,
+    {
+        *self.project().progress = Some(Box::new(callback));
+    }
+
+    /// Starts a binary copy into the specified columns of a table, looking up their types from the database.
+    ///
+    /// If no columns are specified, all columns of the table are copied in table order. The table name is included in
+    /// the generated SQL as is, and so may be schema-qualified but must be quoted if necessary. Enum and composite
+    /// column types are resolved to their full definitions, so values are checked against the actual column types as
+    /// they are written. Domain columns are written as their base type, leaving the domain's constraints to be checked
+    /// by the server.
+    pub async fn for_table<C>(
+        client: &C,
+        table: &str,
+        columns: &[&str],
+    ) -> Result<BinaryCopyInWriter, Error>
+    where
+        C: GenericClient,
+    {
+        let client = client.client();
+        let attributes = client
+            .query(
+                "SELECT attname, atttypid \
+                 FROM pg_catalog.pg_attribute \
+                 WHERE attrelid = $1::TEXT::regclass AND attnum > 0 AND NOT attisdropped \
+                 ORDER BY attnum",
+                &[&table],
+            )
+            .await?
+            .iter()
+            .map(|row| Ok((row.try_get::<_, String>(0)?, row.try_get::<_, Oid>(1)?)))
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let attributes = if columns.is_empty() {
+            attributes
+        } else {
+            columns
+                .iter()
+                .map(|column| {
+                    attributes
+                        .iter()
+                        .find(|(name, _)| name == column)
+                        .cloned()
+                        .ok_or_else(|| Error::column(column.to_string()))
+                })
+                .collect::<Result<Vec<_>, Error>>()?
+        };
+
+        let names = attributes
+            .iter()
+            .map(|(name, _)| escape_identifier(name))
+            .collect::<Vec<_>>();
+        let oids = attributes.iter().map(|(_, oid)| *oid).collect::<Vec<_>>();
+        let types = prepare::get_types(client.inner(), &oids)
+            .await?
+            .into_iter()
+            .map(base_type)
+            .collect::<Vec<_>>();
+
+        let sink = client
+            .copy_in(&format!(
+                "COPY {} ({}) FROM STDIN BINARY",
+                table,
+                names.join(", ")
+            ))
+            .await?;
+        Ok(BinaryCopyInWriter::new(sink, &types))
+    }
+
     /// Writes a single row.
     ///
     /// # Panics
@@ -75,25 +168,34 @@ impl BinaryCopyInWriter {
             values.len(),
         );
 
-        this.buf.put_i16(this.types.len() as i16);
+        // every value is checked against its column's type as it's encoded, so the row is built separately and only
+        // added to the copy data once all of its values have been accepted
+        this.row_buf.clear();
+        this.row_buf.put_i16(this.types.len() as i16);
 
         for (i, (value, type_)) in values.zip(this.types).enumerate() {
-            let idx = this.buf.len();
-            this.buf.put_i32(0);
+            let idx = this.row_buf.len();
+            this.row_buf.put_i32(0);
             let len = match value
                 .borrow_to_sql()
-                .to_sql_checked(type_, this.buf)
+                .to_sql_checked(type_, this.row_buf)
                 .map_err(|e| Error::to_sql(e, i))?
             {
                 IsNull::Yes => -1,
-                IsNull::No => i32::try_from(this.buf.len() - idx - 4)
+                IsNull::No => i32::try_from(this.row_buf.len() - idx - 4)
                     .map_err(|e| Error::encode(io::Error::new(io::ErrorKind::InvalidInput, e)))?,
             };
-            BigEndian::write_i32(&mut this.buf[idx..], len);
+            BigEndian::write_i32(&mut this.row_buf[idx..], len);
         }
 
+        this.buf.extend_from_slice(this.row_buf);
+        *this.rows_written += 1;
+
         if this.buf.len() > 4096 {
             this.sink.send(this.buf.split().freeze()).await?;
+            if let Some(progress) = this.progress {
+                progress(*this.rows_written, this.sink.bytes_sent());
+            }
         }
 
         Ok(())
@@ -107,8 +209,45 @@ impl BinaryCopyInWriter {
 
         this.buf.put_i16(-1);
         this.sink.send(this.buf.split().freeze()).await?;
+        if let Some(progress) = this.progress {
+            progress(*this.rows_written, this.sink.bytes_sent());
+        }
         this.sink.finish().await
     }
+
+    /// Writes each row of an Arrow record batch.
+    ///
+    /// The columns of the batch must match the types of the writer. See the [`arrow`](crate::arrow) module for the
+    /// supported conversions.
+    ///
+    /// Requires the `arrow` Cargo feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of columns in the batch does not match the number expected.
+    #[cfg(feature = "arrow")]
+    pub async fn write_record_batch(
+        mut self: Pin<&mut Self>,
+        batch: &arrow_array_53::RecordBatch,
+    ) -> Result<(), Error> {
+        for row in 0..batch.num_rows() {
+            let values = batch
+                .columns()
+                .iter()
+                .map(|column| crate::arrow::ArrowValue::new(column.as_ref(), row));
+            self.as_mut().write_raw(values).await?;
+        }
+
+        Ok(())
+    }
+}
+
+// domain values have the same binary representation as their base type
+fn base_type(type_: Type) -> Type {
+    match type_.kind() {
+        Kind::Domain(base) => base_type(base.clone()),
+        _ => type_,
+    }
 }
 
 struct Header {
@@ -134,6 +273,11 @@ impl BinaryCopyOutStream {
             header: None,
         }
     }
+
+    #[cfg(feature = "arrow")]
+    pub(crate) fn types(&self) -> &[Type] {
+        &self.types
+    }
 }
 
 impl Stream for BinaryCopyOutStream {
@@ -231,6 +375,16 @@ pub struct BinaryCopyOutRow {
 }
 
 impl BinaryCopyOutRow {
+    #[cfg(any(feature = "serde", feature = "arrow"))]
+    pub(crate) fn types(&self) -> &[Type] {
+        &self.types
+    }
+
+    #[cfg(any(feature = "serde", feature = "arrow"))]
+    pub(crate) fn col_buffer(&self, idx: usize) -> Option<&[u8]> {
+        self.ranges[idx].clone().map(|r| &self.buf[r])
+    }
+
     /// Like `get`, but returns a `Result` rather than panicking.
     pub fn try_get<'a, T>(&'a self, idx: usize) -> Result<T, Error>
     where
@@ -270,4 +424,17 @@ impl BinaryCopyOutRow {
             Err(e) => panic!("error retrieving column {}: {}", idx, e),
         }
     }
+
+    /// Deserializes the entire row into a value implementing `serde::Deserialize`.
+    ///
+    /// Copy rows carry no column names, so struct fields are mapped to columns by position.
+    ///
+    /// Requires the `serde` Cargo feature.
+    #[cfg(feature = "serde")]
+    pub fn deserialize<'de, T>(&'de self) -> Result<T, Error>
+    where
+        T: serde_1::Deserialize<'de>,
+    {
+        T::deserialize(self)
+    }
 }
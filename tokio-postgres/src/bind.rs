@@ -1,7 +1,7 @@
 use crate::client::InnerClient;
 use crate::codec::FrontendMessage;
 use crate::connection::RequestMessages;
-use crate::types::BorrowToSql;
+use crate::types::{BorrowToSql, Format};
 use crate::{query, Error, Portal, Statement};
 use postgres_protocol::message::backend::Message;
 use postgres_protocol::message::frontend;
@@ -14,6 +14,7 @@ pub async fn bind<P, I>(
     client: &Arc<InnerClient>,
     statement: Statement,
     params: I,
+    formats: Option<Arc<[Format]>>,
 ) -> Result<Portal, Error>
 where
     P: BorrowToSql,
@@ -22,7 +23,7 @@ where
 {
     let name = format!("p{}", NEXT_ID.fetch_add(1, Ordering::SeqCst));
     let buf = client.with_buf(|buf| {
-        query::encode_bind(&statement, params, &name, buf)?;
+        query::encode_bind(&statement, params, formats.as_deref(), &name, buf)?;
         frontend::sync(buf);
         Ok(buf.split().freeze())
     })?;
@@ -34,5 +35,5 @@ where
         _ => return Err(Error::unexpected_message()),
     }
 
-    Ok(Portal::new(client, name, statement))
+    Ok(Portal::new(client, name, statement, formats))
 }
@@ -0,0 +1,262 @@
+//! Utilities for bulk loading rows into a table.
+
+use crate::binary_copy::BinaryCopyInWriter;
+use crate::types::BorrowToSql;
+use crate::{Client, Error};
+use futures_util::{pin_mut, Stream, StreamExt};
+use postgres_protocol::escape::escape_identifier;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// The action taken when a loaded row conflicts with an existing row of the table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConflictAction {
+    /// Update all loaded non-key columns of the existing row.
+    Update,
+    /// Update the specified columns of the existing row.
+    UpdateColumns(Vec<String>),
+    /// Leave the existing row unchanged.
+    Nothing,
+    /// Abort the load with a unique violation error.
+    Error,
+}
+
+/// The number of rows affected by a bulk load.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct LoadCounts {
+    inserted: u64,
+    updated: u64,
+    skipped: u64,
+}
+
+impl LoadCounts {
+    /// Returns the number of rows inserted into the table.
+    pub fn inserted(&self) -> u64 {
+        self.inserted
+    }
+
+    /// Returns the number of existing rows updated.
+    pub fn updated(&self) -> u64 {
+        self.updated
+    }
+
+    /// Returns the number of loaded rows which conflicted with an existing row which was left unchanged.
+    pub fn skipped(&self) -> u64 {
+        self.skipped
+    }
+}
+
+/// A builder for bulk loads into a table.
+///
+/// Rows are written via binary `COPY` into a temporary table, which is then merged into the target table with an
+/// `INSERT ... ON CONFLICT` statement. Returned by the [`Transaction::bulk_loader`] method.
+///
+/// [`Transaction::bulk_loader`]: crate::Transaction::bulk_loader
+pub struct BulkLoader<'a> {
+    client: &'a Client,
+    table: String,
+    key_columns: Vec<String>,
+    columns: Option<Vec<String>>,
+    conflict_action: ConflictAction,
+}
+
+impl<'a> BulkLoader<'a> {
+    pub(crate) fn new(client: &'a Client, table: &str, key_columns: &[&str]) -> BulkLoader<'a> {
+        BulkLoader {
+            client,
+            table: table.to_string(),
+            key_columns: key_columns.iter().map(|c| c.to_string()).collect(),
+            columns: None,
+            conflict_action: ConflictAction::Update,
+        }
+    }
+
+    /// Sets the columns to load, in the order their values appear in each row.
+    ///
+    /// Defaults to all columns of the table which can be written to, in table order. This excludes generated columns
+    /// and identity columns declared `GENERATED ALWAYS`.
+    pub fn columns(mut self, columns: &[&str]) -> Self {
+        self.columns = Some(columns.iter().map(|c| c.to_string()).collect());
+        self
+    }
+
+    /// Sets the action taken when a loaded row conflicts with an existing row.
+    ///
+    /// Defaults to `ConflictAction::Update`.
+    pub fn on_conflict(mut self, conflict_action: ConflictAction) -> Self {
+        self.conflict_action = conflict_action;
+        self
+    }
+
+    /// Loads the rows of a stream into the table.
+    ///
+    /// The column types are looked up from the table before any rows are written. Each row must contain one value per
+    /// loaded column. A row which conflicts with another row of the same load causes an error if the conflict action
+    /// updates existing rows.
+    ///
+    /// Returns an error without contacting the server if the conflict action updates existing rows but no key columns
+    /// were specified.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of values in a row does not match the number of columns loaded.
+    pub async fn load<S, R, P>(self, rows: S) -> Result<LoadCounts, Error>
+    where
+        S: Stream<Item = R>,
+        R: IntoIterator<Item = P>,
+        R::IntoIter: ExactSizeIterator,
+        P: BorrowToSql,
+    {
+        // `ON CONFLICT DO UPDATE` requires a conflict target
+        if self.key_columns.is_empty()
+            && matches!(
+                self.conflict_action,
+                ConflictAction::Update | ConflictAction::UpdateColumns(_)
+            )
+        {
+            return Err(Error::config(
+                "key columns are required to update conflicting rows".into(),
+            ));
+        }
+
+        let columns = match &self.columns {
+            Some(columns) => columns
+                .iter()
+                .map(|c| escape_identifier(c))
+                .collect::<Vec<_>>()
+                .join(", "),
+            None => {
+                // generated and `GENERATED ALWAYS` identity columns can't be written to
+                let rows = self
+                    .client
+                    .query(
+                        "SELECT attname FROM pg_catalog.pg_attribute \
+                         WHERE attrelid = $1::TEXT::pg_catalog.regclass AND attnum > 0 \
+                         AND NOT attisdropped AND attgenerated = '' AND attidentity <> 'a' \
+                         ORDER BY attnum",
+                        &[&self.table],
+                    )
+                    .await?;
+                rows.iter()
+                    .map(|row| row.try_get::<_, &str>(0).map(escape_identifier))
+                    .collect::<Result<Vec<_>, _>>()?
+                    .join(", ")
+            }
+        };
+        let statement = self
+            .client
+            .prepare(&format!("SELECT {} FROM {}", columns, self.table))
+            .await?;
+        let names = statement
+            .columns()
+            .iter()
+            .map(|c| c.name())
+            .collect::<Vec<_>>();
+        let types = statement
+            .columns()
+            .iter()
+            .map(|c| c.type_().clone())
+            .collect::<Vec<_>>();
+        let columns = names
+            .iter()
+            .map(|c| escape_identifier(c))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let temp_table = format!(
+            "tokio_postgres_bulk_load_{}",
+            NEXT_ID.fetch_add(1, Ordering::SeqCst)
+        );
+        self.client
+            .batch_execute(&format!(
+                "CREATE TEMPORARY TABLE {} ON COMMIT DROP AS SELECT {} FROM {} WITH NO DATA",
+                temp_table, columns, self.table
+            ))
+            .await?;
+
+        let sink = self
+            .client
+            .copy_in(&format!("COPY {} FROM STDIN BINARY", temp_table))
+            .await?;
+        let writer = BinaryCopyInWriter::new(sink, &types);
+        pin_mut!(writer);
+        pin_mut!(rows);
+        while let Some(row) = rows.next().await {
+            writer.as_mut().write_raw(row).await?;
+        }
+        let loaded = writer.finish().await?;
+
+        let row = self
+            .client
+            .query_one(
+                &*format!(
+                    "WITH merged AS (\
+                        INSERT INTO {table} ({columns}) SELECT {columns} FROM {temp_table} {conflict} \
+                        RETURNING xmax = 0 AS inserted\
+                     ) \
+                     SELECT count(*) FILTER (WHERE inserted), count(*) FILTER (WHERE NOT inserted) \
+                     FROM merged",
+                    table = self.table,
+                    columns = columns,
+                    temp_table = temp_table,
+                    conflict = self.conflict_clause(&names),
+                ),
+                &[],
+            )
+            .await?;
+        let inserted = row.try_get::<_, i64>(0)? as u64;
+        let updated = row.try_get::<_, i64>(1)? as u64;
+
+        self.client
+            .batch_execute(&format!("DROP TABLE {}", temp_table))
+            .await?;
+
+        Ok(LoadCounts {
+            inserted,
+            updated,
+            skipped: loaded - inserted - updated,
+        })
+    }
+
+    fn conflict_clause(&self, columns: &[&str]) -> String {
+        let updates = match &self.conflict_action {
+            ConflictAction::Error => return String::new(),
+            ConflictAction::Nothing => vec![],
+            ConflictAction::Update => columns
+                .iter()
+                .filter(|c| !self.key_columns.iter().any(|k| k == *c))
+                .map(|c| c.to_string())
+                .collect(),
+            ConflictAction::UpdateColumns(columns) => columns.clone(),
+        };
+
+        let mut clause = "ON CONFLICT".to_string();
+        if !self.key_columns.is_empty() {
+            let keys = self
+                .key_columns
+                .iter()
+                .map(|c| escape_identifier(c))
+                .collect::<Vec<_>>()
+                .join(", ");
+            clause.push_str(&format!(" ({})", keys));
+        }
+
+        if updates.is_empty() {
+            clause.push_str(" DO NOTHING");
+        } else {
+            let updates = updates
+                .iter()
+                .map(|c| {
+                    let c = escape_identifier(c);
+                    format!("{} = EXCLUDED.{}", c, c)
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            clause.push_str(&format!(" DO UPDATE SET {}", updates));
+        }
+
+        clause
+    }
+}
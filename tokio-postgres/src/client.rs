@@ -4,28 +4,33 @@ use crate::config::Host;
 use crate::config::SslMode;
 use crate::connection::{Request, RequestMessages};
 use crate::copy_out::CopyOutStream;
+use crate::cursor::{self, CursorStream};
 #[cfg(feature = "runtime")]
 use crate::keepalive::KeepaliveConfig;
+use crate::large_object;
+use crate::listen::{self, Listeners, NotificationStream};
+use crate::named::NamedStatement;
+use crate::prepared_xact::{self, PreparedXact};
 use crate::query::RowStream;
 use crate::simple_query::SimpleQueryStream;
 #[cfg(feature = "runtime")]
 use crate::tls::MakeTlsConnect;
 use crate::tls::TlsConnect;
-use crate::types::{Oid, ToSql, Type};
+use crate::types::{Format, Oid, ToSql, Type};
 #[cfg(feature = "runtime")]
 use crate::Socket;
 use crate::{
-    copy_in, copy_out, prepare, query, simple_query, slice_iter, CancelToken, CopyInSink, Error,
-    Row, SimpleQueryMessage, Statement, ToStatement, Transaction, TransactionBuilder,
+    copy_in, copy_out, function_call, prepare, query, simple_query, slice_iter, CancelToken,
+    Column, CopyInSink, Error, Row, SimpleQueryMessage, Statement, ToStatement, Transaction,
+    TransactionBuilder, TypeRegistry,
 };
-use bytes::{Buf, BytesMut};
+use bytes::{Buf, Bytes, BytesMut};
 use fallible_iterator::FallibleIterator;
 use futures_channel::mpsc;
 use futures_util::{future, pin_mut, ready, StreamExt, TryStreamExt};
 use parking_lot::Mutex;
 use postgres_protocol::message::{backend::Message, frontend};
 use postgres_types::BorrowToSql;
-use std::collections::HashMap;
 use std::fmt;
 use std::sync::Arc;
 use std::task::{Context, Poll};
@@ -59,29 +64,23 @@ impl Responses {
     }
 }
 
-/// A cache of type info and prepared statements for fetching type info
-/// (corresponding to the queries in the [prepare](prepare) module).
+/// A cache of the prepared statement for fetching type info (corresponding to the queries in the
+/// [prepare](prepare) module).
 #[derive(Default)]
 struct CachedTypeInfo {
-    /// A statement for basic information for a type from its
-    /// OID. Corresponds to [TYPEINFO_QUERY](prepare::TYPEINFO_QUERY) (or its
-    /// fallback).
+    /// A statement for information about a set of types, and the types they depend on, from their
+    /// OIDs. Corresponds to [TYPEINFO_QUERY](prepare::TYPEINFO_QUERY) (or its fallback).
     typeinfo: Option<Statement>,
-    /// A statement for getting information for a composite type from its OID.
-    /// Corresponds to [TYPEINFO_QUERY](prepare::TYPEINFO_COMPOSITE_QUERY).
-    typeinfo_composite: Option<Statement>,
-    /// A statement for getting information for a composite type from its OID.
-    /// Corresponds to [TYPEINFO_QUERY](prepare::TYPEINFO_COMPOSITE_QUERY) (or
-    /// its fallback).
-    typeinfo_enum: Option<Statement>,
-
-    /// Cache of types already looked up.
-    types: HashMap<Oid, Type>,
 }
 
 pub struct InnerClient {
     sender: mpsc::UnboundedSender<Request>,
     cached_typeinfo: Mutex<CachedTypeInfo>,
+    /// Cache of types already looked up, possibly shared with other clients.
+    types: TypeRegistry,
+    /// The statements used to operate on large objects, prepared the first time one is opened.
+    large_object_statements: Mutex<Option<Arc<large_object::Statements>>>,
+    listeners: Arc<Listeners>,
 
     /// A buffer to use when writing out postgres commands.
     buffer: Mutex<BytesMut>,
@@ -109,32 +108,32 @@ impl InnerClient {
         self.cached_typeinfo.lock().typeinfo = Some(statement.clone());
     }
 
-    pub fn typeinfo_composite(&self) -> Option<Statement> {
-        self.cached_typeinfo.lock().typeinfo_composite.clone()
+    pub fn large_object_statements(&self) -> Option<Arc<large_object::Statements>> {
+        self.large_object_statements.lock().clone()
     }
 
-    pub fn set_typeinfo_composite(&self, statement: &Statement) {
-        self.cached_typeinfo.lock().typeinfo_composite = Some(statement.clone());
+    pub fn set_large_object_statements(&self, statements: &Arc<large_object::Statements>) {
+        *self.large_object_statements.lock() = Some(statements.clone());
     }
 
-    pub fn typeinfo_enum(&self) -> Option<Statement> {
-        self.cached_typeinfo.lock().typeinfo_enum.clone()
-    }
-
-    pub fn set_typeinfo_enum(&self, statement: &Statement) {
-        self.cached_typeinfo.lock().typeinfo_enum = Some(statement.clone());
+    pub fn types(&self) -> &TypeRegistry {
+        &self.types
     }
 
     pub fn type_(&self, oid: Oid) -> Option<Type> {
-        self.cached_typeinfo.lock().types.get(&oid).cloned()
+        self.types.get(oid)
     }
 
-    pub fn set_type(&self, oid: Oid, type_: &Type) {
-        self.cached_typeinfo.lock().types.insert(oid, type_.clone());
+    pub fn set_type(&self, type_: &Type) {
+        self.types.insert(type_);
     }
 
     pub fn clear_type_cache(&self) {
-        self.cached_typeinfo.lock().types.clear();
+        self.types.clear();
+    }
+
+    pub fn listeners(&self) -> &Arc<Listeners> {
+        &self.listeners
     }
 
     /// Call the given function with a buffer to be used when writing out
@@ -179,11 +178,15 @@ impl Client {
         ssl_mode: SslMode,
         process_id: i32,
         secret_key: i32,
+        types: TypeRegistry,
     ) -> Client {
         Client {
             inner: Arc::new(InnerClient {
                 sender,
                 cached_typeinfo: Default::default(),
+                large_object_statements: Mutex::new(None),
+                types,
+                listeners: Default::default(),
                 buffer: Default::default(),
             }),
             #[cfg(feature = "runtime")]
@@ -357,6 +360,57 @@ impl Client {
         query::query(&self.inner, statement, params).await
     }
 
+    /// Like `query_raw`, but allows the format of each result column to be selected.
+    ///
+    /// The `formats` closure is called with each column of the statement. `Row::get` decodes columns requested in
+    /// `Format::Text` with `FromSql::from_text`, and `Row::get_text` decodes them with `FromSqlText`, which allows
+    /// values of types without a binary `FromSql` implementation, such as those defined by extensions, to be read
+    /// without a cast in the query.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn async_main(client: &tokio_postgres::Client) -> Result<(), tokio_postgres::Error> {
+    /// use tokio_postgres::types::Format;
+    /// use futures_util::{pin_mut, TryStreamExt};
+    ///
+    /// let params: Vec<String> = vec![];
+    /// let mut it = client.query_raw_with_formats(
+    ///     "SELECT id, location FROM places",
+    ///     params,
+    ///     |column| match column.name() {
+    ///         "location" => Format::Text,
+    ///         _ => Format::Binary,
+    ///     },
+    /// ).await?;
+    ///
+    /// pin_mut!(it);
+    /// while let Some(row) = it.try_next().await? {
+    ///     let id: i32 = row.get("id");
+    ///     let location: &str = row.get_text("location");
+    ///     println!("{}: {}", id, location);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn query_raw_with_formats<T, P, I, F>(
+        &self,
+        statement: &T,
+        params: I,
+        formats: F,
+    ) -> Result<RowStream, Error>
+    where
+        T: ?Sized + ToStatement,
+        P: BorrowToSql,
+        I: IntoIterator<Item = P>,
+        I::IntoIter: ExactSizeIterator,
+        F: FnMut(&Column) -> Format,
+    {
+        let statement = statement.__convert().into_statement(self).await?;
+        let formats = query::result_formats(&statement, formats);
+        query::query_with_formats(&self.inner, statement, params, Some(formats)).await
+    }
+
     /// Executes a statement, returning the number of rows modified.
     ///
     /// A statement may contain parameters, specified by `$n`, where `n` is the index of the parameter of the list
@@ -399,6 +453,71 @@ impl Client {
         query::execute(self.inner(), statement, params).await
     }
 
+    /// Executes a statement with named parameters, returning a vector of the resulting rows.
+    ///
+    /// Parameters are specified by `:name`, and their values are matched to the placeholders by name. Values which are
+    /// not referenced by the query are ignored. See [`NamedStatement`] for details of the placeholder syntax.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn async_main(client: &tokio_postgres::Client) -> Result<(), tokio_postgres::Error> {
+    /// let id = 1i32;
+    /// let rows = client
+    ///     .query_named("SELECT name FROM users WHERE id = :id", &[("id", &id)])
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn query_named(
+        &self,
+        query: &str,
+        params: &[(&str, &(dyn ToSql + Sync))],
+    ) -> Result<Vec<Row>, Error> {
+        let statement = NamedStatement::new(query);
+        let params = statement.bind(params)?;
+        self.query(statement.query(), &params).await
+    }
+
+    /// Like [`query_one`], but with named parameters.
+    ///
+    /// [`query_one`]: #method.query_one
+    pub async fn query_one_named(
+        &self,
+        query: &str,
+        params: &[(&str, &(dyn ToSql + Sync))],
+    ) -> Result<Row, Error> {
+        let statement = NamedStatement::new(query);
+        let params = statement.bind(params)?;
+        self.query_one(statement.query(), &params).await
+    }
+
+    /// Like [`query_opt`], but with named parameters.
+    ///
+    /// [`query_opt`]: #method.query_opt
+    pub async fn query_opt_named(
+        &self,
+        query: &str,
+        params: &[(&str, &(dyn ToSql + Sync))],
+    ) -> Result<Option<Row>, Error> {
+        let statement = NamedStatement::new(query);
+        let params = statement.bind(params)?;
+        self.query_opt(statement.query(), &params).await
+    }
+
+    /// Like [`execute`], but with named parameters.
+    ///
+    /// [`execute`]: #method.execute
+    pub async fn execute_named(
+        &self,
+        query: &str,
+        params: &[(&str, &(dyn ToSql + Sync))],
+    ) -> Result<u64, Error> {
+        let statement = NamedStatement::new(query);
+        let params = statement.bind(params)?;
+        self.execute(statement.query(), &params).await
+    }
+
     /// Executes a `COPY FROM STDIN` statement, returning a sink used to write the copy data.
     ///
     /// PostgreSQL does not support parameters in `COPY` statements, so this method does not take any. The copy *must*
@@ -458,6 +577,118 @@ impl Client {
         simple_query::batch_execute(self.inner(), query).await
     }
 
+    /// Commits a transaction prepared for two-phase commit with `Transaction::prepare_transaction`.
+    ///
+    /// The transaction may have been prepared by any connection to the same database. This cannot be called while a
+    /// transaction is open.
+    pub async fn commit_prepared(&self, gid: &str) -> Result<(), Error> {
+        prepared_xact::commit(self, gid).await
+    }
+
+    /// Rolls back a transaction prepared for two-phase commit with `Transaction::prepare_transaction`.
+    ///
+    /// The transaction may have been prepared by any connection to the same database. This cannot be called while a
+    /// transaction is open.
+    pub async fn rollback_prepared(&self, gid: &str) -> Result<(), Error> {
+        prepared_xact::rollback(self, gid).await
+    }
+
+    /// Returns the transactions currently prepared for two-phase commit, in the order they were prepared.
+    ///
+    /// This includes transactions prepared in all databases of the server, which is useful when recovering from a
+    /// failure of a transaction coordinator.
+    pub async fn list_prepared(&self) -> Result<Vec<PreparedXact>, Error> {
+        prepared_xact::list(self).await
+    }
+
+    /// Declares a `WITH HOLD` cursor for a query, returning a stream which fetches its rows in batches of `fetch_size`.
+    ///
+    /// Unlike `Transaction::cursor`, the cursor is not tied to a transaction, and can be used on a client with no
+    /// transaction open. The server materializes the entire result of the query when the declaring transaction commits,
+    /// so this trades server-side resources for incremental transfer of the rows. The cursor is closed when the stream
+    /// is dropped. If `fetch_size` is 0 or negative, all rows will be fetched at once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of parameters provided does not match the number expected.
+    pub async fn cursor_with_hold(
+        &self,
+        query: &str,
+        params: &[&(dyn ToSql + Sync)],
+        fetch_size: i32,
+    ) -> Result<CursorStream<'_>, Error> {
+        let name = cursor::next_name();
+        self.execute(
+            &*format!("DECLARE {} NO SCROLL CURSOR WITH HOLD FOR {}", name, query),
+            params,
+        )
+        .await?;
+
+        let fetch = if fetch_size > 0 {
+            format!("FETCH FORWARD {} FROM {}", fetch_size, name)
+        } else {
+            format!("FETCH ALL FROM {}", name)
+        };
+        let fetch = match self.prepare(&fetch).await {
+            Ok(fetch) => fetch,
+            Err(e) => {
+                let _ = self.batch_execute(&format!("CLOSE {}", name)).await;
+                return Err(e);
+            }
+        };
+
+        Ok(CursorStream::declared(
+            self.inner(),
+            name,
+            fetch,
+            fetch_size,
+        ))
+    }
+
+    /// Calls a server function by OID using the fast-path function call protocol, returning its raw result.
+    ///
+    /// Each argument is paired with the type of the corresponding function parameter, which is used to serialize it.
+    /// This avoids the parse and bind round trips of a prepared statement, but the server performs no type checking,
+    /// so the types must match the function's signature exactly. The result is returned in the binary format, or
+    /// `None` if the function returned `NULL`.
+    pub async fn function_call(
+        &self,
+        function: Oid,
+        args: &[(&(dyn ToSql + Sync), Type)],
+    ) -> Result<Option<Bytes>, Error> {
+        function_call::function_call(self.inner(), function, args).await
+    }
+
+    /// Subscribes to notifications sent on a channel, returning a stream of them.
+    ///
+    /// This issues a `LISTEN` command for the channel. Notifications are routed to the stream by the `Connection`, so
+    /// this works when the connection has simply been spawned off rather than driven by `Connection::poll_message`.
+    /// The channel name is quoted, so it is matched exactly rather than folded to lowercase.
+    ///
+    /// Multiple streams may listen on the same channel, each receiving every notification. Once the last of them is
+    /// dropped, an `UNLISTEN` command is issued for the channel. Any other `LISTEN` or `UNLISTEN` commands issued
+    /// manually for the channel will interfere with this.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn async_main(client: &tokio_postgres::Client) -> Result<(), tokio_postgres::Error> {
+    /// use futures_util::StreamExt;
+    ///
+    /// let mut notifications = client.listen("jobs").await?;
+    /// while let Some(notification) = notifications.next().await {
+    ///     println!("new job: {}", notification.payload());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn listen(&self, channel: &str) -> Result<NotificationStream, Error> {
+        // Subscribe before issuing the command so notifications sent immediately after it aren't missed.
+        let stream = NotificationStream::new(&self.inner, channel);
+        self.batch_execute(&listen::listen_query(channel)).await?;
+        Ok(stream)
+    }
+
     /// Begins a new database transaction.
     ///
     /// The transaction will roll back by default - use the `commit` method to commit it.
@@ -552,6 +783,8 @@ impl Client {
     /// When user-defined types are used in a query, the client loads their definitions from the database and caches
     /// them for the lifetime of the client. If those definitions are changed in the database, this method can be used
     /// to flush the local cache and allow the new, updated definitions to be loaded.
+    ///
+    /// If the client was configured with a shared [`TypeRegistry`], the cache of every client sharing it is cleared.
     pub fn clear_type_cache(&self) {
         self.inner().clear_type_cache();
     }
@@ -3,13 +3,14 @@
 #[cfg(feature = "runtime")]
 use crate::connect::connect;
 use crate::connect_raw::connect_raw;
+use crate::error::DbError;
 use crate::keepalive::KeepaliveConfig;
 #[cfg(feature = "runtime")]
 use crate::tls::MakeTlsConnect;
 use crate::tls::TlsConnect;
 #[cfg(feature = "runtime")]
 use crate::Socket;
-use crate::{Client, Connection, Error};
+use crate::{Client, Connection, Error, TypeRegistry};
 use std::borrow::Cow;
 #[cfg(unix)]
 use std::ffi::OsStr;
@@ -19,6 +20,7 @@ use std::os::unix::ffi::OsStrExt;
 use std::path::{Path, PathBuf};
 use std::str;
 use std::str::FromStr;
+use std::sync::Arc;
 use std::time::Duration;
 use std::{error, fmt, iter, mem};
 use tokio::io::{AsyncRead, AsyncWrite};
@@ -86,33 +88,33 @@ pub enum Host {
 /// * `options` - Command line options used to configure the server.
 /// * `application_name` - Sets the `application_name` parameter on the server.
 /// * `sslmode` - Controls usage of TLS. If set to `disable`, TLS will not be used. If set to `prefer`, TLS will be used
-///     if available, but not used otherwise. If set to `require`, TLS will be forced to be used. Defaults to `prefer`.
+///   if available, but not used otherwise. If set to `require`, TLS will be forced to be used. Defaults to `prefer`.
 /// * `host` - The host to connect to. On Unix platforms, if the host starts with a `/` character it is treated as the
-///     path to the directory containing Unix domain sockets. Otherwise, it is treated as a hostname. Multiple hosts
-///     can be specified, separated by commas. Each host will be tried in turn when connecting. Required if connecting
-///     with the `connect` method.
+///   path to the directory containing Unix domain sockets. Otherwise, it is treated as a hostname. Multiple hosts
+///   can be specified, separated by commas. Each host will be tried in turn when connecting. Required if connecting
+///   with the `connect` method.
 /// * `port` - The port to connect to. Multiple ports can be specified, separated by commas. The number of ports must be
-///     either 1, in which case it will be used for all hosts, or the same as the number of hosts. Defaults to 5432 if
-///     omitted or the empty string.
+///   either 1, in which case it will be used for all hosts, or the same as the number of hosts. Defaults to 5432 if
+///   omitted or the empty string.
 /// * `connect_timeout` - The time limit in seconds applied to each socket-level connection attempt. Note that hostnames
-///     can resolve to multiple IP addresses, and this limit is applied to each address. Defaults to no timeout.
+///   can resolve to multiple IP addresses, and this limit is applied to each address. Defaults to no timeout.
 /// * `tcp_user_timeout` - The time limit that transmitted data may remain unacknowledged before a connection is forcibly closed.
-///     This is ignored for Unix domain socket connections. It is only supported on systems where TCP_USER_TIMEOUT is available
-///     and will default to the system default if omitted or set to 0; on other systems, it has no effect.
+///   This is ignored for Unix domain socket connections. It is only supported on systems where TCP_USER_TIMEOUT is available
+///   and will default to the system default if omitted or set to 0; on other systems, it has no effect.
 /// * `keepalives` - Controls the use of TCP keepalive. A value of 0 disables keepalive and nonzero integers enable it.
-///     This option is ignored when connecting with Unix sockets. Defaults to on.
+///   This option is ignored when connecting with Unix sockets. Defaults to on.
 /// * `keepalives_idle` - The number of seconds of inactivity after which a keepalive message is sent to the server.
-///     This option is ignored when connecting with Unix sockets. Defaults to 2 hours.
+///   This option is ignored when connecting with Unix sockets. Defaults to 2 hours.
 /// * `keepalives_interval` - The time interval between TCP keepalive probes.
-///     This option is ignored when connecting with Unix sockets.
+///   This option is ignored when connecting with Unix sockets.
 /// * `keepalives_retries` - The maximum number of TCP keepalive probes that will be sent before dropping a connection.
-///     This option is ignored when connecting with Unix sockets.
+///   This option is ignored when connecting with Unix sockets.
 /// * `target_session_attrs` - Specifies requirements of the session. If set to `read-write`, the client will check that
-///     the `transaction_read_write` session parameter is set to `on`. This can be used to connect to the primary server
-///     in a database cluster as opposed to the secondary read-only mirrors. Defaults to `all`.
+///   the `transaction_read_write` session parameter is set to `on`. This can be used to connect to the primary server
+///   in a database cluster as opposed to the secondary read-only mirrors. Defaults to `all`.
 /// * `channel_binding` - Controls usage of channel binding in the authentication process. If set to `disable`, channel
-///     binding will not be used. If set to `prefer`, channel binding will be used if available, but not used otherwise.
-///     If set to `require`, the authentication process will fail if channel binding is not used. Defaults to `prefer`.
+///   binding will not be used. If set to `prefer`, channel binding will be used if available, but not used otherwise.
+///   If set to `require`, the authentication process will fail if channel binding is not used. Defaults to `prefer`.
 ///
 /// ## Examples
 ///
@@ -168,8 +170,22 @@ pub struct Config {
     pub(crate) keepalive_config: KeepaliveConfig,
     pub(crate) target_session_attrs: TargetSessionAttrs,
     pub(crate) channel_binding: ChannelBinding,
+    pub(crate) notice_handler: Option<NoticeHandler>,
+    pub(crate) type_registry: Option<TypeRegistry>,
 }
 
+/// A callback invoked with the notices received by a connection.
+#[derive(Clone)]
+pub(crate) struct NoticeHandler(pub(crate) Arc<dyn Fn(DbError) + Send + Sync>);
+
+impl PartialEq for NoticeHandler {
+    fn eq(&self, other: &NoticeHandler) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for NoticeHandler {}
+
 impl Default for Config {
     fn default() -> Config {
         Config::new()
@@ -199,6 +215,8 @@ impl Config {
             keepalive_config,
             target_session_attrs: TargetSessionAttrs::Any,
             channel_binding: ChannelBinding::Prefer,
+            notice_handler: None,
+            type_registry: None,
         }
     }
 
@@ -445,6 +463,44 @@ impl Config {
         self.channel_binding
     }
 
+    /// Sets a handler for notices received by the connection.
+    ///
+    /// The handler is invoked with the contents of every [`AsyncMessage::Notice`] received once the connection has
+    /// been established, including notices sent during the connection process itself. Notices use the same structure
+    /// as errors, but they are not "errors" per-se. This is the way to capture the output of `RAISE NOTICE` while the
+    /// [`Connection`] is simply spawned off to run in the background.
+    ///
+    /// When a handler is set, notices are passed to it rather than being returned by [`Connection::poll_message`].
+    /// By default, they are logged at the `info` level by the `Connection`'s `Future` implementation.
+    ///
+    /// [`AsyncMessage::Notice`]: crate::AsyncMessage::Notice
+    pub fn notice_handler<F>(&mut self, f: F) -> &mut Config
+    where
+        F: Fn(DbError) + Send + Sync + 'static,
+    {
+        self.notice_handler = Some(NoticeHandler(Arc::new(f)));
+        self
+    }
+
+    /// Sets the registry used to cache the definitions of custom types.
+    ///
+    /// Every connection created from this configuration shares the registry, so a type loaded by one of them doesn't
+    /// need to be loaded again by the others. By default, each connection has its own cache.
+    ///
+    /// Type definitions are keyed by OID, and OIDs are only meaningful within a single database. The registry must
+    /// therefore only be shared between connections to the same database. In particular, it should not be set on a
+    /// configuration with multiple hosts unless they are replicas of the same cluster.
+    pub fn type_registry(&mut self, type_registry: TypeRegistry) -> &mut Config {
+        self.type_registry = Some(type_registry);
+        self
+    }
+
+    /// Gets the registry used to cache the definitions of custom types, if one has been configured with the
+    /// `type_registry` method.
+    pub fn get_type_registry(&self) -> Option<&TypeRegistry> {
+        self.type_registry.as_ref()
+    }
+
     fn param(&mut self, key: &str, value: &str) -> Result<(), Error> {
         match key {
             "user" => {
@@ -631,6 +687,11 @@ impl fmt::Debug for Config {
             .field("keepalives_retries", &self.keepalive_config.retries)
             .field("target_session_attrs", &self.target_session_attrs)
             .field("channel_binding", &self.channel_binding)
+            .field(
+                "notice_handler",
+                &self.notice_handler.as_ref().map(|_| Redaction {}),
+            )
+            .field("type_registry", &self.type_registry)
             .finish()
     }
 }
@@ -1,6 +1,8 @@
 use crate::codec::{BackendMessage, BackendMessages, FrontendMessage, PostgresCodec};
+use crate::config::NoticeHandler;
 use crate::copy_in::CopyInReceiver;
 use crate::error::DbError;
+use crate::listen::Listeners;
 use crate::maybe_tls_stream::MaybeTlsStream;
 use crate::{AsyncMessage, Error, Notification};
 use bytes::BytesMut;
@@ -13,6 +15,7 @@ use postgres_protocol::message::frontend;
 use std::collections::{HashMap, VecDeque};
 use std::future::Future;
 use std::pin::Pin;
+use std::sync::Arc;
 use std::task::{Context, Poll};
 use tokio::io::{AsyncRead, AsyncWrite};
 use tokio_util::codec::Framed;
@@ -54,6 +57,8 @@ pub struct Connection<S, T> {
     pending_responses: VecDeque<BackendMessage>,
     responses: VecDeque<Response>,
     state: State,
+    listeners: Arc<Listeners>,
+    notice_handler: Option<NoticeHandler>,
 }
 
 impl<S, T> Connection<S, T>
@@ -66,6 +71,8 @@ where
         pending_responses: VecDeque<BackendMessage>,
         parameters: HashMap<String, String>,
         receiver: mpsc::UnboundedReceiver<Request>,
+        listeners: Arc<Listeners>,
+        notice_handler: Option<NoticeHandler>,
     ) -> Connection<S, T> {
         Connection {
             stream,
@@ -75,6 +82,8 @@ where
             pending_responses,
             responses: VecDeque::new(),
             state: State::Active,
+            listeners,
+            notice_handler,
         }
     }
 
@@ -111,7 +120,13 @@ where
             let (mut messages, request_complete) = match message {
                 BackendMessage::Async(Message::NoticeResponse(body)) => {
                     let error = DbError::parse(&mut body.fields()).map_err(Error::parse)?;
-                    return Ok(Some(AsyncMessage::Notice(error)));
+                    match &self.notice_handler {
+                        Some(handler) => {
+                            (handler.0)(error);
+                            continue;
+                        }
+                        None => return Ok(Some(AsyncMessage::Notice(error))),
+                    }
                 }
                 BackendMessage::Async(Message::NotificationResponse(body)) => {
                     let notification = Notification {
@@ -119,6 +134,7 @@ where
                         channel: body.channel().map_err(Error::parse)?.to_string(),
                         payload: body.message().map_err(Error::parse)?.to_string(),
                     };
+                    self.listeners.dispatch(&notification);
                     return Ok(Some(AsyncMessage::Notification(notification)));
                 }
                 BackendMessage::Async(Message::ParameterStatus(body)) => {
@@ -302,6 +318,7 @@ where
     ///
     /// The server can send notices as well as notifications asynchronously to the client. Applications that wish to
     /// examine those messages should use this method to drive the connection rather than its `Future` implementation.
+    /// Notices are not returned if a handler has been registered for them with `Config::notice_handler`.
     ///
     /// Return values of `None` or `Some(Err(_))` are "terminal"; callers should not invoke this method again after
     /// receiving one of those values.
@@ -325,6 +342,12 @@ where
     }
 }
 
+impl<S, T> Drop for Connection<S, T> {
+    fn drop(&mut self) {
+        self.listeners.close();
+    }
+}
+
 impl<S, T> Future for Connection<S, T>
 where
     S: AsyncRead + AsyncWrite + Unpin,
@@ -79,6 +79,8 @@ pin_project! {
         responses: Responses,
         buf: BytesMut,
         state: SinkState,
+        bytes_sent: u64,
+        progress: Option<Box<dyn FnMut(u64) + Send + Sync>>,
         #[pin]
         _p: PhantomPinned,
         _p2: PhantomData<T>,
@@ -89,6 +91,23 @@ impl<T> CopyInSink<T>
 where
     T: Buf + 'static + Send,
 {
+    /// Returns the number of bytes of copy data sent to the connection so far.
+    ///
+    /// Data is buffered internally, so this may lag behind the amount of data written to the sink until it is
+    /// flushed.
+    pub fn bytes_sent(&self) -> u64 {
+        self.bytes_sent
+    }
+
+    /// Sets a callback which is invoked with the total number of bytes sent each time copy data is sent to the
+    /// connection.
+    pub fn set_progress_callback<F>(self: Pin<&mut Self>, callback: F)
+    where
+        F: FnMut(u64) + Send + Sync + 'static,
+    {
+        *self.project().progress = Some(Box::new(callback));
+    }
+
     /// A poll-based version of `finish`.
     pub fn poll_finish(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<u64, Error>> {
         loop {
@@ -161,10 +180,13 @@ where
             }
         };
 
+        let len = data.remaining();
         let data = CopyData::new(data).map_err(Error::encode)?;
         this.sender
             .start_send(CopyInMessage::Message(FrontendMessage::CopyData(data)))
-            .map_err(|_| Error::closed())
+            .map_err(|_| Error::closed())?;
+        report_progress(this.bytes_sent, this.progress, len);
+        Ok(())
     }
 
     fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
@@ -173,11 +195,13 @@ where
         if !this.buf.is_empty() {
             ready!(this.sender.as_mut().poll_ready(cx)).map_err(|_| Error::closed())?;
             let data: Box<dyn Buf + Send> = Box::new(this.buf.split().freeze());
+            let len = data.remaining();
             let data = CopyData::new(data).map_err(Error::encode)?;
             this.sender
                 .as_mut()
                 .start_send(CopyInMessage::Message(FrontendMessage::CopyData(data)))
                 .map_err(|_| Error::closed())?;
+            report_progress(this.bytes_sent, this.progress, len);
         }
 
         this.sender.poll_flush(cx).map_err(|_| Error::closed())
@@ -188,6 +212,17 @@ where
     }
 }
 
+fn report_progress(
+    bytes_sent: &mut u64,
+    progress: &mut Option<Box<dyn FnMut(u64) + Send + Sync>>,
+    len: usize,
+) {
+    *bytes_sent += len as u64;
+    if let Some(progress) = progress {
+        progress(*bytes_sent);
+    }
+}
+
 pub async fn copy_in<T>(client: &InnerClient, statement: Statement) -> Result<CopyInSink<T>, Error>
 where
     T: Buf + 'static + Send,
@@ -220,6 +255,8 @@ where
         responses,
         buf: BytesMut::new(),
         state: SinkState::Active,
+        bytes_sent: 0,
+        progress: None,
         _p: PhantomPinned,
         _p2: PhantomData,
     })
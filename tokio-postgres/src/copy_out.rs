@@ -18,6 +18,8 @@ pub async fn copy_out(client: &InnerClient, statement: Statement) -> Result<Copy
     let responses = start(client, buf).await?;
     Ok(CopyOutStream {
         responses,
+        bytes_received: 0,
+        rows_received: 0,
         _p: PhantomPinned,
     })
 }
@@ -42,11 +44,29 @@ pin_project! {
     /// A stream of `COPY ... TO STDOUT` query data.
     pub struct CopyOutStream {
         responses: Responses,
+        bytes_received: u64,
+        rows_received: u64,
         #[pin]
         _p: PhantomPinned,
     }
 }
 
+impl CopyOutStream {
+    /// Returns the number of bytes of copy data received so far.
+    pub fn bytes_received(&self) -> u64 {
+        self.bytes_received
+    }
+
+    /// Returns the number of rows received so far.
+    ///
+    /// The server sends each row in its own message, so this is a count of the chunks of data returned by the stream.
+    /// In the binary format, the file header is included in the first chunk and the file trailer is sent as a chunk
+    /// of its own.
+    pub fn rows_received(&self) -> u64 {
+        self.rows_received
+    }
+}
+
 impl Stream for CopyOutStream {
     type Item = Result<Bytes, Error>;
 
@@ -54,7 +74,12 @@ impl Stream for CopyOutStream {
         let this = self.project();
 
         match ready!(this.responses.poll_next(cx)?) {
-            Message::CopyData(body) => Poll::Ready(Some(Ok(body.into_bytes()))),
+            Message::CopyData(body) => {
+                let data = body.into_bytes();
+                *this.bytes_received += data.len() as u64;
+                *this.rows_received += 1;
+                Poll::Ready(Some(Ok(data)))
+            }
             Message::CopyDone => Poll::Ready(None),
             _ => Poll::Ready(Some(Err(Error::unexpected_message()))),
         }
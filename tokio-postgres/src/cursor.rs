@@ -0,0 +1,156 @@
+use crate::client::InnerClient;
+use crate::codec::FrontendMessage;
+use crate::connection::RequestMessages;
+use crate::{query, slice_iter, Error, Portal, Row, RowStream, Statement};
+use futures_util::{ready, Future, Stream};
+use postgres_protocol::message::frontend;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Weak};
+use std::task::{Context, Poll};
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+type BoxFuture<T> = Pin<Box<dyn Future<Output = Result<T, Error>> + Send>>;
+
+pub(crate) fn next_name() -> String {
+    format!(
+        "tokio_postgres_cursor_{}",
+        NEXT_ID.fetch_add(1, Ordering::SeqCst)
+    )
+}
+
+/// A cursor declared with `DECLARE`, which is closed when dropped.
+struct Declared {
+    client: Weak<InnerClient>,
+    name: String,
+    fetch: Statement,
+}
+
+impl Drop for Declared {
+    fn drop(&mut self) {
+        if let Some(client) = self.client.upgrade() {
+            let buf = client.with_buf(|buf| {
+                frontend::query(&format!("CLOSE {}", self.name), buf).unwrap();
+                buf.split().freeze()
+            });
+            let _ = client.send(RequestMessages::Single(FrontendMessage::Raw(buf)));
+        }
+    }
+}
+
+enum Source {
+    Portal(Portal),
+    Declared(Declared),
+}
+
+enum State {
+    Idle,
+    Fetching(BoxFuture<RowStream>),
+    Reading(Pin<Box<RowStream>>, i32),
+    Done,
+}
+
+/// A stream of rows fetched incrementally from a server-side cursor.
+///
+/// Rows are fetched in batches of the configured size as the stream is polled.
+pub struct CursorStream<'a> {
+    client: Arc<InnerClient>,
+    source: Source,
+    fetch_size: i32,
+    state: State,
+    _p: PhantomData<&'a ()>,
+}
+
+impl<'a> CursorStream<'a> {
+    pub(crate) fn portal(
+        client: &Arc<InnerClient>,
+        portal: Portal,
+        fetch_size: i32,
+    ) -> CursorStream<'a> {
+        CursorStream {
+            client: client.clone(),
+            source: Source::Portal(portal),
+            fetch_size,
+            state: State::Idle,
+            _p: PhantomData,
+        }
+    }
+
+    pub(crate) fn declared(
+        client: &Arc<InnerClient>,
+        name: String,
+        fetch: Statement,
+        fetch_size: i32,
+    ) -> CursorStream<'a> {
+        CursorStream {
+            client: client.clone(),
+            source: Source::Declared(Declared {
+                client: Arc::downgrade(client),
+                name,
+                fetch,
+            }),
+            fetch_size,
+            state: State::Idle,
+            _p: PhantomData,
+        }
+    }
+
+    fn fetch(&self) -> BoxFuture<RowStream> {
+        let client = self.client.clone();
+        match &self.source {
+            Source::Portal(portal) => {
+                let portal = portal.clone();
+                let fetch_size = self.fetch_size;
+                Box::pin(async move { query::query_portal(&client, &portal, fetch_size).await })
+            }
+            Source::Declared(declared) => {
+                let statement = declared.fetch.clone();
+                Box::pin(async move { query::query(&client, statement, slice_iter(&[])).await })
+            }
+        }
+    }
+}
+
+impl Stream for CursorStream<'_> {
+    type Item = Result<Row, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            match &mut this.state {
+                State::Idle => this.state = State::Fetching(this.fetch()),
+                State::Fetching(future) => match ready!(future.as_mut().poll(cx)) {
+                    Ok(rows) => this.state = State::Reading(Box::pin(rows), 0),
+                    Err(e) => {
+                        this.state = State::Done;
+                        return Poll::Ready(Some(Err(e)));
+                    }
+                },
+                State::Reading(rows, count) => match ready!(rows.as_mut().poll_next(cx)) {
+                    Some(Ok(row)) => {
+                        *count += 1;
+                        return Poll::Ready(Some(Ok(row)));
+                    }
+                    Some(Err(e)) => {
+                        this.state = State::Done;
+                        return Poll::Ready(Some(Err(e)));
+                    }
+                    None => {
+                        let exhausted = match this.source {
+                            Source::Portal(_) => rows.rows_affected().is_some(),
+                            Source::Declared(_) => *count < this.fetch_size,
+                        };
+                        this.state = if this.fetch_size <= 0 || exhausted {
+                            State::Done
+                        } else {
+                            State::Idle
+                        };
+                    }
+                },
+                State::Done => return Poll::Ready(None),
+            }
+        }
+    }
+}
@@ -0,0 +1,698 @@
+//! Serde deserialization of rows.
+
+use crate::binary_copy::BinaryCopyOutRow;
+use crate::types::{Field, Format, FromSql, Kind, Type};
+use crate::{text_encode, Error, Row};
+use fallible_iterator::FallibleIterator;
+use postgres_protocol::types;
+use serde_1::de::{
+    self, DeserializeSeed, Deserializer, IntoDeserializer, MapAccess, SeqAccess, Visitor,
+};
+use serde_1::forward_to_deserialize_any;
+use std::error;
+use std::fmt;
+use std::io::Read;
+use std::vec;
+
+impl de::Error for Error {
+    fn custom<T>(msg: T) -> Error
+    where
+        T: fmt::Display,
+    {
+        Error::deserialize(msg.to_string().into())
+    }
+}
+
+/// The raw column data of a row.
+trait RowData {
+    fn len(&self) -> usize;
+
+    /// Returns the name of a column, or `None` if the row's columns are unnamed.
+    fn name(&self, idx: usize) -> Option<&str>;
+
+    fn type_(&self, idx: usize) -> &Type;
+
+    /// Returns the binary-encoded value of a column, or an error if it was returned in another format.
+    fn raw(&self, idx: usize) -> Result<Option<&[u8]>, ValueError>;
+}
+
+impl RowData for Row {
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn name(&self, idx: usize) -> Option<&str> {
+        Some(self.columns()[idx].name())
+    }
+
+    fn type_(&self, idx: usize) -> &Type {
+        self.columns()[idx].type_()
+    }
+
+    fn raw(&self, idx: usize) -> Result<Option<&[u8]>, ValueError> {
+        match self.format(idx) {
+            Some(Format::Text) => Err(de::Error::custom("value was returned in text format")),
+            _ => Ok(self.col_buffer(idx)),
+        }
+    }
+}
+
+impl RowData for BinaryCopyOutRow {
+    fn len(&self) -> usize {
+        self.types().len()
+    }
+
+    fn name(&self, _: usize) -> Option<&str> {
+        None
+    }
+
+    fn type_(&self, idx: usize) -> &Type {
+        &self.types()[idx]
+    }
+
+    fn raw(&self, idx: usize) -> Result<Option<&[u8]>, ValueError> {
+        Ok(self.col_buffer(idx))
+    }
+}
+
+fn deserialize_row_any<'de, R, V>(row: &'de R, visitor: V) -> Result<V::Value, Error>
+where
+    R: RowData,
+    V: Visitor<'de>,
+{
+    if row.len() > 0 && row.name(0).is_some() {
+        deserialize_row_map(row, visitor)
+    } else {
+        deserialize_row_seq(row, visitor)
+    }
+}
+
+fn deserialize_row_map<'de, R, V>(row: &'de R, visitor: V) -> Result<V::Value, Error>
+where
+    R: RowData,
+    V: Visitor<'de>,
+{
+    if row.len() > 0 && row.name(0).is_none() {
+        return Err(de::Error::custom(
+            "row columns are unnamed and cannot be deserialized as a map",
+        ));
+    }
+
+    visitor.visit_map(RowAccess { row, idx: 0 })
+}
+
+fn deserialize_row_seq<'de, R, V>(row: &'de R, visitor: V) -> Result<V::Value, Error>
+where
+    R: RowData,
+    V: Visitor<'de>,
+{
+    visitor.visit_seq(RowAccess { row, idx: 0 })
+}
+
+fn deserialize_row_struct<'de, R, V>(row: &'de R, visitor: V) -> Result<V::Value, Error>
+where
+    R: RowData,
+    V: Visitor<'de>,
+{
+    // Rows without column names map onto struct fields by position.
+    deserialize_row_any(row, visitor)
+}
+
+macro_rules! row_deserializer {
+    ($t:ty) => {
+        impl<'de> Deserializer<'de> for &'de $t {
+            type Error = Error;
+
+            fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Error>
+            where
+                V: Visitor<'de>,
+            {
+                deserialize_row_any(self, visitor)
+            }
+
+            fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Error>
+            where
+                V: Visitor<'de>,
+            {
+                visitor.visit_some(self)
+            }
+
+            fn deserialize_newtype_struct<V>(
+                self,
+                _: &'static str,
+                visitor: V,
+            ) -> Result<V::Value, Error>
+            where
+                V: Visitor<'de>,
+            {
+                visitor.visit_newtype_struct(self)
+            }
+
+            fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Error>
+            where
+                V: Visitor<'de>,
+            {
+                deserialize_row_seq(self, visitor)
+            }
+
+            fn deserialize_tuple<V>(self, _: usize, visitor: V) -> Result<V::Value, Error>
+            where
+                V: Visitor<'de>,
+            {
+                deserialize_row_seq(self, visitor)
+            }
+
+            fn deserialize_tuple_struct<V>(
+                self,
+                _: &'static str,
+                _: usize,
+                visitor: V,
+            ) -> Result<V::Value, Error>
+            where
+                V: Visitor<'de>,
+            {
+                deserialize_row_seq(self, visitor)
+            }
+
+            fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Error>
+            where
+                V: Visitor<'de>,
+            {
+                deserialize_row_map(self, visitor)
+            }
+
+            fn deserialize_struct<V>(
+                self,
+                _: &'static str,
+                _: &'static [&'static str],
+                visitor: V,
+            ) -> Result<V::Value, Error>
+            where
+                V: Visitor<'de>,
+            {
+                deserialize_row_struct(self, visitor)
+            }
+
+            fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Error>
+            where
+                V: Visitor<'de>,
+            {
+                visitor.visit_unit()
+            }
+
+            forward_to_deserialize_any! {
+                bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+                bytes byte_buf unit unit_struct enum identifier
+            }
+        }
+    };
+}
+
+row_deserializer!(Row);
+row_deserializer!(BinaryCopyOutRow);
+
+struct RowAccess<'de, R> {
+    row: &'de R,
+    idx: usize,
+}
+
+impl<'de, R> RowAccess<'de, R>
+where
+    R: RowData,
+{
+    fn value(&self, idx: usize) -> Result<ValueDeserializer<'de>, ValueError> {
+        Ok(ValueDeserializer {
+            type_: self.row.type_(idx),
+            raw: self.row.raw(idx)?,
+        })
+    }
+}
+
+impl<'de, R> SeqAccess<'de> for RowAccess<'de, R>
+where
+    R: RowData,
+{
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        if self.idx == self.row.len() {
+            return Ok(None);
+        }
+
+        let idx = self.idx;
+        self.idx += 1;
+        self.value(idx)
+            .and_then(|value| seed.deserialize(value))
+            .map(Some)
+            .map_err(|e| Error::from_sql(e.0, idx))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.row.len() - self.idx)
+    }
+}
+
+impl<'de, R> MapAccess<'de> for RowAccess<'de, R>
+where
+    R: RowData,
+{
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        if self.idx == self.row.len() {
+            return Ok(None);
+        }
+
+        let name = self.row.name(self.idx).unwrap_or_default();
+        seed.deserialize(name.into_deserializer()).map(Some)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let idx = self.idx;
+        self.idx += 1;
+        self.value(idx)
+            .and_then(|value| seed.deserialize(value))
+            .map_err(|e| Error::from_sql(e.0, idx))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.row.len() - self.idx)
+    }
+}
+
+/// An error deserializing an individual value.
+///
+/// This is attached to the index of the offending column when returned from a row.
+#[derive(Debug)]
+struct ValueError(Box<dyn error::Error + Sync + Send>);
+
+impl fmt::Display for ValueError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, fmt)
+    }
+}
+
+impl error::Error for ValueError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        self.0.source()
+    }
+}
+
+impl de::Error for ValueError {
+    fn custom<T>(msg: T) -> ValueError
+    where
+        T: fmt::Display,
+    {
+        ValueError(msg.to_string().into())
+    }
+}
+
+impl From<Box<dyn error::Error + Sync + Send>> for ValueError {
+    fn from(e: Box<dyn error::Error + Sync + Send>) -> ValueError {
+        ValueError(e)
+    }
+}
+
+impl From<serde_json_1::Error> for ValueError {
+    fn from(e: serde_json_1::Error) -> ValueError {
+        ValueError(Box::new(e))
+    }
+}
+
+/// A deserializer over a single Postgres value in the binary format.
+struct ValueDeserializer<'de> {
+    type_: &'de Type,
+    raw: Option<&'de [u8]>,
+}
+
+impl<'de> ValueDeserializer<'de> {
+    fn raw(&self) -> Result<&'de [u8], ValueError> {
+        self.raw
+            .ok_or_else(|| de::Error::custom("unexpected NULL value"))
+    }
+
+    /// Returns the type used to decode the value, looking through domains.
+    fn base_type(&self) -> &'de Type {
+        let mut type_ = self.type_;
+        while let Kind::Domain(base) = type_.kind() {
+            type_ = base;
+        }
+        type_
+    }
+
+    fn json(
+        &self,
+    ) -> Result<serde_json_1::Deserializer<serde_json_1::de::SliceRead<'de>>, ValueError> {
+        let mut raw = self.raw()?;
+        if *self.base_type() == Type::JSONB {
+            let mut b = [0; 1];
+            raw.read_exact(&mut b)
+                .map_err(|e| ValueError(Box::new(e)))?;
+            // We only support version 1 of the jsonb binary format
+            if b[0] != 1 {
+                return Err(de::Error::custom("unsupported JSONB encoding version"));
+            }
+        }
+        Ok(serde_json_1::Deserializer::from_slice(raw))
+    }
+
+    fn is_json(&self) -> bool {
+        matches!(*self.base_type(), Type::JSON | Type::JSONB)
+    }
+}
+
+fn from_sql<'de, T>(type_: &Type, raw: &'de [u8]) -> Result<T, ValueError>
+where
+    T: FromSql<'de>,
+{
+    T::from_sql(type_, raw).map_err(ValueError)
+}
+
+impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
+    type Error = ValueError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, ValueError>
+    where
+        V: Visitor<'de>,
+    {
+        let raw = match self.raw {
+            Some(raw) => raw,
+            None => return visitor.visit_none(),
+        };
+
+        let type_ = self.base_type();
+        match *type_ {
+            Type::BOOL => visitor.visit_bool(from_sql(type_, raw)?),
+            Type::CHAR => visitor.visit_i8(from_sql(type_, raw)?),
+            Type::INT2 => visitor.visit_i16(from_sql(type_, raw)?),
+            Type::INT4 => visitor.visit_i32(from_sql(type_, raw)?),
+            Type::INT8 => visitor.visit_i64(from_sql(type_, raw)?),
+            Type::OID => visitor.visit_u32(from_sql(type_, raw)?),
+            Type::FLOAT4 => visitor.visit_f32(from_sql(type_, raw)?),
+            Type::FLOAT8 => visitor.visit_f64(from_sql(type_, raw)?),
+            Type::BYTEA => visitor.visit_borrowed_bytes(types::bytea_from_sql(raw)),
+            // types without a counterpart in the serde data model are passed as their Postgres text representation
+            Type::NUMERIC
+            | Type::DATE
+            | Type::TIME
+            | Type::TIMETZ
+            | Type::TIMESTAMP
+            | Type::TIMESTAMPTZ
+            | Type::INTERVAL
+            | Type::UUID
+            | Type::INET
+            | Type::CIDR
+            | Type::MACADDR => {
+                let mut s = String::new();
+                text_encode::write(type_, raw, &mut s)?;
+                visitor.visit_string(s)
+            }
+            Type::JSON | Type::JSONB => {
+                let mut json = self.json()?;
+                let value = json.deserialize_any(visitor)?;
+                json.end()?;
+                Ok(value)
+            }
+            ref ty if <&str as FromSql>::accepts(ty) => {
+                visitor.visit_borrowed_str(from_sql(type_, raw)?)
+            }
+            ref ty => match ty.kind() {
+                Kind::Enum(_) => visitor.visit_borrowed_str(types::text_from_sql(raw)?),
+                Kind::Array(member) => deserialize_array(member, raw, visitor),
+                Kind::Composite(fields) => visitor.visit_map(CompositeAccess::new(fields, raw)?),
+                _ => Err(de::Error::custom(format_args!(
+                    "cannot deserialize a value of type {}",
+                    ty
+                ))),
+            },
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, ValueError>
+    where
+        V: Visitor<'de>,
+    {
+        match self.raw {
+            Some(_) => visitor.visit_some(self),
+            None => visitor.visit_none(),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, ValueError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, ValueError>
+    where
+        V: Visitor<'de>,
+    {
+        if self.is_json() {
+            let mut json = self.json()?;
+            let value = json.deserialize_enum(name, variants, visitor)?;
+            json.end()?;
+            return Ok(value);
+        }
+
+        let raw = self.raw()?;
+        let variant = match self.base_type().kind() {
+            Kind::Enum(_) => types::text_from_sql(raw)?,
+            _ => from_sql::<&str>(self.base_type(), raw)?,
+        };
+        visitor.visit_enum(variant.into_deserializer())
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, ValueError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct identifier
+    }
+}
+
+fn deserialize_array<'de, V>(
+    member: &'de Type,
+    raw: &'de [u8],
+    visitor: V,
+) -> Result<V::Value, ValueError>
+where
+    V: Visitor<'de>,
+{
+    let array = types::array_from_sql(raw)?;
+    let mut dimensions = array
+        .dimensions()
+        .map(|d| Ok(d.len as usize))
+        .collect::<Vec<_>>()?;
+    // Empty arrays have no dimensions at all.
+    if dimensions.is_empty() {
+        dimensions.push(0);
+    }
+    let mut values = array.values().collect::<Vec<_>>()?.into_iter();
+
+    ArrayDeserializer {
+        member,
+        dimensions: &dimensions,
+        values: &mut values,
+    }
+    .deserialize_any(visitor)
+}
+
+/// A deserializer over one dimension of a multidimensional array.
+///
+/// Elements are stored flattened in row-major order, so each dimension consumes the values of its sub-arrays in turn.
+struct ArrayDeserializer<'a, 'de> {
+    member: &'de Type,
+    dimensions: &'a [usize],
+    values: &'a mut vec::IntoIter<Option<&'de [u8]>>,
+}
+
+impl<'a, 'de> Deserializer<'de> for ArrayDeserializer<'a, 'de> {
+    type Error = ValueError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, ValueError>
+    where
+        V: Visitor<'de>,
+    {
+        let (len, rest) = match self.dimensions.split_first() {
+            Some(split) => split,
+            None => return Err(de::Error::custom("invalid array dimensions")),
+        };
+
+        let mut access = NestedArrayAccess {
+            member: self.member,
+            dimensions: rest,
+            values: self.values,
+            remaining: *len,
+        };
+        let value = visitor.visit_seq(&mut access)?;
+        // skip over any sub-arrays which the visitor didn't consume
+        while access.remaining > 0 {
+            access.next_element::<de::IgnoredAny>()?;
+        }
+        Ok(value)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+struct NestedArrayAccess<'a, 'de> {
+    member: &'de Type,
+    dimensions: &'a [usize],
+    values: &'a mut vec::IntoIter<Option<&'de [u8]>>,
+    remaining: usize,
+}
+
+impl<'a, 'de> SeqAccess<'de> for NestedArrayAccess<'a, 'de> {
+    type Error = ValueError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, ValueError>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+
+        if self.dimensions.is_empty() {
+            let raw = match self.values.next() {
+                Some(raw) => raw,
+                None => return Err(de::Error::custom("array contains too few values")),
+            };
+            seed.deserialize(ValueDeserializer {
+                type_: self.member,
+                raw,
+            })
+            .map(Some)
+        } else {
+            seed.deserialize(ArrayDeserializer {
+                member: self.member,
+                dimensions: self.dimensions,
+                values: self.values,
+            })
+            .map(Some)
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+/// A map over the fields of a composite value.
+struct CompositeAccess<'de> {
+    fields: vec::IntoIter<(&'de str, ValueDeserializer<'de>)>,
+    value: Option<ValueDeserializer<'de>>,
+}
+
+impl<'de> CompositeAccess<'de> {
+    fn new(fields: &'de [Field], mut raw: &'de [u8]) -> Result<Self, ValueError> {
+        let num_fields = read_be_i32(&mut raw)?;
+        if num_fields as usize != fields.len() {
+            return Err(de::Error::custom(format_args!(
+                "invalid field count: {} vs {}",
+                num_fields,
+                fields.len()
+            )));
+        }
+
+        let mut values = Vec::with_capacity(fields.len());
+        for field in fields {
+            // skip the field's type OID
+            read_be_i32(&mut raw)?;
+            let len = read_be_i32(&mut raw)?;
+            let value = if len < 0 {
+                None
+            } else {
+                if len as usize > raw.len() {
+                    return Err(de::Error::custom("invalid buffer size"));
+                }
+                let (head, tail) = raw.split_at(len as usize);
+                raw = tail;
+                Some(head)
+            };
+            values.push((
+                field.name(),
+                ValueDeserializer {
+                    type_: field.type_(),
+                    raw: value,
+                },
+            ));
+        }
+
+        Ok(CompositeAccess {
+            fields: values.into_iter(),
+            value: None,
+        })
+    }
+}
+
+fn read_be_i32(buf: &mut &[u8]) -> Result<i32, ValueError> {
+    if buf.len() < 4 {
+        return Err(de::Error::custom("invalid buffer size"));
+    }
+    let mut bytes = [0; 4];
+    bytes.copy_from_slice(&buf[..4]);
+    *buf = &buf[4..];
+    Ok(i32::from_be_bytes(bytes))
+}
+
+impl<'de> MapAccess<'de> for CompositeAccess<'de> {
+    type Error = ValueError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, ValueError>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match self.fields.next() {
+            Some((name, value)) => {
+                self.value = Some(value);
+                seed.deserialize(name.into_deserializer()).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, ValueError>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        match self.value.take() {
+            Some(value) => seed.deserialize(value),
+            None => Err(de::Error::custom("value requested before key")),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.fields.len())
+    }
+}
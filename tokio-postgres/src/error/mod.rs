@@ -251,6 +251,23 @@ impl DbError {
         self.where_.as_deref()
     }
 
+    /// If the error occurred while processing `COPY` data, the 1-based row number reported in the `where_` context.
+    ///
+    /// For text and CSV data this is the line number of the input, including any header line.
+    ///
+    /// The server does not report the row as a separate field, so it is parsed from the English `COPY table, line N`
+    /// context message. If the server's `lc_messages` setting selects a translation, this returns `None`.
+    pub fn copy_error_row(&self) -> Option<u64> {
+        self.where_.as_deref()?.lines().find_map(|line| {
+            let rest = line.strip_prefix("COPY ")?;
+            let idx = rest.find(", line ")?;
+            let digits = rest[idx + ", line ".len()..]
+                .split(|c: char| !c.is_ascii_digit())
+                .next()?;
+            digits.parse().ok()
+        })
+    }
+
     /// If the error was associated with a specific database object, the name
     /// of the schema containing that object, if any. (PostgreSQL 9.3+)
     pub fn schema(&self) -> Option<&str> {
@@ -345,6 +362,7 @@ enum Kind {
     FromSql(usize),
     Column(String),
     Parameters(usize, usize),
+    Parameter(String),
     Closed,
     Db,
     Parse,
@@ -356,6 +374,8 @@ enum Kind {
     #[cfg(feature = "runtime")]
     Connect,
     Timeout,
+    #[cfg(feature = "serde")]
+    Deserialize,
 }
 
 struct ErrorInner {
@@ -387,6 +407,7 @@ impl fmt::Display for Error {
             Kind::Parameters(real, expected) => {
                 write!(fmt, "expected {expected} parameters but got {real}")?
             }
+            Kind::Parameter(name) => write!(fmt, "missing parameter `{}`", name)?,
             Kind::Closed => fmt.write_str("connection closed")?,
             Kind::Db => fmt.write_str("db error")?,
             Kind::Parse => fmt.write_str("error parsing response from server")?,
@@ -398,6 +419,8 @@ impl fmt::Display for Error {
             #[cfg(feature = "runtime")]
             Kind::Connect => fmt.write_str("error connecting to server")?,
             Kind::Timeout => fmt.write_str("timeout waiting for server")?,
+            #[cfg(feature = "serde")]
+            Kind::Deserialize => fmt.write_str("error deserializing row")?,
         };
         if let Some(ref cause) = self.0.cause {
             write!(fmt, ": {}", cause)?;
@@ -437,6 +460,14 @@ impl Error {
         self.as_db_error().map(DbError::code)
     }
 
+    /// Returns the row of `COPY` data associated with the error.
+    ///
+    /// This is a convenience method that downcasts the cause to a `DbError` and returns its `copy_error_row`. As
+    /// described there, the row is only available when the server reports messages in English.
+    pub fn copy_error_row(&self) -> Option<u64> {
+        self.as_db_error().and_then(DbError::copy_error_row)
+    }
+
     fn new(kind: Kind, cause: Option<Box<dyn error::Error + Sync + Send>>) -> Error {
         Error(Box::new(ErrorInner { kind, cause }))
     }
@@ -482,6 +513,10 @@ impl Error {
         Error::new(Kind::Parameters(real, expected), None)
     }
 
+    pub(crate) fn parameter(name: String) -> Error {
+        Error::new(Kind::Parameter(name), None)
+    }
+
     pub(crate) fn tls(e: Box<dyn error::Error + Sync + Send>) -> Error {
         Error::new(Kind::Tls, Some(e))
     }
@@ -511,6 +546,11 @@ impl Error {
         Error::new(Kind::Connect, Some(Box::new(e)))
     }
 
+    #[cfg(feature = "serde")]
+    pub(crate) fn deserialize(e: Box<dyn error::Error + Sync + Send>) -> Error {
+        Error::new(Kind::Deserialize, Some(e))
+    }
+
     #[doc(hidden)]
     pub fn __private_api_timeout() -> Error {
         Error::new(Kind::Timeout, None)
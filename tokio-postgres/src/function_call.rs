@@ -0,0 +1,60 @@
+use crate::client::InnerClient;
+use crate::codec::FrontendMessage;
+use crate::connection::RequestMessages;
+use crate::types::{IsNull, Oid, ToSql, Type};
+use crate::Error;
+use bytes::Bytes;
+use log::debug;
+use postgres_protocol::message::backend::Message;
+use postgres_protocol::message::frontend;
+
+pub async fn function_call(
+    client: &InnerClient,
+    function: Oid,
+    args: &[(&(dyn ToSql + Sync), Type)],
+) -> Result<Option<Bytes>, Error> {
+    debug!("calling function {}", function);
+
+    let buf = encode(client, function, args)?;
+    let mut responses = client.send(RequestMessages::Single(FrontendMessage::Raw(buf)))?;
+
+    let value = match responses.next().await? {
+        Message::FunctionCallResponse(body) => body.into_value(),
+        _ => return Err(Error::unexpected_message()),
+    };
+
+    match responses.next().await? {
+        Message::ReadyForQuery(_) => Ok(value),
+        _ => Err(Error::unexpected_message()),
+    }
+}
+
+fn encode(
+    client: &InnerClient,
+    function: Oid,
+    args: &[(&(dyn ToSql + Sync), Type)],
+) -> Result<Bytes, Error> {
+    client.with_buf(|buf| {
+        let mut error_idx = 0;
+        let r = frontend::function_call(
+            function,
+            args.iter().map(|(arg, ty)| arg.encode_format(ty) as i16),
+            args.iter().enumerate(),
+            |(idx, (arg, ty)), buf| match arg.to_sql_checked(ty, buf) {
+                Ok(IsNull::No) => Ok(postgres_protocol::IsNull::No),
+                Ok(IsNull::Yes) => Ok(postgres_protocol::IsNull::Yes),
+                Err(e) => {
+                    error_idx = idx;
+                    Err(e)
+                }
+            },
+            1,
+            buf,
+        );
+        match r {
+            Ok(()) => Ok(buf.split().freeze()),
+            Err(frontend::BindError::Conversion(e)) => Err(Error::to_sql(e, error_idx)),
+            Err(frontend::BindError::Serialization(e)) => Err(Error::encode(e)),
+        }
+    })
+}
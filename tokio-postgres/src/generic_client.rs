@@ -56,6 +56,34 @@ pub trait GenericClient: private::Sealed {
         I: IntoIterator<Item = P> + Sync + Send,
         I::IntoIter: ExactSizeIterator;
 
+    /// Like `Client::execute_named`.
+    async fn execute_named(
+        &self,
+        query: &str,
+        params: &[(&str, &(dyn ToSql + Sync))],
+    ) -> Result<u64, Error>;
+
+    /// Like `Client::query_named`.
+    async fn query_named(
+        &self,
+        query: &str,
+        params: &[(&str, &(dyn ToSql + Sync))],
+    ) -> Result<Vec<Row>, Error>;
+
+    /// Like `Client::query_one_named`.
+    async fn query_one_named(
+        &self,
+        query: &str,
+        params: &[(&str, &(dyn ToSql + Sync))],
+    ) -> Result<Row, Error>;
+
+    /// Like `Client::query_opt_named`.
+    async fn query_opt_named(
+        &self,
+        query: &str,
+        params: &[(&str, &(dyn ToSql + Sync))],
+    ) -> Result<Option<Row>, Error>;
+
     /// Like `Client::prepare`.
     async fn prepare(&self, query: &str) -> Result<Statement, Error>;
 
@@ -136,6 +164,38 @@ impl GenericClient for Client {
         self.query_raw(statement, params).await
     }
 
+    async fn execute_named(
+        &self,
+        query: &str,
+        params: &[(&str, &(dyn ToSql + Sync))],
+    ) -> Result<u64, Error> {
+        self.execute_named(query, params).await
+    }
+
+    async fn query_named(
+        &self,
+        query: &str,
+        params: &[(&str, &(dyn ToSql + Sync))],
+    ) -> Result<Vec<Row>, Error> {
+        self.query_named(query, params).await
+    }
+
+    async fn query_one_named(
+        &self,
+        query: &str,
+        params: &[(&str, &(dyn ToSql + Sync))],
+    ) -> Result<Row, Error> {
+        self.query_one_named(query, params).await
+    }
+
+    async fn query_opt_named(
+        &self,
+        query: &str,
+        params: &[(&str, &(dyn ToSql + Sync))],
+    ) -> Result<Option<Row>, Error> {
+        self.query_opt_named(query, params).await
+    }
+
     async fn prepare(&self, query: &str) -> Result<Statement, Error> {
         self.prepare(query).await
     }
@@ -222,6 +282,38 @@ impl GenericClient for Transaction<'_> {
         self.query_raw(statement, params).await
     }
 
+    async fn execute_named(
+        &self,
+        query: &str,
+        params: &[(&str, &(dyn ToSql + Sync))],
+    ) -> Result<u64, Error> {
+        self.execute_named(query, params).await
+    }
+
+    async fn query_named(
+        &self,
+        query: &str,
+        params: &[(&str, &(dyn ToSql + Sync))],
+    ) -> Result<Vec<Row>, Error> {
+        self.query_named(query, params).await
+    }
+
+    async fn query_one_named(
+        &self,
+        query: &str,
+        params: &[(&str, &(dyn ToSql + Sync))],
+    ) -> Result<Row, Error> {
+        self.query_one_named(query, params).await
+    }
+
+    async fn query_opt_named(
+        &self,
+        query: &str,
+        params: &[(&str, &(dyn ToSql + Sync))],
+    ) -> Result<Option<Row>, Error> {
+        self.query_opt_named(query, params).await
+    }
+
     async fn prepare(&self, query: &str) -> Result<Statement, Error> {
         self.prepare(query).await
     }
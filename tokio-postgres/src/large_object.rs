@@ -0,0 +1,400 @@
+//! Utilities for working with PostgreSQL large objects.
+
+use crate::client::InnerClient;
+use crate::types::{FromSql, Oid, ToSql, Type};
+use crate::{prepare, query, slice_iter, Client, Error, Statement};
+use bytes::{Buf, Bytes};
+use futures_util::{future, ready, Future, TryStreamExt};
+use std::convert::TryFrom;
+use std::io::{self, SeekFrom};
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tokio::io::{AsyncRead, AsyncSeek, AsyncWrite, ReadBuf};
+
+const INV_WRITE: i32 = 0x0002_0000;
+const INV_READ: i32 = 0x0004_0000;
+
+const SEEK_SET: i32 = 0;
+const SEEK_CUR: i32 = 1;
+const SEEK_END: i32 = 2;
+
+type BoxFuture<T> = Pin<Box<dyn Future<Output = Result<T, Error>> + Send>>;
+
+/// The mode a large object is opened in.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Mode {
+    /// The object can only be read.
+    ///
+    /// Reads see the contents of the object as of the snapshot of the transaction that opened it.
+    Read,
+    /// The object can only be written.
+    Write,
+    /// The object can be both read and written.
+    ReadWrite,
+}
+
+impl Mode {
+    fn flags(self) -> i32 {
+        match self {
+            Mode::Read => INV_READ,
+            Mode::Write => INV_WRITE,
+            Mode::ReadWrite => INV_READ | INV_WRITE,
+        }
+    }
+}
+
+/// The statements used to operate on large objects, prepared once per client.
+pub(crate) struct Statements {
+    open: Statement,
+    read: Statement,
+    write: Statement,
+    seek: Statement,
+    tell: Statement,
+    truncate: Statement,
+    close: Statement,
+}
+
+enum State {
+    Idle,
+    Reading(BoxFuture<Vec<u8>>),
+    Writing(BoxFuture<usize>),
+    Seeking(BoxFuture<u64>),
+}
+
+/// An open PostgreSQL large object.
+///
+/// Large objects can only be accessed within a transaction, and the handle is closed automatically when the
+/// transaction ends. Returned by the [`Transaction::open_large_object`] method.
+///
+/// [`Transaction::open_large_object`]: crate::Transaction::open_large_object
+pub struct LargeObject<'a> {
+    client: Arc<InnerClient>,
+    statements: Arc<Statements>,
+    oid: Oid,
+    fd: i32,
+    pos: u64,
+    // data read from the server but not yet returned, so the server's position is `pos + buf.len()`
+    buf: Bytes,
+    state: State,
+    _p: PhantomData<&'a Client>,
+}
+
+impl<'a> LargeObject<'a> {
+    pub(crate) async fn open(
+        client: &Arc<InnerClient>,
+        oid: Oid,
+        mode: Mode,
+    ) -> Result<LargeObject<'a>, Error> {
+        let statements = match client.large_object_statements() {
+            Some(statements) => statements,
+            None => {
+                let statements = Arc::new(prepare_statements(client).await?);
+                client.set_large_object_statements(&statements);
+                statements
+            }
+        };
+
+        let fd = call(client, statements.open.clone(), &[&oid, &mode.flags()]).await?;
+
+        Ok(LargeObject {
+            client: client.clone(),
+            statements,
+            oid,
+            fd,
+            pos: 0,
+            buf: Bytes::new(),
+            state: State::Idle,
+            _p: PhantomData,
+        })
+    }
+
+    /// Returns the OID of the large object.
+    pub fn oid(&self) -> Oid {
+        self.oid
+    }
+
+    /// Reads data from the current position of the object into the provided buffer, returning the number of bytes
+    /// read.
+    ///
+    /// A return value of 0 indicates that the end of the object has been reached.
+    pub async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+        if self.buf.is_empty() && !buf.is_empty() {
+            self.buf = Bytes::from(self.read_future(buf.len()).await?);
+        }
+        let len = usize::min(self.buf.len(), buf.len());
+        self.buf.copy_to_slice(&mut buf[..len]);
+        self.pos += len as u64;
+        Ok(len)
+    }
+
+    /// Writes the provided data at the current position of the object, returning the number of bytes written.
+    pub async fn write(&mut self, buf: &[u8]) -> Result<usize, Error> {
+        let n = self.write_future(buf).await?;
+        self.pos += n as u64;
+        Ok(n)
+    }
+
+    /// Moves the current position of the object, returning the new position.
+    pub async fn seek(&mut self, pos: SeekFrom) -> Result<u64, Error> {
+        let future = self.seek_future(pos).map_err(Error::io)?;
+        self.pos = future.await?;
+        Ok(self.pos)
+    }
+
+    /// Returns the current position of the object.
+    pub async fn tell(&mut self) -> Result<u64, Error> {
+        let pos = call::<i64>(&self.client, self.statements.tell.clone(), &[&self.fd]).await?;
+        self.pos = pos as u64 - self.buf.len() as u64;
+        Ok(self.pos)
+    }
+
+    /// Truncates or extends the object to the specified length.
+    ///
+    /// The current position of the object is not changed.
+    pub async fn truncate(&mut self, len: u64) -> Result<(), Error> {
+        let buffered = self.take_buffered();
+        rewind(&self.client, &self.statements, self.fd, buffered).await?;
+        call::<i32>(
+            &self.client,
+            self.statements.truncate.clone(),
+            &[&self.fd, &(len as i64)],
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Consumes the handle, closing the large object.
+    ///
+    /// This is equivalent to dropping the handle and letting the transaction close it when it ends, but allows the
+    /// object to be opened again within the same transaction.
+    pub async fn close(self) -> Result<(), Error> {
+        call::<i32>(&self.client, self.statements.close.clone(), &[&self.fd]).await?;
+        Ok(())
+    }
+
+    fn read_future(&self, len: usize) -> BoxFuture<Vec<u8>> {
+        let client = self.client.clone();
+        let statements = self.statements.clone();
+        let fd = self.fd;
+        let len = usize::min(len, i32::MAX as usize) as i32;
+        Box::pin(async move { call(&client, statements.read.clone(), &[&fd, &len]).await })
+    }
+
+    // discards buffered data, returning the distance the server's position is ahead of `pos`
+    fn take_buffered(&mut self) -> i64 {
+        let buffered = self.buf.len() as i64;
+        self.buf.clear();
+        buffered
+    }
+
+    fn write_future(&mut self, buf: &[u8]) -> BoxFuture<usize> {
+        let client = self.client.clone();
+        let statements = self.statements.clone();
+        let fd = self.fd;
+        let buf = buf.to_vec();
+        let buffered = self.take_buffered();
+        Box::pin(async move {
+            rewind(&client, &statements, fd, buffered).await?;
+            let n = call::<i32>(&client, statements.write.clone(), &[&fd, &buf]).await?;
+            Ok(n as usize)
+        })
+    }
+
+    fn seek_future(&mut self, pos: SeekFrom) -> io::Result<BoxFuture<u64>> {
+        let client = self.client.clone();
+        let statements = self.statements.clone();
+        let fd = self.fd;
+        let (offset, whence) = match pos {
+            SeekFrom::Start(offset) => {
+                (i64::try_from(offset).map_err(|_| invalid_seek())?, SEEK_SET)
+            }
+            SeekFrom::Current(offset) => (
+                offset
+                    .checked_sub(self.buf.len() as i64)
+                    .ok_or_else(invalid_seek)?,
+                SEEK_CUR,
+            ),
+            SeekFrom::End(offset) => (offset, SEEK_END),
+        };
+        self.buf.clear();
+        Ok(Box::pin(async move {
+            let pos =
+                call::<i64>(&client, statements.seek.clone(), &[&fd, &offset, &whence]).await?;
+            Ok(pos as u64)
+        }))
+    }
+}
+
+async fn rewind(
+    client: &InnerClient,
+    statements: &Statements,
+    fd: i32,
+    buffered: i64,
+) -> Result<(), Error> {
+    if buffered != 0 {
+        call::<i64>(
+            client,
+            statements.seek.clone(),
+            &[&fd, &-buffered, &SEEK_CUR],
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+async fn call<T>(
+    client: &InnerClient,
+    statement: Statement,
+    params: &[&(dyn ToSql + Sync)],
+) -> Result<T, Error>
+where
+    T: for<'a> FromSql<'a>,
+{
+    let rows = query::query(client, statement, slice_iter(params))
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    match rows.first() {
+        Some(row) => row.try_get(0),
+        None => Err(Error::row_count()),
+    }
+}
+
+fn busy() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Other,
+        "another large object operation is in progress",
+    )
+}
+
+async fn prepare_statements(client: &Arc<InnerClient>) -> Result<Statements, Error> {
+    let mut statements = future::try_join_all(
+        [
+            ("SELECT lo_open($1, $2)", &[Type::OID, Type::INT4][..]),
+            ("SELECT loread($1, $2)", &[Type::INT4, Type::INT4]),
+            ("SELECT lowrite($1, $2)", &[Type::INT4, Type::BYTEA]),
+            (
+                "SELECT lo_lseek64($1, $2, $3)",
+                &[Type::INT4, Type::INT8, Type::INT4],
+            ),
+            ("SELECT lo_tell64($1)", &[Type::INT4]),
+            ("SELECT lo_truncate64($1, $2)", &[Type::INT4, Type::INT8]),
+            ("SELECT lo_close($1)", &[Type::INT4]),
+        ]
+        .iter()
+        .map(|(query, types)| prepare::prepare(client, query, types)),
+    )
+    .await?
+    .into_iter();
+    let mut next = || statements.next().unwrap();
+
+    Ok(Statements {
+        open: next(),
+        read: next(),
+        write: next(),
+        seek: next(),
+        tell: next(),
+        truncate: next(),
+        close: next(),
+    })
+}
+
+fn invalid_seek() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        "invalid seek to an out of range position",
+    )
+}
+
+impl AsyncRead for LargeObject<'_> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        loop {
+            match &mut this.state {
+                State::Idle if !this.buf.is_empty() => {
+                    let len = usize::min(this.buf.len(), buf.remaining());
+                    buf.put_slice(&this.buf.split_to(len));
+                    this.pos += len as u64;
+                    return Poll::Ready(Ok(()));
+                }
+                State::Idle => this.state = State::Reading(this.read_future(buf.remaining())),
+                State::Reading(future) => {
+                    let r = ready!(future.as_mut().poll(cx));
+                    this.state = State::Idle;
+                    let data = r.map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+                    if data.is_empty() {
+                        return Poll::Ready(Ok(()));
+                    }
+                    // the caller's buffer may have shrunk since the read was issued, so hold onto the data
+                    this.buf = Bytes::from(data);
+                }
+                _ => return Poll::Ready(Err(busy())),
+            }
+        }
+    }
+}
+
+impl AsyncWrite for LargeObject<'_> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        loop {
+            match &mut this.state {
+                State::Idle => this.state = State::Writing(this.write_future(buf)),
+                State::Writing(future) => {
+                    let r = ready!(future.as_mut().poll(cx));
+                    this.state = State::Idle;
+                    let n = r.map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+                    this.pos += n as u64;
+                    return Poll::Ready(Ok(n));
+                }
+                _ => return Poll::Ready(Err(busy())),
+            }
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncSeek for LargeObject<'_> {
+    fn start_seek(self: Pin<&mut Self>, position: SeekFrom) -> io::Result<()> {
+        let this = self.get_mut();
+        match this.state {
+            State::Idle => {
+                this.state = State::Seeking(this.seek_future(position)?);
+                Ok(())
+            }
+            _ => Err(busy()),
+        }
+    }
+
+    fn poll_complete(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<u64>> {
+        let this = self.get_mut();
+        match &mut this.state {
+            State::Idle => Poll::Ready(Ok(this.pos)),
+            State::Seeking(future) => {
+                let r = ready!(future.as_mut().poll(cx));
+                this.state = State::Idle;
+                this.pos = r.map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+                Poll::Ready(Ok(this.pos))
+            }
+            _ => Poll::Ready(Err(busy())),
+        }
+    }
+}
@@ -104,13 +104,19 @@
 //! | Feature | Description | Extra dependencies | Default |
 //! | ------- | ----------- | ------------------ | ------- |
 //! | `runtime` | Enable convenience API for the connection process based on the `tokio` crate. | [tokio](https://crates.io/crates/tokio) 1.0 with the features `net` and `time` | yes |
+//! | `serde` | Enable deserializing rows into `serde` types. | [serde](https://crates.io/crates/serde) 1.0, [serde_json](https://crates.io/crates/serde_json) 1.0 | no |
+//! | `arrow` | Enable conversions between rows and Apache Arrow record batches. | [arrow-array](https://crates.io/crates/arrow-array) 53, [arrow-schema](https://crates.io/crates/arrow-schema) 53 | no |
 //! | `array-impls` | Enables `ToSql` and `FromSql` trait impls for arrays | - | no |
+//! | `pgvector` | Enable support for the types of the `pgvector` extension. | [half](https://crates.io/crates/half) 2 | no |
+//! | `postgis` | Enable support for the `geometry` and `geography` types of the PostGIS extension as `geo-types` geometries. | [geo-types](https://crates.io/crates/geo-types/0.7.0) 0.7 | no |
 //! | `with-bit-vec-0_6` | Enable support for the `bit-vec` crate. | [bit-vec](https://crates.io/crates/bit-vec) 0.6 | no |
 //! | `with-chrono-0_4` | Enable support for the `chrono` crate. | [chrono](https://crates.io/crates/chrono) 0.4 | no |
 //! | `with-eui48-0_4` | Enable support for the 0.4 version of the `eui48` crate. | [eui48](https://crates.io/crates/eui48) 0.4 | no |
 //! | `with-eui48-1` | Enable support for the 1.0 version of the `eui48` crate. | [eui48](https://crates.io/crates/eui48) 1.0 | no |
 //! | `with-geo-types-0_6` | Enable support for the 0.6 version of the `geo-types` crate. | [geo-types](https://crates.io/crates/geo-types/0.6.0) 0.6 | no |
 //! | `with-geo-types-0_7` | Enable support for the 0.7 version of the `geo-types` crate. | [geo-types](https://crates.io/crates/geo-types/0.7.0) 0.7 | no |
+//! | `with-jiff-0_1` | Enable support for the 0.1 version of the `jiff` crate. | [jiff](https://crates.io/crates/jiff/0.1.0) 0.1 | no |
+//! | `with-ndarray-0_16` | Enable support for the 0.16 version of the `ndarray` crate. | [ndarray](https://crates.io/crates/ndarray/0.16.0) 0.16 | no |
 //! | `with-serde_json-1` | Enable support for the `serde_json` crate. | [serde_json](https://crates.io/crates/serde_json) 1.0 | no |
 //! | `with-uuid-0_8` | Enable support for the `uuid` crate. | [uuid](https://crates.io/crates/uuid) 0.8 | no |
 //! | `with-uuid-1` | Enable support for the `uuid` crate. | [uuid](https://crates.io/crates/uuid) 1.0 | no |
@@ -125,10 +131,14 @@ pub use crate::config::Config;
 pub use crate::connection::Connection;
 pub use crate::copy_in::CopyInSink;
 pub use crate::copy_out::CopyOutStream;
+pub use crate::cursor::CursorStream;
 use crate::error::DbError;
 pub use crate::error::Error;
 pub use crate::generic_client::GenericClient;
+pub use crate::listen::NotificationStream;
+pub use crate::named::NamedStatement;
 pub use crate::portal::Portal;
+pub use crate::prepared_xact::PreparedXact;
 pub use crate::query::RowStream;
 pub use crate::row::{Row, SimpleQueryRow};
 pub use crate::simple_query::SimpleQueryStream;
@@ -141,10 +151,14 @@ pub use crate::tls::NoTls;
 pub use crate::to_statement::ToStatement;
 pub use crate::transaction::Transaction;
 pub use crate::transaction_builder::{IsolationLevel, TransactionBuilder};
+pub use crate::type_registry::TypeRegistry;
 use crate::types::ToSql;
 
+#[cfg(feature = "arrow")]
+pub mod arrow;
 pub mod binary_copy;
 mod bind;
+pub mod bulk_load;
 #[cfg(feature = "runtime")]
 mod cancel_query;
 mod cancel_query_raw;
@@ -161,22 +175,34 @@ mod connect_tls;
 mod connection;
 mod copy_in;
 mod copy_out;
+mod cursor;
+#[cfg(feature = "serde")]
+mod de;
 pub mod error;
+mod function_call;
 mod generic_client;
 mod keepalive;
+pub mod large_object;
+mod listen;
+pub mod macros;
 mod maybe_tls_stream;
+mod named;
 mod portal;
 mod prepare;
+mod prepared_xact;
 mod query;
 pub mod row;
 mod simple_query;
 #[cfg(feature = "runtime")]
 mod socket;
 mod statement;
+pub mod text_copy;
+mod text_encode;
 pub mod tls;
 mod to_statement;
 mod transaction;
 mod transaction_builder;
+mod type_registry;
 pub mod types;
 
 /// A convenience function which parses a connection string and connects to the database.
@@ -234,7 +260,8 @@ pub enum AsyncMessage {
     Notice(DbError),
     /// A notification.
     ///
-    /// Connections can subscribe to notifications with the `LISTEN` command.
+    /// Connections can subscribe to notifications with the `LISTEN` command. Notifications on channels subscribed to
+    /// with `Client::listen` are also delivered to the corresponding `NotificationStream`.
     Notification(Notification),
 }
 
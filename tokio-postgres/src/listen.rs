@@ -0,0 +1,148 @@
+use crate::client::InnerClient;
+use crate::codec::FrontendMessage;
+use crate::connection::RequestMessages;
+use crate::Notification;
+use futures_channel::mpsc;
+use futures_util::{Stream, StreamExt};
+use parking_lot::Mutex;
+use postgres_protocol::escape::escape_identifier;
+use postgres_protocol::message::frontend;
+use std::collections::HashMap;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::{Arc, Weak};
+use std::task::{Context, Poll};
+
+#[derive(Default)]
+struct State {
+    channels: HashMap<String, Vec<(u64, mpsc::UnboundedSender<Notification>)>>,
+    next_id: u64,
+    closed: bool,
+}
+
+/// The notification subscriptions of a connection, shared between the `Client` and the `Connection`.
+#[derive(Default)]
+pub(crate) struct Listeners(Mutex<State>);
+
+impl Listeners {
+    fn subscribe(&self, channel: &str) -> (u64, mpsc::UnboundedReceiver<Notification>) {
+        let (sender, receiver) = mpsc::unbounded();
+
+        let mut state = self.0.lock();
+        let id = state.next_id;
+        state.next_id += 1;
+        // if the connection has already closed, drop the sender so the stream terminates immediately
+        if !state.closed {
+            state
+                .channels
+                .entry(channel.to_string())
+                .or_default()
+                .push((id, sender));
+        }
+
+        (id, receiver)
+    }
+
+    /// Removes a subscription, returning `true` if it was the last one for its channel.
+    fn unsubscribe(&self, channel: &str, id: u64) -> bool {
+        let mut state = self.0.lock();
+        let subscribers = match state.channels.get_mut(channel) {
+            Some(subscribers) => subscribers,
+            None => return false,
+        };
+
+        subscribers.retain(|(i, _)| *i != id);
+        if subscribers.is_empty() {
+            state.channels.remove(channel);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Forwards a notification to every subscriber of its channel.
+    pub fn dispatch(&self, notification: &Notification) {
+        let mut state = self.0.lock();
+        if let Some(subscribers) = state.channels.get_mut(notification.channel()) {
+            for (_, sender) in subscribers.iter() {
+                let _ = sender.unbounded_send(notification.clone());
+            }
+        }
+    }
+
+    /// Terminates all subscriptions after the connection has closed.
+    pub fn close(&self) {
+        let mut state = self.0.lock();
+        state.closed = true;
+        state.channels.clear();
+    }
+}
+
+/// A stream of notifications received on a single channel.
+///
+/// Returned by the [`Client::listen`] method. The stream terminates when the connection closes. Dropping the last
+/// stream subscribed to a channel stops listening on it with `UNLISTEN`.
+///
+/// [`Client::listen`]: crate::Client::listen
+pub struct NotificationStream {
+    client: Weak<InnerClient>,
+    channel: String,
+    id: u64,
+    receiver: mpsc::UnboundedReceiver<Notification>,
+}
+
+impl NotificationStream {
+    pub(crate) fn new(client: &Arc<InnerClient>, channel: &str) -> NotificationStream {
+        let (id, receiver) = client.listeners().subscribe(channel);
+
+        NotificationStream {
+            client: Arc::downgrade(client),
+            channel: channel.to_string(),
+            id,
+            receiver,
+        }
+    }
+
+    /// Returns the name of the channel the stream is listening on.
+    pub fn channel(&self) -> &str {
+        &self.channel
+    }
+}
+
+impl Stream for NotificationStream {
+    type Item = Notification;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Notification>> {
+        self.receiver.poll_next_unpin(cx)
+    }
+}
+
+impl Drop for NotificationStream {
+    fn drop(&mut self) {
+        if let Some(client) = self.client.upgrade() {
+            if client.listeners().unsubscribe(&self.channel, self.id) {
+                let buf = client.with_buf(|buf| {
+                    frontend::query(&unlisten_query(&self.channel), buf).unwrap();
+                    buf.split().freeze()
+                });
+                let _ = client.send(RequestMessages::Single(FrontendMessage::Raw(buf)));
+            }
+        }
+    }
+}
+
+impl fmt::Debug for NotificationStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NotificationStream")
+            .field("channel", &self.channel)
+            .finish()
+    }
+}
+
+pub(crate) fn listen_query(channel: &str) -> String {
+    format!("LISTEN {}", escape_identifier(channel))
+}
+
+fn unlisten_query(channel: &str) -> String {
+    format!("UNLISTEN {}", escape_identifier(channel))
+}
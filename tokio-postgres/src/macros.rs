@@ -0,0 +1,191 @@
+//! Support for compile-time checked queries.
+//!
+//! The macro itself is implemented by the `postgres-macros` crate, which must be a dependency of the crate using it.
+//!
+//! The [`query!`] macro prepares a statement while the crate using it is compiled, and checks the number and types of
+//! its parameters against the Rust expressions passed to it. It evaluates to a [`Query`] which deserializes the rows it
+//! returns into a `Record` struct generated from the statement's columns.
+//!
+//! # Database access
+//!
+//! If the `DATABASE_URL` environment variable is set at build time, the statement is prepared against that database.
+//! It may be either form of connection string accepted by [`Config`](crate::Config). Connections are made without TLS
+//! unless the `native-tls` Cargo feature of `postgres-macros` is enabled.
+//!
+//! If the `POSTGRES_MACROS_PREPARE` environment variable is also set to `1` or `true`, the statement's metadata is
+//! saved as a JSON file in the `.postgres-macros` directory of the crate being compiled. The source tree is never
+//! written to otherwise, so ordinary builds work with read-only or vendored sources.
+//!
+//! If `DATABASE_URL` is not set, or `POSTGRES_MACROS_OFFLINE` is set to `1` or `true`, the saved metadata is used
+//! instead. Checking the `.postgres-macros` directory into version control allows the crate to be built without a
+//! database.
+//!
+//! # Column types
+//!
+//! Columns of the following types map to Rust types, as do one-dimensional arrays of them, which map to `Vec`s of
+//! `Option`s since array elements may be null:
+//!
+//! | Postgres type                                 | Rust type |
+//! |-----------------------------------------------|-----------|
+//! | `BOOL`                                        | `bool`    |
+//! | `"char"`                                      | `i8`      |
+//! | `INT2`                                        | `i16`     |
+//! | `INT4`                                        | `i32`     |
+//! | `INT8`                                        | `i64`     |
+//! | `OID`                                         | `u32`     |
+//! | `FLOAT4`                                      | `f32`     |
+//! | `FLOAT8`                                      | `f64`     |
+//! | `TEXT`, `VARCHAR`, `CHAR(n)`, `NAME`          | `String`  |
+//! | `BYTEA`                                       | `Vec<u8>` |
+//!
+//! A column is wrapped in an `Option` unless it refers directly to a table column declared `NOT NULL`. Note that this
+//! is not accurate for columns from the nullable side of an outer join. The inference can be overridden by suffixing
+//! the column's name with `!` (not null) or `?` (nullable), and the Rust type of any column can be specified by
+//! suffixing its name with `: Type`:
+//!
+//! ```sql
+//! SELECT id AS "id!", created_at AS "created_at: std::time::SystemTime" FROM users
+//! ```
+//!
+//! Parameters of types in the table above are checked against the Rust types of the expressions passed for them, which
+//! may also be references or `Option`s of those types. Parameters of other types are checked when the query is run.
+//! The expressions are moved into the returned `Query`, so pass a reference to keep using a value afterwards.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! # async fn async_main(client: &tokio_postgres::Client) -> Result<(), tokio_postgres::Error> {
+//! let id = 1i32;
+//! let user = tokio_postgres::query!("SELECT id, name FROM users WHERE id = $1", id)
+//!     .fetch_one(client)
+//!     .await?;
+//! println!("{}: {}", user.id, user.name);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::types::ToSql;
+use crate::{Error, GenericClient, Row};
+use std::marker::PhantomData;
+
+/// Creates a [`Query`] checked against the database at compile time.
+///
+/// Takes a SQL string literal followed by an expression for each of its parameters. See the [module
+/// documentation](crate::macros) for details.
+#[macro_export]
+macro_rules! query {
+    ($($args:tt)*) => {
+        ::postgres_macros::query!($crate, $($args)*)
+    };
+}
+
+/// A statement checked at compile time, along with its parameters.
+///
+/// Created by the [`query!`] macro.
+pub struct Query<'a, R> {
+    sql: &'static str,
+    params: Vec<Box<dyn ToSql + Sync + 'a>>,
+    map: fn(&Row) -> Result<R, Error>,
+}
+
+impl<'a, R> Query<'a, R> {
+    #[doc(hidden)]
+    pub fn new(
+        sql: &'static str,
+        params: Vec<Box<dyn ToSql + Sync + 'a>>,
+        map: fn(&Row) -> Result<R, Error>,
+    ) -> Query<'a, R> {
+        Query { sql, params, map }
+    }
+
+    /// Returns the SQL of the statement.
+    pub fn sql(&self) -> &'static str {
+        self.sql
+    }
+
+    /// Returns the parameters of the statement.
+    pub fn params(&self) -> &[Box<dyn ToSql + Sync + 'a>] {
+        &self.params
+    }
+
+    #[doc(hidden)]
+    pub fn param_refs(&self) -> Vec<&(dyn ToSql + Sync)> {
+        self.params.iter().map(|p| &**p as _).collect()
+    }
+
+    /// Deserializes a row returned by the statement.
+    pub fn map_row(&self, row: &Row) -> Result<R, Error> {
+        (self.map)(row)
+    }
+
+    /// Executes the statement, returning the resulting records.
+    pub async fn fetch_all<C>(&self, client: &C) -> Result<Vec<R>, Error>
+    where
+        C: GenericClient,
+    {
+        client
+            .query(self.sql, &self.param_refs())
+            .await?
+            .iter()
+            .map(self.map)
+            .collect()
+    }
+
+    /// Executes the statement, returning the single resulting record.
+    ///
+    /// Returns an error if the statement does not return exactly one row.
+    pub async fn fetch_one<C>(&self, client: &C) -> Result<R, Error>
+    where
+        C: GenericClient,
+    {
+        let row = client.query_one(self.sql, &self.param_refs()).await?;
+        (self.map)(&row)
+    }
+
+    /// Executes the statement, returning zero or one resulting records.
+    ///
+    /// Returns an error if the statement returns more than one row.
+    pub async fn fetch_optional<C>(&self, client: &C) -> Result<Option<R>, Error>
+    where
+        C: GenericClient,
+    {
+        match client.query_opt(self.sql, &self.param_refs()).await? {
+            Some(row) => (self.map)(&row).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Executes the statement, returning the number of rows modified.
+    pub async fn execute<C>(&self, client: &C) -> Result<u64, Error>
+    where
+        C: GenericClient,
+    {
+        client.execute(self.sql, &self.param_refs()).await
+    }
+}
+
+/// A marker for array parameters with elements of type `T`.
+#[doc(hidden)]
+pub struct Array<T: ?Sized>(PhantomData<T>);
+
+/// Implemented for Rust types which can be passed for a parameter whose type is represented by `T`.
+#[doc(hidden)]
+pub trait ParamType<T: ?Sized> {}
+
+macro_rules! param_types {
+    ($($t:ty),*) => {
+        $(impl ParamType<$t> for $t {})*
+    };
+}
+
+param_types!(bool, i8, i16, i32, i64, u32, f32, f64, str, [u8]);
+
+impl ParamType<str> for String {}
+impl ParamType<[u8]> for Vec<u8> {}
+impl<T: ?Sized, U: ?Sized + ParamType<T>> ParamType<T> for &U {}
+impl<T: ?Sized, U: ParamType<T>> ParamType<T> for Option<U> {}
+impl<T: ?Sized, U: ParamType<T>> ParamType<Array<T>> for [U] {}
+impl<T: ?Sized, U: ParamType<T>> ParamType<Array<T>> for Vec<U> {}
+
+#[doc(hidden)]
+pub fn check_param<T: ?Sized, U: ?Sized + ParamType<T>>(_: &U) {}
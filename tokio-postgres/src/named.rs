@@ -0,0 +1,187 @@
+use crate::types::ToSql;
+use crate::Error;
+
+/// A query using named placeholders, rewritten into the positional form understood by the server.
+///
+/// Placeholders are written as `:name`, where the name is an ASCII identifier. Each distinct name is assigned a
+/// positional parameter in order of first appearance, so a name may be referenced more than once. String literals,
+/// quoted identifiers, dollar-quoted strings, comments and `::` casts are left untouched.
+///
+/// Named and positional (`$1`) placeholders should not be mixed in the same query. Array slices must be written with
+/// spaces around the colon (`arr[a : b]`), or they will be parsed as placeholders.
+///
+/// # Examples
+///
+/// ```
+/// use tokio_postgres::NamedStatement;
+///
+/// let statement = NamedStatement::new("SELECT * FROM users WHERE id = :id OR parent = :id AND name = :name::TEXT");
+/// assert_eq!(
+///     statement.query(),
+///     "SELECT * FROM users WHERE id = $1 OR parent = $1 AND name = $2::TEXT",
+/// );
+/// assert_eq!(statement.names(), ["id", "name"]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamedStatement {
+    query: String,
+    names: Vec<String>,
+}
+
+impl NamedStatement {
+    /// Rewrites a query with named placeholders.
+    pub fn new(query: &str) -> NamedStatement {
+        let bytes = query.as_bytes();
+        let mut out = String::with_capacity(query.len());
+        let mut names: Vec<String> = vec![];
+        // the start of the input not yet copied to the output
+        let mut copied = 0;
+        let mut i = 0;
+
+        while i < bytes.len() {
+            match bytes[i] {
+                b'\'' => {
+                    let escapes = i > 0
+                        && matches!(bytes[i - 1], b'E' | b'e')
+                        && (i < 2 || !is_ident_byte(bytes[i - 2]));
+                    i = skip_quoted(bytes, i, b'\'', escapes);
+                }
+                b'"' => i = skip_quoted(bytes, i, b'"', false),
+                b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                    i = bytes[i..]
+                        .iter()
+                        .position(|&b| b == b'\n')
+                        .map_or(bytes.len(), |p| i + p + 1);
+                }
+                b'/' if bytes.get(i + 1) == Some(&b'*') => i = skip_block_comment(bytes, i),
+                b'$' => i = skip_dollar_quoted(bytes, i),
+                b':' if bytes.get(i + 1) == Some(&b':') => i += 2,
+                b':' if matches!(bytes.get(i + 1), Some(&b) if is_ident_start(b)) => {
+                    let start = i + 1;
+                    let end = bytes[start..]
+                        .iter()
+                        .position(|&b| !is_ident_byte(b))
+                        .map_or(bytes.len(), |p| start + p);
+                    let name = &query[start..end];
+                    let idx = match names.iter().position(|n| n == name) {
+                        Some(idx) => idx,
+                        None => {
+                            names.push(name.to_string());
+                            names.len() - 1
+                        }
+                    };
+
+                    out.push_str(&query[copied..i]);
+                    out.push('$');
+                    out.push_str(&(idx + 1).to_string());
+                    copied = end;
+                    i = end;
+                }
+                _ => i += 1,
+            }
+        }
+        out.push_str(&query[copied..]);
+
+        NamedStatement { query: out, names }
+    }
+
+    /// Returns the rewritten query, using positional placeholders.
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Returns the names of the placeholders, in positional order.
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Orders named parameter values by the positions of their placeholders.
+    ///
+    /// Values whose names do not appear in the query are ignored. Returns an error if a placeholder has no value.
+    pub fn bind<'a>(
+        &self,
+        params: &[(&str, &'a (dyn ToSql + Sync))],
+    ) -> Result<Vec<&'a (dyn ToSql + Sync)>, Error> {
+        self.names
+            .iter()
+            .map(|name| {
+                params
+                    .iter()
+                    .find(|(n, _)| n == name)
+                    .map(|(_, value)| *value)
+                    .ok_or_else(|| Error::parameter(name.clone()))
+            })
+            .collect()
+    }
+}
+
+fn is_ident_start(b: u8) -> bool {
+    b.is_ascii_alphabetic() || b == b'_'
+}
+
+fn is_ident_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_'
+}
+
+// returns the index after the closing quote, treating a doubled quote as an escaped quote
+fn skip_quoted(bytes: &[u8], start: usize, quote: u8, backslash_escapes: bool) -> usize {
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' if backslash_escapes => i += 2,
+            b if b == quote => {
+                if bytes.get(i + 1) == Some(&quote) {
+                    i += 2;
+                } else {
+                    return i + 1;
+                }
+            }
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+// block comments nest in Postgres
+fn skip_block_comment(bytes: &[u8], start: usize) -> usize {
+    let mut depth = 0;
+    let mut i = start;
+    while i < bytes.len() {
+        if bytes[i..].starts_with(b"/*") {
+            depth += 1;
+            i += 2;
+        } else if bytes[i..].starts_with(b"*/") {
+            depth -= 1;
+            i += 2;
+            if depth == 0 {
+                return i;
+            }
+        } else {
+            i += 1;
+        }
+    }
+    bytes.len()
+}
+
+fn skip_dollar_quoted(bytes: &[u8], start: usize) -> usize {
+    // a dollar sign within an identifier or following a digit is not the start of a quote
+    if start > 0 && is_ident_byte(bytes[start - 1]) {
+        return start + 1;
+    }
+
+    let tag_end = match bytes[start + 1..].iter().position(|&b| !is_ident_byte(b)) {
+        Some(p) => start + 1 + p,
+        None => return bytes.len(),
+    };
+    if bytes[tag_end] != b'$' || (tag_end > start + 1 && !is_ident_start(bytes[start + 1])) {
+        // a positional parameter such as `$1`, or a lone dollar sign
+        return start + 1;
+    }
+
+    let tag = &bytes[start..=tag_end];
+    let body = tag_end + 1;
+    bytes[body..]
+        .windows(tag.len())
+        .position(|w| w == tag)
+        .map_or(bytes.len(), |p| body + p + tag.len())
+}
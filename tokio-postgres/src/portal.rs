@@ -1,6 +1,7 @@
 use crate::client::InnerClient;
 use crate::codec::FrontendMessage;
 use crate::connection::RequestMessages;
+use crate::types::Format;
 use crate::Statement;
 use postgres_protocol::message::frontend;
 use std::sync::{Arc, Weak};
@@ -9,6 +10,7 @@ struct Inner {
     client: Weak<InnerClient>,
     name: String,
     statement: Statement,
+    formats: Option<Arc<[Format]>>,
 }
 
 impl Drop for Inner {
@@ -32,11 +34,17 @@ impl Drop for Inner {
 pub struct Portal(Arc<Inner>);
 
 impl Portal {
-    pub(crate) fn new(client: &Arc<InnerClient>, name: String, statement: Statement) -> Portal {
+    pub(crate) fn new(
+        client: &Arc<InnerClient>,
+        name: String,
+        statement: Statement,
+        formats: Option<Arc<[Format]>>,
+    ) -> Portal {
         Portal(Arc::new(Inner {
             client: Arc::downgrade(client),
             name,
             statement,
+            formats,
         }))
     }
 
@@ -47,4 +55,8 @@ impl Portal {
     pub(crate) fn statement(&self) -> &Statement {
         &self.0.statement
     }
+
+    pub(crate) fn formats(&self) -> Option<&Arc<[Format]>> {
+        self.0.formats.as_ref()
+    }
 }
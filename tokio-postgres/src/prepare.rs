@@ -4,56 +4,90 @@ use crate::connection::RequestMessages;
 use crate::error::SqlState;
 use crate::types::{Field, Kind, Oid, Type};
 use crate::{query, slice_iter};
-use crate::{Column, Error, Statement};
+use crate::{Column, Error, Row, Statement};
 use bytes::Bytes;
 use fallible_iterator::FallibleIterator;
-use futures_util::{pin_mut, TryStreamExt};
+use futures_util::TryStreamExt;
 use log::debug;
 use postgres_protocol::message::backend::Message;
 use postgres_protocol::message::frontend;
+use std::collections::HashMap;
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 
+// Fetches the given types along with every type they depend on: array elements, domain base types, range subtypes and
+// composite fields.
 const TYPEINFO_QUERY: &str = "\
-SELECT t.typname, t.typtype, t.typelem, r.rngsubtype, t.typbasetype, n.nspname, t.typrelid
-FROM pg_catalog.pg_type t
+WITH RECURSIVE types(oid) AS (
+    SELECT unnest($1::OID[])
+  UNION
+    SELECT unnest(ARRAY[t.typelem, t.typbasetype, r.rngsubtype, a.atttypid])
+    FROM types
+    INNER JOIN pg_catalog.pg_type t ON t.oid = types.oid
+    LEFT OUTER JOIN pg_catalog.pg_range r ON r.rngtypid = t.oid
+    LEFT OUTER JOIN pg_catalog.pg_attribute a
+        ON a.attrelid = t.typrelid AND a.attnum > 0 AND NOT a.attisdropped
+)
+SELECT t.oid, t.typname, t.typtype, t.typelem, r.rngsubtype, t.typbasetype, n.nspname, t.typrelid,
+    ARRAY(
+        SELECT e.enumlabel
+        FROM pg_catalog.pg_enum e
+        WHERE e.enumtypid = t.oid
+        ORDER BY e.enumsortorder
+    ),
+    ARRAY(
+        SELECT a.attname
+        FROM pg_catalog.pg_attribute a
+        WHERE a.attrelid = t.typrelid AND a.attnum > 0 AND NOT a.attisdropped
+        ORDER BY a.attnum
+    ),
+    ARRAY(
+        SELECT a.atttypid
+        FROM pg_catalog.pg_attribute a
+        WHERE a.attrelid = t.typrelid AND a.attnum > 0 AND NOT a.attisdropped
+        ORDER BY a.attnum
+    )
+FROM types
+INNER JOIN pg_catalog.pg_type t ON t.oid = types.oid
 LEFT OUTER JOIN pg_catalog.pg_range r ON r.rngtypid = t.oid
 INNER JOIN pg_catalog.pg_namespace n ON t.typnamespace = n.oid
-WHERE t.oid = $1
 ";
 
-// Range types weren't added until Postgres 9.2, so pg_range may not exist
+// Range types weren't added until Postgres 9.2, so pg_range may not exist, and Postgres 9.0 didn't have enumsortorder
 const TYPEINFO_FALLBACK_QUERY: &str = "\
-SELECT t.typname, t.typtype, t.typelem, NULL::OID, t.typbasetype, n.nspname, t.typrelid
-FROM pg_catalog.pg_type t
+WITH RECURSIVE types(oid) AS (
+    SELECT unnest($1::OID[])
+  UNION
+    SELECT unnest(ARRAY[t.typelem, t.typbasetype, a.atttypid])
+    FROM types
+    INNER JOIN pg_catalog.pg_type t ON t.oid = types.oid
+    LEFT OUTER JOIN pg_catalog.pg_attribute a
+        ON a.attrelid = t.typrelid AND a.attnum > 0 AND NOT a.attisdropped
+)
+SELECT t.oid, t.typname, t.typtype, t.typelem, NULL::OID, t.typbasetype, n.nspname, t.typrelid,
+    ARRAY(
+        SELECT e.enumlabel
+        FROM pg_catalog.pg_enum e
+        WHERE e.enumtypid = t.oid
+        ORDER BY e.oid
+    ),
+    ARRAY(
+        SELECT a.attname
+        FROM pg_catalog.pg_attribute a
+        WHERE a.attrelid = t.typrelid AND a.attnum > 0 AND NOT a.attisdropped
+        ORDER BY a.attnum
+    ),
+    ARRAY(
+        SELECT a.atttypid
+        FROM pg_catalog.pg_attribute a
+        WHERE a.attrelid = t.typrelid AND a.attnum > 0 AND NOT a.attisdropped
+        ORDER BY a.attnum
+    )
+FROM types
+INNER JOIN pg_catalog.pg_type t ON t.oid = types.oid
 INNER JOIN pg_catalog.pg_namespace n ON t.typnamespace = n.oid
-WHERE t.oid = $1
-";
-
-const TYPEINFO_ENUM_QUERY: &str = "\
-SELECT enumlabel
-FROM pg_catalog.pg_enum
-WHERE enumtypid = $1
-ORDER BY enumsortorder
-";
-
-// Postgres 9.0 didn't have enumsortorder
-const TYPEINFO_ENUM_FALLBACK_QUERY: &str = "\
-SELECT enumlabel
-FROM pg_catalog.pg_enum
-WHERE enumtypid = $1
-ORDER BY oid
-";
-
-const TYPEINFO_COMPOSITE_QUERY: &str = "\
-SELECT attname, atttypid
-FROM pg_catalog.pg_attribute
-WHERE attrelid = $1
-AND NOT attisdropped
-AND attnum > 0
-ORDER BY attnum
 ";
 
 static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
@@ -83,23 +117,32 @@ pub async fn prepare(
         _ => return Err(Error::unexpected_message()),
     };
 
-    let mut parameters = vec![];
-    let mut it = parameter_description.parameters();
-    while let Some(oid) = it.next().map_err(Error::parse)? {
-        let type_ = get_type(client, oid).await?;
-        parameters.push(type_);
-    }
+    let mut oids = parameter_description
+        .parameters()
+        .collect::<Vec<_>>()
+        .map_err(Error::parse)?;
+    let params_len = oids.len();
 
-    let mut columns = vec![];
-    if let Some(row_description) = row_description {
+    let mut fields = vec![];
+    if let Some(row_description) = &row_description {
         let mut it = row_description.fields();
         while let Some(field) = it.next().map_err(Error::parse)? {
-            let type_ = get_type(client, field.type_oid()).await?;
-            let column = Column::new(field.name().to_string(), type_);
-            columns.push(column);
+            oids.push(field.type_oid());
+            fields.push(field);
         }
     }
 
+    // resolve the types of the parameters and columns together to load any unknown ones in one round trip
+    let mut types = get_types(client, &oids).await?;
+    let column_types = types.split_off(params_len);
+    let parameters = types;
+
+    let columns = fields
+        .iter()
+        .zip(column_types)
+        .map(|(field, type_)| Column::new(field.name().to_string(), type_, field))
+        .collect();
+
     Ok(Statement::new(client, name, parameters, columns))
 }
 
@@ -126,7 +169,34 @@ fn encode(client: &InnerClient, name: &str, query: &str, types: &[Type]) -> Resu
     })
 }
 
-async fn get_type(client: &Arc<InnerClient>, oid: Oid) -> Result<Type, Error> {
+/// Looks up the types with the specified OIDs, loading any which aren't known to the client in a single query.
+pub(crate) async fn get_types(client: &Arc<InnerClient>, oids: &[Oid]) -> Result<Vec<Type>, Error> {
+    let unknown = oids
+        .iter()
+        .copied()
+        .filter(|oid| Type::from_oid(*oid).is_none() && client.type_(*oid).is_none())
+        .collect::<Vec<_>>();
+
+    let mut info = HashMap::new();
+    if !unknown.is_empty() {
+        let stmt = typeinfo_statement(client).await?;
+
+        let rows = query::query(client, stmt, slice_iter(&[&unknown]))
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+        for row in rows {
+            let oid = row.try_get(0)?;
+            info.insert(oid, row);
+        }
+    }
+
+    oids.iter()
+        .map(|oid| build_type(client, &info, *oid))
+        .collect()
+}
+
+fn build_type(client: &InnerClient, info: &HashMap<Oid, Row>, oid: Oid) -> Result<Type, Error> {
     if let Some(type_) = Type::from_oid(oid) {
         return Ok(type_);
     }
@@ -135,58 +205,52 @@ async fn get_type(client: &Arc<InnerClient>, oid: Oid) -> Result<Type, Error> {
         return Ok(type_);
     }
 
-    let stmt = typeinfo_statement(client).await?;
-
-    let rows = query::query(client, stmt, slice_iter(&[&oid])).await?;
-    pin_mut!(rows);
-
-    let row = match rows.try_next().await? {
+    let row = match info.get(&oid) {
         Some(row) => row,
         None => return Err(Error::unexpected_message()),
     };
 
-    let name: String = row.try_get(0)?;
-    let type_: i8 = row.try_get(1)?;
-    let elem_oid: Oid = row.try_get(2)?;
-    let rngsubtype: Option<Oid> = row.try_get(3)?;
-    let basetype: Oid = row.try_get(4)?;
-    let schema: String = row.try_get(5)?;
-    let relid: Oid = row.try_get(6)?;
+    let name: String = row.try_get(1)?;
+    let type_: i8 = row.try_get(2)?;
+    let elem_oid: Oid = row.try_get(3)?;
+    let rngsubtype: Option<Oid> = row.try_get(4)?;
+    let basetype: Oid = row.try_get(5)?;
+    let schema: String = row.try_get(6)?;
+    let relid: Oid = row.try_get(7)?;
 
     let kind = if type_ == b'e' as i8 {
-        let variants = get_enum_variants(client, oid).await?;
+        let variants = row.try_get(8)?;
         Kind::Enum(variants)
     } else if type_ == b'p' as i8 {
         Kind::Pseudo
     } else if basetype != 0 {
-        let type_ = get_type_rec(client, basetype).await?;
+        let type_ = build_type(client, info, basetype)?;
         Kind::Domain(type_)
     } else if elem_oid != 0 {
-        let type_ = get_type_rec(client, elem_oid).await?;
+        let type_ = build_type(client, info, elem_oid)?;
         Kind::Array(type_)
     } else if relid != 0 {
-        let fields = get_composite_fields(client, relid).await?;
+        let names: Vec<String> = row.try_get(9)?;
+        let oids: Vec<Oid> = row.try_get(10)?;
+        let fields = names
+            .into_iter()
+            .zip(oids)
+            .map(|(name, oid)| Ok(Field::new(name, build_type(client, info, oid)?)))
+            .collect::<Result<_, Error>>()?;
         Kind::Composite(fields)
     } else if let Some(rngsubtype) = rngsubtype {
-        let type_ = get_type_rec(client, rngsubtype).await?;
+        let type_ = build_type(client, info, rngsubtype)?;
         Kind::Range(type_)
     } else {
         Kind::Simple
     };
 
     let type_ = Type::new(name, oid, kind, schema);
-    client.set_type(oid, &type_);
+    client.set_type(&type_);
 
     Ok(type_)
 }
 
-fn get_type_rec<'a>(
-    client: &'a Arc<InnerClient>,
-    oid: Oid,
-) -> Pin<Box<dyn Future<Output = Result<Type, Error>> + Send + 'a>> {
-    Box::pin(get_type(client, oid))
-}
-
 async fn typeinfo_statement(client: &Arc<InnerClient>) -> Result<Statement, Error> {
     if let Some(stmt) = client.typeinfo() {
         return Ok(stmt);
@@ -194,7 +258,10 @@ async fn typeinfo_statement(client: &Arc<InnerClient>) -> Result<Statement, Erro
 
     let stmt = match prepare_rec(client, TYPEINFO_QUERY, &[]).await {
         Ok(stmt) => stmt,
-        Err(ref e) if e.code() == Some(&SqlState::UNDEFINED_TABLE) => {
+        Err(ref e)
+            if e.code() == Some(&SqlState::UNDEFINED_TABLE)
+                || e.code() == Some(&SqlState::UNDEFINED_COLUMN) =>
+        {
             prepare_rec(client, TYPEINFO_FALLBACK_QUERY, &[]).await?
         }
         Err(e) => return Err(e),
@@ -203,60 +270,3 @@ async fn typeinfo_statement(client: &Arc<InnerClient>) -> Result<Statement, Erro
     client.set_typeinfo(&stmt);
     Ok(stmt)
 }
-
-async fn get_enum_variants(client: &Arc<InnerClient>, oid: Oid) -> Result<Vec<String>, Error> {
-    let stmt = typeinfo_enum_statement(client).await?;
-
-    query::query(client, stmt, slice_iter(&[&oid]))
-        .await?
-        .and_then(|row| async move { row.try_get(0) })
-        .try_collect()
-        .await
-}
-
-async fn typeinfo_enum_statement(client: &Arc<InnerClient>) -> Result<Statement, Error> {
-    if let Some(stmt) = client.typeinfo_enum() {
-        return Ok(stmt);
-    }
-
-    let stmt = match prepare_rec(client, TYPEINFO_ENUM_QUERY, &[]).await {
-        Ok(stmt) => stmt,
-        Err(ref e) if e.code() == Some(&SqlState::UNDEFINED_COLUMN) => {
-            prepare_rec(client, TYPEINFO_ENUM_FALLBACK_QUERY, &[]).await?
-        }
-        Err(e) => return Err(e),
-    };
-
-    client.set_typeinfo_enum(&stmt);
-    Ok(stmt)
-}
-
-async fn get_composite_fields(client: &Arc<InnerClient>, oid: Oid) -> Result<Vec<Field>, Error> {
-    let stmt = typeinfo_composite_statement(client).await?;
-
-    let rows = query::query(client, stmt, slice_iter(&[&oid]))
-        .await?
-        .try_collect::<Vec<_>>()
-        .await?;
-
-    let mut fields = vec![];
-    for row in rows {
-        let name = row.try_get(0)?;
-        let oid = row.try_get(1)?;
-        let type_ = get_type_rec(client, oid).await?;
-        fields.push(Field::new(name, type_));
-    }
-
-    Ok(fields)
-}
-
-async fn typeinfo_composite_statement(client: &Arc<InnerClient>) -> Result<Statement, Error> {
-    if let Some(stmt) = client.typeinfo_composite() {
-        return Ok(stmt);
-    }
-
-    let stmt = prepare_rec(client, TYPEINFO_COMPOSITE_QUERY, &[]).await?;
-
-    client.set_typeinfo_composite(&stmt);
-    Ok(stmt)
-}
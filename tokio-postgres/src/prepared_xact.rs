@@ -0,0 +1,89 @@
+use crate::{Client, Error, Row};
+use postgres_protocol::escape::escape_literal;
+use std::time::SystemTime;
+
+const SELECT: &str = "SELECT transaction::text::int8, gid, prepared, owner, database \
+                      FROM pg_catalog.pg_prepared_xacts";
+
+/// A transaction prepared for two-phase commit.
+///
+/// Returned by the `Transaction::prepare_transaction` and `Client::list_prepared` methods. The transaction can be
+/// completed by passing its global identifier to `Client::commit_prepared` or `Client::rollback_prepared`, from any
+/// connection to the same database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreparedXact {
+    transaction: u32,
+    gid: String,
+    prepared: SystemTime,
+    owner: String,
+    database: String,
+}
+
+impl PreparedXact {
+    fn from_row(row: &Row) -> Result<PreparedXact, Error> {
+        Ok(PreparedXact {
+            transaction: row.try_get::<_, i64>(0)? as u32,
+            gid: row.try_get(1)?,
+            prepared: row.try_get(2)?,
+            owner: row.try_get(3)?,
+            database: row.try_get(4)?,
+        })
+    }
+
+    /// Returns the ID of the transaction.
+    pub fn transaction(&self) -> u32 {
+        self.transaction
+    }
+
+    /// Returns the global identifier the transaction was prepared with.
+    pub fn gid(&self) -> &str {
+        &self.gid
+    }
+
+    /// Returns the time at which the transaction was prepared.
+    pub fn prepared(&self) -> SystemTime {
+        self.prepared
+    }
+
+    /// Returns the name of the user that prepared the transaction.
+    pub fn owner(&self) -> &str {
+        &self.owner
+    }
+
+    /// Returns the name of the database the transaction was prepared in.
+    pub fn database(&self) -> &str {
+        &self.database
+    }
+}
+
+pub(crate) fn prepare_query(gid: &str) -> String {
+    format!("PREPARE TRANSACTION {}", escape_literal(gid))
+}
+
+pub(crate) async fn get(client: &Client, gid: &str) -> Result<PreparedXact, Error> {
+    let row = client
+        .query_one(&*format!("{} WHERE gid = $1", SELECT), &[&gid])
+        .await?;
+    PreparedXact::from_row(&row)
+}
+
+pub(crate) async fn list(client: &Client) -> Result<Vec<PreparedXact>, Error> {
+    client
+        .query(&*format!("{} ORDER BY prepared", SELECT), &[])
+        .await?
+        .iter()
+        .map(PreparedXact::from_row)
+        .collect()
+}
+
+pub(crate) async fn commit(client: &Client, gid: &str) -> Result<(), Error> {
+    client
+        .batch_execute(&format!("COMMIT PREPARED {}", escape_literal(gid)))
+        .await
+}
+
+pub(crate) async fn rollback(client: &Client, gid: &str) -> Result<(), Error> {
+    client
+        .batch_execute(&format!("ROLLBACK PREPARED {}", escape_literal(gid)))
+        .await
+}
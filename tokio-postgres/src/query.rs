@@ -1,8 +1,8 @@
 use crate::client::{InnerClient, Responses};
 use crate::codec::FrontendMessage;
 use crate::connection::RequestMessages;
-use crate::types::{BorrowToSql, IsNull};
-use crate::{Error, Portal, Row, Statement};
+use crate::types::{BorrowToSql, Format, IsNull};
+use crate::{Column, Error, Portal, Row, Statement};
 use bytes::{Bytes, BytesMut};
 use futures_util::{ready, Stream};
 use log::{debug, log_enabled, Level};
@@ -12,6 +12,7 @@ use postgres_protocol::message::frontend;
 use std::fmt;
 use std::marker::PhantomPinned;
 use std::pin::Pin;
+use std::sync::Arc;
 use std::task::{Context, Poll};
 
 struct BorrowToSqlParamsDebug<'a, T>(&'a [T]);
@@ -32,6 +33,20 @@ pub async fn query<P, I>(
     statement: Statement,
     params: I,
 ) -> Result<RowStream, Error>
+where
+    P: BorrowToSql,
+    I: IntoIterator<Item = P>,
+    I::IntoIter: ExactSizeIterator,
+{
+    query_with_formats(client, statement, params, None).await
+}
+
+pub async fn query_with_formats<P, I>(
+    client: &InnerClient,
+    statement: Statement,
+    params: I,
+    formats: Option<Arc<[Format]>>,
+) -> Result<RowStream, Error>
 where
     P: BorrowToSql,
     I: IntoIterator<Item = P>,
@@ -44,19 +59,28 @@ where
             statement.name(),
             BorrowToSqlParamsDebug(params.as_slice()),
         );
-        encode(client, &statement, params)?
+        encode_with_formats(client, &statement, params, formats.as_deref())?
     } else {
-        encode(client, &statement, params)?
+        encode_with_formats(client, &statement, params, formats.as_deref())?
     };
     let responses = start(client, buf).await?;
     Ok(RowStream {
         statement,
+        formats,
         responses,
         rows_affected: None,
         _p: PhantomPinned,
     })
 }
 
+/// Determines the result format of each column of a statement.
+pub fn result_formats<F>(statement: &Statement, formats: F) -> Arc<[Format]>
+where
+    F: FnMut(&Column) -> Format,
+{
+    statement.columns().iter().map(formats).collect()
+}
+
 pub async fn query_portal(
     client: &InnerClient,
     portal: &Portal,
@@ -72,6 +96,7 @@ pub async fn query_portal(
 
     Ok(RowStream {
         statement: portal.statement().clone(),
+        formats: portal.formats().cloned(),
         responses,
         rows_affected: None,
         _p: PhantomPinned,
@@ -140,13 +165,27 @@ async fn start(client: &InnerClient, buf: Bytes) -> Result<Responses, Error> {
 }
 
 pub fn encode<P, I>(client: &InnerClient, statement: &Statement, params: I) -> Result<Bytes, Error>
+where
+    P: BorrowToSql,
+    I: IntoIterator<Item = P>,
+    I::IntoIter: ExactSizeIterator,
+{
+    encode_with_formats(client, statement, params, None)
+}
+
+fn encode_with_formats<P, I>(
+    client: &InnerClient,
+    statement: &Statement,
+    params: I,
+    formats: Option<&[Format]>,
+) -> Result<Bytes, Error>
 where
     P: BorrowToSql,
     I: IntoIterator<Item = P>,
     I::IntoIter: ExactSizeIterator,
 {
     client.with_buf(|buf| {
-        encode_bind(statement, params, "", buf)?;
+        encode_bind(statement, params, formats, "", buf)?;
         frontend::execute("", 0, buf).map_err(Error::encode)?;
         frontend::sync(buf);
         Ok(buf.split().freeze())
@@ -156,6 +195,7 @@ where
 pub fn encode_bind<P, I>(
     statement: &Statement,
     params: I,
+    formats: Option<&[Format]>,
     portal: &str,
     buf: &mut BytesMut,
 ) -> Result<(), Error>
@@ -178,6 +218,12 @@ where
 
     let params = params.into_iter();
 
+    // a single format code applies to every column
+    let result_formats = formats
+        .unwrap_or(&[Format::Binary])
+        .iter()
+        .map(|f| *f as i16);
+
     let mut error_idx = 0;
     let r = frontend::bind(
         portal,
@@ -192,7 +238,7 @@ where
                 Err(e)
             }
         },
-        Some(1),
+        result_formats,
         buf,
     );
     match r {
@@ -206,6 +252,7 @@ pin_project! {
     /// A stream of table rows.
     pub struct RowStream {
         statement: Statement,
+        formats: Option<Arc<[Format]>>,
         responses: Responses,
         rows_affected: Option<u64>,
         #[pin]
@@ -221,7 +268,11 @@ impl Stream for RowStream {
         loop {
             match ready!(this.responses.poll_next(cx)?) {
                 Message::DataRow(body) => {
-                    return Poll::Ready(Some(Ok(Row::new(this.statement.clone(), body)?)))
+                    return Poll::Ready(Some(Ok(Row::new(
+                        this.statement.clone(),
+                        this.formats.clone(),
+                        body,
+                    )?)))
                 }
                 Message::CommandComplete(body) => {
                     *this.rows_affected = Some(extract_row_affected(&body)?);
@@ -241,4 +292,9 @@ impl RowStream {
     pub fn rows_affected(&self) -> Option<u64> {
         self.rows_affected
     }
+
+    #[cfg(feature = "arrow")]
+    pub(crate) fn statement(&self) -> &Statement {
+        &self.statement
+    }
 }
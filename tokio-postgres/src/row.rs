@@ -3,10 +3,11 @@
 use crate::row::sealed::{AsName, Sealed};
 use crate::simple_query::SimpleColumn;
 use crate::statement::Column;
-use crate::types::{FromSql, Type, WrongType};
+use crate::types::{Format, FromSql, FromSqlText, Type, WrongType};
 use crate::{Error, Statement};
 use fallible_iterator::FallibleIterator;
 use postgres_protocol::message::backend::DataRowBody;
+use std::borrow::Cow;
 use std::fmt;
 use std::ops::Range;
 use std::str;
@@ -79,9 +80,9 @@ impl RowIndex for str {
     }
 }
 
-impl<'a, T> Sealed for &'a T where T: ?Sized + Sealed {}
+impl<T> Sealed for &T where T: ?Sized + Sealed {}
 
-impl<'a, T> RowIndex for &'a T
+impl<T> RowIndex for &T
 where
     T: ?Sized + RowIndex,
 {
@@ -97,6 +98,7 @@ where
 /// A row of data returned from the database by a query.
 pub struct Row {
     statement: Statement,
+    formats: Option<Arc<[Format]>>,
     body: DataRowBody,
     ranges: Vec<Option<Range<usize>>>,
 }
@@ -110,10 +112,15 @@ impl fmt::Debug for Row {
 }
 
 impl Row {
-    pub(crate) fn new(statement: Statement, body: DataRowBody) -> Result<Row, Error> {
+    pub(crate) fn new(
+        statement: Statement,
+        formats: Option<Arc<[Format]>>,
+        body: DataRowBody,
+    ) -> Result<Row, Error> {
         let ranges = body.ranges().collect().map_err(Error::parse)?;
         Ok(Row {
             statement,
+            formats,
             body,
             ranges,
         })
@@ -136,7 +143,8 @@ impl Row {
 
     /// Deserializes a value from the row.
     ///
-    /// The value can be specified either by its numeric index in the row, or by its column name.
+    /// The value can be specified either by its numeric index in the row, or by its column name. Values requested
+    /// in `Format::Text` are decoded with `FromSql::from_text`.
     ///
     /// # Panics
     ///
@@ -166,10 +174,7 @@ impl Row {
         I: RowIndex + fmt::Display,
         T: FromSql<'a>,
     {
-        let idx = match idx.__idx(self.columns()) {
-            Some(idx) => idx,
-            None => return Err(Error::column(idx.to_string())),
-        };
+        let idx = self.checked_idx(idx)?;
 
         let ty = self.columns()[idx].type_();
         if !T::accepts(ty) {
@@ -179,14 +184,140 @@ impl Row {
             ));
         }
 
-        FromSql::from_sql_nullable(ty, self.col_buffer(idx)).map_err(|e| Error::from_sql(e, idx))
+        match self.format(idx) {
+            Some(Format::Text) => {
+                let raw = self.col_text(idx)?.map(Cow::Borrowed);
+                T::from_text_nullable(ty, raw)
+            }
+            _ => T::from_sql_nullable(ty, self.col_buffer(idx)),
+        }
+        .map_err(|e| Error::from_sql(e, idx))
+    }
+
+    /// Deserializes a value requested in `Format::Text` from the row.
+    ///
+    /// The value can be specified either by its numeric index in the row, or by its column name. Unlike `Row::get`,
+    /// this accepts types which only implement `FromSqlText`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds or if the value cannot be converted to the specified type.
+    pub fn get_text<'a, I, T>(&'a self, idx: I) -> T
+    where
+        I: RowIndex + fmt::Display,
+        T: FromSqlText<'a>,
+    {
+        match self.get_text_inner(&idx) {
+            Ok(ok) => ok,
+            Err(err) => panic!("error retrieving column {}: {}", idx, err),
+        }
+    }
+
+    /// Like `Row::get_text`, but returns a `Result` rather than panicking.
+    pub fn try_get_text<'a, I, T>(&'a self, idx: I) -> Result<T, Error>
+    where
+        I: RowIndex + fmt::Display,
+        T: FromSqlText<'a>,
+    {
+        self.get_text_inner(&idx)
+    }
+
+    fn get_text_inner<'a, I, T>(&'a self, idx: &I) -> Result<T, Error>
+    where
+        I: RowIndex + fmt::Display,
+        T: FromSqlText<'a>,
+    {
+        let idx = self.checked_idx(idx)?;
+        if self.format(idx) != Some(Format::Text) {
+            return Err(Error::from_sql(
+                "value was returned in binary format".into(),
+                idx,
+            ));
+        }
+
+        let ty = self.columns()[idx].type_();
+        if !T::accepts(ty) {
+            return Err(Error::from_sql(
+                Box::new(WrongType::new::<T>(ty.clone())),
+                idx,
+            ));
+        }
+
+        T::from_sql_text_nullable(ty, self.col_text(idx)?).map_err(|e| Error::from_sql(e, idx))
+    }
+
+    fn checked_idx<I>(&self, idx: &I) -> Result<usize, Error>
+    where
+        I: RowIndex + fmt::Display,
+    {
+        match idx.__idx(self.columns()) {
+            Some(idx) => Ok(idx),
+            None => Err(Error::column(idx.to_string())),
+        }
+    }
+
+    /// Returns the format in which the value of a column was returned, or `None` if the index is out of bounds.
+    pub fn format(&self, idx: usize) -> Option<Format> {
+        if idx >= self.len() {
+            return None;
+        }
+
+        match self.formats {
+            Some(ref formats) => Some(formats[idx]),
+            None => Some(Format::Binary),
+        }
+    }
+
+    /// Deserializes the entire row into a value implementing `serde::Deserialize`.
+    ///
+    /// Columns are mapped to struct fields by name, and to tuple and sequence elements by position. Postgres arrays
+    /// are deserialized as sequences, composites as maps, and `JSON`/`JSONB` values are passed through to the target
+    /// type as-is. Date/time, `NUMERIC`, `UUID`, and network address values are deserialized as strings in the format
+    /// Postgres uses for their text representation (with `TIMESTAMPTZ` values in UTC).
+    ///
+    /// Requires the `serde` Cargo feature.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn async_main(client: &tokio_postgres::Client) -> Result<(), tokio_postgres::Error> {
+    /// # use serde_1 as serde;
+    /// #[derive(serde::Deserialize)]
+    /// # #[serde(crate = "serde_1")]
+    /// struct User {
+    ///     id: i32,
+    ///     name: String,
+    ///     tags: Vec<String>,
+    /// }
+    ///
+    /// let row = client.query_one("SELECT id, name, tags FROM users LIMIT 1", &[]).await?;
+    /// let user: User = row.deserialize()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn deserialize<'de, T>(&'de self) -> Result<T, Error>
+    where
+        T: serde_1::Deserialize<'de>,
+    {
+        T::deserialize(self)
     }
 
     /// Get the raw bytes for the column at the given index.
-    fn col_buffer(&self, idx: usize) -> Option<&[u8]> {
+    pub(crate) fn col_buffer(&self, idx: usize) -> Option<&[u8]> {
         let range = self.ranges[idx].to_owned()?;
         Some(&self.body.buffer()[range])
     }
+
+    fn col_text(&self, idx: usize) -> Result<Option<&str>, Error> {
+        match self.col_buffer(idx) {
+            Some(raw) => match str::from_utf8(raw) {
+                Ok(raw) => Ok(Some(raw)),
+                Err(e) => Err(Error::from_sql(Box::new(e), idx)),
+            },
+            None => Ok(None),
+        }
+    }
 }
 
 impl AsName for SimpleColumn {
@@ -257,6 +388,44 @@ impl SimpleQueryRow {
         self.get_inner(&idx)
     }
 
+    /// Like `SimpleQueryRow::try_get`, but parses the text format of the value into a type implementing
+    /// `FromSqlText`.
+    ///
+    /// The type of the column is looked up from its OID among the types built into Postgres and the custom types whose
+    /// definitions the client has already cached, for example with [`TypeRegistry::load`]. Columns of any other type
+    /// return a `WrongType` error; their text can still be read with `try_get`.
+    ///
+    /// [`TypeRegistry::load`]: crate::TypeRegistry::load
+    pub fn try_get_typed<'a, I, T>(&'a self, idx: I) -> Result<T, Error>
+    where
+        I: RowIndex + fmt::Display,
+        T: FromSqlText<'a>,
+    {
+        let idx = match idx.__idx(&self.columns) {
+            Some(idx) => idx,
+            None => return Err(Error::column(idx.to_string())),
+        };
+        let raw = self.get_inner(&idx)?;
+
+        let ty = match self.columns[idx].type_() {
+            Some(ty) if T::accepts(ty) => ty,
+            Some(ty) => {
+                return Err(Error::from_sql(
+                    Box::new(WrongType::new::<T>(ty.clone())),
+                    idx,
+                ))
+            }
+            None => {
+                return Err(Error::from_sql(
+                    Box::new(WrongType::new::<T>(Type::UNKNOWN)),
+                    idx,
+                ))
+            }
+        };
+
+        T::from_sql_text_nullable(ty, raw).map_err(|e| Error::from_sql(e, idx))
+    }
+
     fn get_inner<I>(&self, idx: &I) -> Result<Option<&str>, Error>
     where
         I: RowIndex + fmt::Display,
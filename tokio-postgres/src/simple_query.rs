@@ -2,7 +2,8 @@ use crate::client::{InnerClient, Responses};
 use crate::codec::FrontendMessage;
 use crate::connection::RequestMessages;
 use crate::query::extract_row_affected;
-use crate::{Error, SimpleQueryMessage, SimpleQueryRow};
+use crate::types::{Oid, Type};
+use crate::{Error, SimpleQueryMessage, SimpleQueryRow, TypeRegistry};
 use bytes::Bytes;
 use fallible_iterator::FallibleIterator;
 use futures_util::{ready, Stream};
@@ -19,17 +20,33 @@ use std::task::{Context, Poll};
 #[derive(Debug)]
 pub struct SimpleColumn {
     name: String,
+    type_oid: Oid,
+    type_: Option<Type>,
 }
 
 impl SimpleColumn {
-    pub(crate) fn new(name: String) -> SimpleColumn {
-        SimpleColumn { name }
+    pub(crate) fn new(name: String, type_oid: Oid, type_: Option<Type>) -> SimpleColumn {
+        SimpleColumn {
+            name,
+            type_oid,
+            type_,
+        }
     }
 
     /// Returns the name of the column.
     pub fn name(&self) -> &str {
         &self.name
     }
+
+    /// Returns the OID of the type of the column.
+    pub fn type_oid(&self) -> Oid {
+        self.type_oid
+    }
+
+    /// Returns the type of the column, if it is built into Postgres or its definition is cached by the client.
+    pub(crate) fn type_(&self) -> Option<&Type> {
+        self.type_.as_ref()
+    }
 }
 
 pub async fn simple_query(client: &InnerClient, query: &str) -> Result<SimpleQueryStream, Error> {
@@ -40,6 +57,7 @@ pub async fn simple_query(client: &InnerClient, query: &str) -> Result<SimpleQue
 
     Ok(SimpleQueryStream {
         responses,
+        types: client.types().clone(),
         columns: None,
         _p: PhantomPinned,
     })
@@ -74,6 +92,7 @@ pin_project! {
     /// A stream of simple query results.
     pub struct SimpleQueryStream {
         responses: Responses,
+        types: TypeRegistry,
         columns: Option<Arc<[SimpleColumn]>>,
         #[pin]
         _p: PhantomPinned,
@@ -97,7 +116,13 @@ impl Stream for SimpleQueryStream {
                 Message::RowDescription(body) => {
                     let columns = body
                         .fields()
-                        .map(|f| Ok(SimpleColumn::new(f.name().to_string())))
+                        .map(|f| {
+                            // the definitions of custom types can only be loaded with another query, so only those
+                            // already cached are used
+                            let type_ = Type::from_oid(f.type_oid())
+                                .or_else(|| this.types.get(f.type_oid()));
+                            Ok(SimpleColumn::new(f.name().to_string(), f.type_oid(), type_))
+                        })
                         .collect::<Vec<_>>()
                         .map_err(Error::parse)?
                         .into();
@@ -1,7 +1,8 @@
 use crate::client::InnerClient;
 use crate::codec::FrontendMessage;
 use crate::connection::RequestMessages;
-use crate::types::Type;
+use crate::types::{Kind, Oid, Type};
+use postgres_protocol::message::backend::Field;
 use postgres_protocol::message::frontend;
 use std::{
     fmt,
@@ -68,11 +69,24 @@ impl Statement {
 pub struct Column {
     name: String,
     type_: Type,
+    table_oid: Oid,
+    column_id: i16,
+    type_size: i16,
+    type_modifier: i32,
+    format: i16,
 }
 
 impl Column {
-    pub(crate) fn new(name: String, type_: Type) -> Column {
-        Column { name, type_ }
+    pub(crate) fn new(name: String, type_: Type, field: &Field<'_>) -> Column {
+        Column {
+            name,
+            type_,
+            table_oid: field.table_oid(),
+            column_id: field.column_id(),
+            type_size: field.type_size(),
+            type_modifier: field.type_modifier(),
+            format: field.format(),
+        }
     }
 
     /// Returns the name of the column.
@@ -84,6 +98,103 @@ impl Column {
     pub fn type_(&self) -> &Type {
         &self.type_
     }
+
+    /// Returns the OID of the table the column was taken from, if it is a plain reference to a table column.
+    pub fn table_oid(&self) -> Option<Oid> {
+        if self.table_oid == 0 {
+            None
+        } else {
+            Some(self.table_oid)
+        }
+    }
+
+    /// Returns the attribute number of the column within its source table, if it is a plain reference to a table
+    /// column.
+    pub fn column_id(&self) -> Option<i16> {
+        if self.column_id == 0 {
+            None
+        } else {
+            Some(self.column_id)
+        }
+    }
+
+    /// Returns the size of the column's type in bytes, as in `pg_type.typlen`.
+    ///
+    /// Negative values indicate variable-width types.
+    pub fn type_size(&self) -> i16 {
+        self.type_size
+    }
+
+    /// Returns the type modifier of the column, as in `pg_attribute.atttypmod`.
+    ///
+    /// The meaning of the modifier is type-specific, and -1 indicates that there is none. The
+    /// `character_maximum_length`, `numeric_precision_scale` and `datetime_precision` methods decode the modifiers of
+    /// common types.
+    pub fn type_modifier(&self) -> i32 {
+        self.type_modifier
+    }
+
+    /// Returns the format code of the column reported by the server, where 0 is text and 1 is binary.
+    ///
+    /// The format is not known when a statement is prepared, so this is always 0 for the columns of a `Statement`.
+    pub fn format(&self) -> i16 {
+        self.format
+    }
+
+    /// Returns the declared maximum length of a `CHAR`, `VARCHAR`, `BIT` or `VARBIT` column.
+    pub fn character_maximum_length(&self) -> Option<i32> {
+        if self.type_modifier < 0 {
+            return None;
+        }
+
+        match *base_type(&self.type_) {
+            Type::BPCHAR | Type::VARCHAR => Some(self.type_modifier - VARHDRSZ),
+            Type::BIT | Type::VARBIT => Some(self.type_modifier),
+            _ => None,
+        }
+    }
+
+    /// Returns the declared precision and scale of a `NUMERIC` column.
+    pub fn numeric_precision_scale(&self) -> Option<(i32, i32)> {
+        if *base_type(&self.type_) != Type::NUMERIC || self.type_modifier < VARHDRSZ {
+            return None;
+        }
+
+        let typmod = self.type_modifier - VARHDRSZ;
+        let precision = (typmod >> 16) & 0xffff;
+        // the scale is an 11 bit signed integer
+        let scale = ((typmod & 0x7ff) ^ 0x400) - 0x400;
+        Some((precision, scale))
+    }
+
+    /// Returns the declared fractional seconds precision of a `TIME`, `TIMETZ`, `TIMESTAMP`, `TIMESTAMPTZ` or
+    /// `INTERVAL` column.
+    pub fn datetime_precision(&self) -> Option<i32> {
+        if self.type_modifier < 0 {
+            return None;
+        }
+
+        match *base_type(&self.type_) {
+            Type::TIME | Type::TIMETZ | Type::TIMESTAMP | Type::TIMESTAMPTZ => {
+                Some(self.type_modifier)
+            }
+            Type::INTERVAL => match self.type_modifier & 0xffff {
+                INTERVAL_FULL_PRECISION => None,
+                precision => Some(precision),
+            },
+            _ => None,
+        }
+    }
+}
+
+const VARHDRSZ: i32 = 4;
+const INTERVAL_FULL_PRECISION: i32 = 0xffff;
+
+fn base_type(type_: &Type) -> &Type {
+    match type_.kind() {
+        Kind::Domain(base) => base_type(base),
+        _ => type_,
+    }
 }
 
 impl fmt::Debug for Column {
@@ -91,6 +202,11 @@ impl fmt::Debug for Column {
         fmt.debug_struct("Column")
             .field("name", &self.name)
             .field("type", &self.type_)
+            .field("table_oid", &self.table_oid)
+            .field("column_id", &self.column_id)
+            .field("type_size", &self.type_size)
+            .field("type_modifier", &self.type_modifier)
+            .field("format", &self.format)
             .finish()
     }
 }
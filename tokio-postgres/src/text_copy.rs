@@ -0,0 +1,776 @@
+//! Utilities for working with the PostgreSQL text and CSV copy formats.
+
+use crate::types::{Format, IsNull, ToSql, Type};
+use crate::{slice_iter, text_encode, CopyInSink, CopyOutStream, Error};
+use bytes::{BufMut, Bytes, BytesMut};
+use futures_util::{ready, SinkExt, Stream};
+use pin_project_lite::pin_project;
+use postgres_types::BorrowToSql;
+use std::error;
+use std::io;
+use std::ops::Range;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::task::{Context, Poll};
+
+/// Options for the PostgreSQL text copy format.
+///
+/// These must match the options of the `COPY` statement the data is read from or written to.
+#[derive(Debug, Clone)]
+pub struct TextOptions {
+    delimiter: u8,
+    null: String,
+    header: bool,
+}
+
+impl Default for TextOptions {
+    fn default() -> TextOptions {
+        TextOptions {
+            delimiter: b'\t',
+            null: "\\N".to_string(),
+            header: false,
+        }
+    }
+}
+
+impl TextOptions {
+    /// Creates a new set of options with the PostgreSQL defaults.
+    pub fn new() -> TextOptions {
+        TextOptions::default()
+    }
+
+    /// Sets the single-byte character separating columns.
+    ///
+    /// Defaults to a tab character.
+    pub fn delimiter(mut self, delimiter: u8) -> TextOptions {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Sets the string representing a null value.
+    ///
+    /// Defaults to `\N`.
+    pub fn null(mut self, null: &str) -> TextOptions {
+        self.null = null.to_string();
+        self
+    }
+
+    /// Sets whether the data starts with a header line.
+    ///
+    /// Out streams parse the header separately rather than returning it as a row. In writers, the header must be
+    /// written explicitly with `write_header`.
+    ///
+    /// Defaults to `false`.
+    pub fn header(mut self, header: bool) -> TextOptions {
+        self.header = header;
+        self
+    }
+}
+
+/// Options for the PostgreSQL CSV copy format.
+///
+/// These must match the options of the `COPY` statement the data is read from or written to.
+#[derive(Debug, Clone)]
+pub struct CsvOptions {
+    delimiter: u8,
+    quote: u8,
+    escape: Option<u8>,
+    null: String,
+    header: bool,
+}
+
+impl Default for CsvOptions {
+    fn default() -> CsvOptions {
+        CsvOptions {
+            delimiter: b',',
+            quote: b'"',
+            escape: None,
+            null: String::new(),
+            header: false,
+        }
+    }
+}
+
+impl CsvOptions {
+    /// Creates a new set of options with the PostgreSQL defaults.
+    pub fn new() -> CsvOptions {
+        CsvOptions::default()
+    }
+
+    /// Sets the single-byte character separating columns.
+    ///
+    /// Defaults to `,`.
+    pub fn delimiter(mut self, delimiter: u8) -> CsvOptions {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Sets the single-byte character used to quote values.
+    ///
+    /// Defaults to `"`.
+    pub fn quote(mut self, quote: u8) -> CsvOptions {
+        self.quote = quote;
+        self
+    }
+
+    /// Sets the single-byte character used to escape quote characters within quoted values.
+    ///
+    /// Defaults to the quote character.
+    pub fn escape(mut self, escape: u8) -> CsvOptions {
+        self.escape = Some(escape);
+        self
+    }
+
+    /// Sets the string representing a null value.
+    ///
+    /// Defaults to an unquoted empty string.
+    pub fn null(mut self, null: &str) -> CsvOptions {
+        self.null = null.to_string();
+        self
+    }
+
+    /// Sets whether the data starts with a header line.
+    ///
+    /// Out streams parse the header separately rather than returning it as a row. In writers, the header must be
+    /// written explicitly with `write_header`.
+    ///
+    /// Defaults to `false`.
+    pub fn header(mut self, header: bool) -> CsvOptions {
+        self.header = header;
+        self
+    }
+
+    fn escape_char(&self) -> u8 {
+        self.escape.unwrap_or(self.quote)
+    }
+}
+
+enum Options {
+    Text(TextOptions),
+    Csv(CsvOptions),
+}
+
+impl Options {
+    fn delimiter(&self) -> u8 {
+        match self {
+            Options::Text(options) => options.delimiter,
+            Options::Csv(options) => options.delimiter,
+        }
+    }
+
+    fn header(&self) -> bool {
+        match self {
+            Options::Text(options) => options.header,
+            Options::Csv(options) => options.header,
+        }
+    }
+
+    fn encode_row<'a, I>(&self, fields: I, buf: &mut BytesMut)
+    where
+        I: IntoIterator<Item = Option<&'a [u8]>>,
+    {
+        for (i, field) in fields.into_iter().enumerate() {
+            if i > 0 {
+                buf.put_u8(self.delimiter());
+            }
+            match self {
+                Options::Text(options) => encode_text_field(options, field, buf),
+                Options::Csv(options) => encode_csv_field(options, field, buf),
+            }
+        }
+        buf.put_u8(b'\n');
+    }
+
+    fn decode_row(&self, mut line: &[u8]) -> Result<TextCopyOutRow, String> {
+        if let Some(stripped) = line.strip_suffix(b"\n") {
+            line = stripped;
+        }
+
+        let mut buf = vec![];
+        let mut ranges = vec![];
+        match self {
+            Options::Text(options) => decode_text_row(options, line, &mut buf, &mut ranges)?,
+            Options::Csv(options) => decode_csv_row(options, line, &mut buf, &mut ranges)?,
+        }
+
+        let buf = String::from_utf8(buf).map_err(|_| "invalid UTF-8".to_string())?;
+        Ok(TextCopyOutRow { buf, ranges })
+    }
+}
+
+fn encode_text_field(options: &TextOptions, field: Option<&[u8]>, buf: &mut BytesMut) {
+    let field = match field {
+        Some(field) => field,
+        None => {
+            buf.put_slice(options.null.as_bytes());
+            return;
+        }
+    };
+
+    for &b in field {
+        match b {
+            b'\\' => buf.put_slice(b"\\\\"),
+            b'\n' => buf.put_slice(b"\\n"),
+            b'\r' => buf.put_slice(b"\\r"),
+            b'\t' => buf.put_slice(b"\\t"),
+            0x08 => buf.put_slice(b"\\b"),
+            0x0b => buf.put_slice(b"\\v"),
+            0x0c => buf.put_slice(b"\\f"),
+            b if b == options.delimiter => {
+                buf.put_u8(b'\\');
+                buf.put_u8(b);
+            }
+            b => buf.put_u8(b),
+        }
+    }
+}
+
+fn encode_csv_field(options: &CsvOptions, field: Option<&[u8]>, buf: &mut BytesMut) {
+    let field = match field {
+        Some(field) => field,
+        None => {
+            buf.put_slice(options.null.as_bytes());
+            return;
+        }
+    };
+
+    let escape = options.escape_char();
+    let needs_quotes = field == options.null.as_bytes()
+        || field == b"\\."
+        || field
+            .iter()
+            .any(|&b| b == options.delimiter || b == options.quote || b == b'\n' || b == b'\r');
+    if !needs_quotes {
+        buf.put_slice(field);
+        return;
+    }
+
+    buf.put_u8(options.quote);
+    for &b in field {
+        if b == options.quote || b == escape {
+            buf.put_u8(escape);
+        }
+        buf.put_u8(b);
+    }
+    buf.put_u8(options.quote);
+}
+
+fn decode_text_row(
+    options: &TextOptions,
+    line: &[u8],
+    buf: &mut Vec<u8>,
+    ranges: &mut Vec<Option<Range<usize>>>,
+) -> Result<(), String> {
+    let mut fields = line;
+    loop {
+        // find the end of the field, skipping over escaped characters
+        let mut end = 0;
+        while end < fields.len() && fields[end] != options.delimiter {
+            if fields[end] == b'\\' {
+                end += 1;
+            }
+            end += 1;
+        }
+        if end > fields.len() {
+            return Err("unterminated escape sequence".to_string());
+        }
+
+        let raw = &fields[..end];
+        if raw == options.null.as_bytes() {
+            ranges.push(None);
+        } else {
+            let start = buf.len();
+            unescape_text(raw, buf);
+            ranges.push(Some(start..buf.len()));
+        }
+
+        if end == fields.len() {
+            return Ok(());
+        }
+        fields = &fields[end + 1..];
+    }
+}
+
+fn unescape_text(mut raw: &[u8], buf: &mut Vec<u8>) {
+    while let Some((&b, rest)) = raw.split_first() {
+        raw = rest;
+        if b != b'\\' {
+            buf.push(b);
+            continue;
+        }
+
+        let (&b, rest) = raw.split_first().unwrap();
+        raw = rest;
+        match b {
+            b'b' => buf.push(0x08),
+            b'f' => buf.push(0x0c),
+            b'n' => buf.push(b'\n'),
+            b'r' => buf.push(b'\r'),
+            b't' => buf.push(b'\t'),
+            b'v' => buf.push(0x0b),
+            b'0'..=b'7' => {
+                let mut value = b - b'0';
+                let mut digits = 1;
+                while digits < 3 {
+                    match raw.first() {
+                        Some(&d @ b'0'..=b'7') => {
+                            value = value.wrapping_mul(8).wrapping_add(d - b'0');
+                            raw = &raw[1..];
+                            digits += 1;
+                        }
+                        _ => break,
+                    }
+                }
+                buf.push(value);
+            }
+            b'x' if matches!(raw.first(), Some(b) if b.is_ascii_hexdigit()) => {
+                let mut value = 0;
+                let mut digits = 0;
+                while digits < 2 {
+                    match raw.first().and_then(|&d| (d as char).to_digit(16)) {
+                        Some(d) => {
+                            value = value * 16 + d as u8;
+                            raw = &raw[1..];
+                            digits += 1;
+                        }
+                        None => break,
+                    }
+                }
+                buf.push(value);
+            }
+            b => buf.push(b),
+        }
+    }
+}
+
+fn decode_csv_row(
+    options: &CsvOptions,
+    line: &[u8],
+    buf: &mut Vec<u8>,
+    ranges: &mut Vec<Option<Range<usize>>>,
+) -> Result<(), String> {
+    let escape = options.escape_char();
+    let mut fields = line;
+    loop {
+        let start = buf.len();
+        let mut in_quotes = false;
+        let mut quoted = false;
+        let mut end = 0;
+        while end < fields.len() {
+            let b = fields[end];
+            if in_quotes {
+                if b == escape
+                    && end + 1 < fields.len()
+                    && (fields[end + 1] == options.quote || fields[end + 1] == escape)
+                {
+                    buf.push(fields[end + 1]);
+                    end += 2;
+                    continue;
+                }
+                if b == options.quote {
+                    in_quotes = false;
+                } else {
+                    buf.push(b);
+                }
+            } else if b == options.delimiter {
+                break;
+            } else if b == options.quote {
+                in_quotes = true;
+                quoted = true;
+            } else {
+                buf.push(b);
+            }
+            end += 1;
+        }
+        if in_quotes {
+            return Err("unterminated quoted field".to_string());
+        }
+
+        if !quoted && &fields[..end] == options.null.as_bytes() {
+            buf.truncate(start);
+            ranges.push(None);
+        } else {
+            ranges.push(Some(start..buf.len()));
+        }
+
+        if end == fields.len() {
+            return Ok(());
+        }
+        fields = &fields[end + 1..];
+    }
+}
+
+/// Serializes a value into its PostgreSQL text representation, returning `IsNull::Yes` for null values.
+fn to_text(
+    value: &dyn ToSql,
+    type_: &Type,
+    buf: &mut BytesMut,
+) -> Result<IsNull, Box<dyn error::Error + Sync + Send>> {
+    let mut raw = BytesMut::new();
+    if let IsNull::Yes = value.to_sql_checked(type_, &mut raw)? {
+        return Ok(IsNull::Yes);
+    }
+
+    if let Format::Text = value.encode_format(type_) {
+        buf.put_slice(&raw);
+    } else {
+        text_encode::write(type_, &raw, buf)?;
+    }
+    Ok(IsNull::No)
+}
+
+pin_project! {
+    struct Writer {
+        #[pin]
+        sink: CopyInSink<Bytes>,
+        types: Vec<Type>,
+        options: Options,
+        buf: BytesMut,
+        scratch: BytesMut,
+    }
+}
+
+impl Writer {
+    fn new(sink: CopyInSink<Bytes>, types: &[Type], options: Options) -> Writer {
+        Writer {
+            sink,
+            types: types.to_vec(),
+            options,
+            buf: BytesMut::new(),
+            scratch: BytesMut::new(),
+        }
+    }
+
+    async fn write_raw<P, I>(self: Pin<&mut Self>, values: I) -> Result<(), Error>
+    where
+        P: BorrowToSql,
+        I: IntoIterator<Item = P>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let this = self.project();
+
+        let values = values.into_iter();
+        assert!(
+            values.len() == this.types.len(),
+            "expected {} values but got {}",
+            this.types.len(),
+            values.len(),
+        );
+
+        this.scratch.clear();
+        let mut ranges = Vec::with_capacity(this.types.len());
+        for (i, (value, type_)) in values.zip(this.types.iter()).enumerate() {
+            let start = this.scratch.len();
+            let range = match to_text(value.borrow_to_sql(), type_, this.scratch)
+                .map_err(|e| Error::to_sql(e, i))?
+            {
+                IsNull::Yes => None,
+                IsNull::No => Some(start..this.scratch.len()),
+            };
+            ranges.push(range);
+        }
+
+        let scratch = &this.scratch[..];
+        this.options
+            .encode_row(ranges.into_iter().map(|r| r.map(|r| &scratch[r])), this.buf);
+        Self::flush(this.sink, this.buf, 4096).await
+    }
+
+    async fn write_text(self: Pin<&mut Self>, fields: &[Option<&str>]) -> Result<(), Error> {
+        let this = self.project();
+
+        assert!(
+            fields.len() == this.types.len(),
+            "expected {} values but got {}",
+            this.types.len(),
+            fields.len(),
+        );
+
+        this.options
+            .encode_row(fields.iter().map(|f| f.map(str::as_bytes)), this.buf);
+        Self::flush(this.sink, this.buf, 4096).await
+    }
+
+    async fn write_header(self: Pin<&mut Self>, names: &[&str]) -> Result<(), Error> {
+        let this = self.project();
+
+        this.options
+            .encode_row(names.iter().map(|n| Some(n.as_bytes())), this.buf);
+        Self::flush(this.sink, this.buf, 4096).await
+    }
+
+    async fn finish(self: Pin<&mut Self>) -> Result<u64, Error> {
+        let mut this = self.project();
+
+        Self::flush(this.sink.as_mut(), this.buf, 0).await?;
+        this.sink.finish().await
+    }
+
+    async fn flush(
+        mut sink: Pin<&mut CopyInSink<Bytes>>,
+        buf: &mut BytesMut,
+        threshold: usize,
+    ) -> Result<(), Error> {
+        if buf.len() > threshold {
+            sink.send(buf.split().freeze()).await?;
+        }
+        Ok(())
+    }
+}
+
+macro_rules! copy_in_writer {
+    ($(#[$attr:meta])* $name:ident, $options:ident, $variant:ident) => {
+        pin_project! {
+            $(#[$attr])*
+            pub struct $name {
+                #[pin]
+                writer: Writer,
+            }
+        }
+
+        impl $name {
+            /// Creates a new writer which will write rows of the provided types to the provided sink.
+            pub fn new(sink: CopyInSink<Bytes>, types: &[Type], options: $options) -> $name {
+                $name {
+                    writer: Writer::new(sink, types, Options::$variant(options)),
+                }
+            }
+
+            /// Writes a single row.
+            ///
+            /// Values are converted to their text representation. This is supported for values which are sent in
+            /// the text format, and for binary values of the following types, along with enums, arrays of any of
+            /// them, and domains over them:
+            ///
+            /// * the textual types, `JSON`, `JSONB`, and `XML`
+            /// * `BOOL`, `INT2`, `INT4`, `INT8`, `OID`, `FLOAT4`, `FLOAT8`, and `NUMERIC`
+            /// * `BYTEA` and `UUID`
+            /// * `DATE`, `TIME`, `TIMETZ`, `TIMESTAMP`, `TIMESTAMPTZ`, and `INTERVAL`
+            /// * `INET`, `CIDR`, and `MACADDR`
+            ///
+            /// Values of any other type, such as ranges, composites, and the geometric types, must be converted
+            /// to text by the caller and written with `write_text`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the number of values provided does not match the number expected.
+            pub async fn write(
+                self: Pin<&mut Self>,
+                values: &[&(dyn ToSql + Sync)],
+            ) -> Result<(), Error> {
+                self.write_raw(slice_iter(values)).await
+            }
+
+            /// A maximally-flexible version of `write`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the number of values provided does not match the number expected.
+            pub async fn write_raw<P, I>(self: Pin<&mut Self>, values: I) -> Result<(), Error>
+            where
+                P: BorrowToSql,
+                I: IntoIterator<Item = P>,
+                I::IntoIter: ExactSizeIterator,
+            {
+                self.project().writer.write_raw(values).await
+            }
+
+            /// Writes a single row of values already in their PostgreSQL text representation, with `None`
+            /// representing a null value.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the number of values provided does not match the number expected.
+            pub async fn write_text(self: Pin<&mut Self>, fields: &[Option<&str>]) -> Result<(), Error> {
+                self.project().writer.write_text(fields).await
+            }
+
+            /// Writes a header line containing the provided column names.
+            ///
+            /// This should only be used when the `COPY` statement has the `HEADER` option, and must be called
+            /// before any rows are written.
+            pub async fn write_header(self: Pin<&mut Self>, names: &[&str]) -> Result<(), Error> {
+                self.project().writer.write_header(names).await
+            }
+
+            /// Completes the copy, returning the number of rows added.
+            ///
+            /// This method *must* be used to complete the copy process. If it is not, the copy will be aborted.
+            pub async fn finish(self: Pin<&mut Self>) -> Result<u64, Error> {
+                self.project().writer.finish().await
+            }
+        }
+    };
+}
+
+copy_in_writer!(
+    /// A type which serializes rows into the PostgreSQL text copy format.
+    ///
+    /// The copy *must* be explicitly completed via the `finish` method. If it is not, the copy will be aborted.
+    TextCopyInWriter,
+    TextOptions,
+    Text
+);
+
+copy_in_writer!(
+    /// A type which serializes rows into the PostgreSQL CSV copy format.
+    ///
+    /// The copy *must* be explicitly completed via the `finish` method. If it is not, the copy will be aborted.
+    CsvCopyInWriter,
+    CsvOptions,
+    Csv
+);
+
+pin_project! {
+    struct Reader {
+        #[pin]
+        stream: CopyOutStream,
+        options: Options,
+        rows: u64,
+        header: Option<TextCopyOutRow>,
+    }
+}
+
+impl Stream for Reader {
+    type Item = Result<TextCopyOutRow, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        loop {
+            let chunk = match ready!(this.stream.as_mut().poll_next(cx)) {
+                Some(Ok(chunk)) => chunk,
+                Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+                None => return Poll::Ready(None),
+            };
+
+            *this.rows += 1;
+            let row = this.options.decode_row(&chunk).map_err(|e| {
+                Error::parse(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("row {}: {}", this.rows, e),
+                ))
+            })?;
+
+            if *this.rows == 1 && this.options.header() {
+                *this.header = Some(row);
+                continue;
+            }
+
+            return Poll::Ready(Some(Ok(row)));
+        }
+    }
+}
+
+macro_rules! copy_out_stream {
+    ($(#[$attr:meta])* $name:ident, $options:ident, $variant:ident) => {
+        pin_project! {
+            $(#[$attr])*
+            pub struct $name {
+                #[pin]
+                reader: Reader,
+            }
+        }
+
+        impl $name {
+            /// Creates a stream from a raw copy out stream.
+            pub fn new(stream: CopyOutStream, options: $options) -> $name {
+                $name {
+                    reader: Reader {
+                        stream,
+                        options: Options::$variant(options),
+                        rows: 0,
+                        header: None,
+                    },
+                }
+            }
+
+            /// Returns the header line, if the stream's options specify one and it has been read.
+            pub fn header(&self) -> Option<&TextCopyOutRow> {
+                self.reader.header.as_ref()
+            }
+        }
+
+        impl Stream for $name {
+            type Item = Result<TextCopyOutRow, Error>;
+
+            fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+                self.project().reader.poll_next(cx)
+            }
+        }
+    };
+}
+
+copy_out_stream!(
+    /// A stream of rows parsed from the PostgreSQL text copy format.
+    ///
+    /// Parse errors identify the offending row by its 1-based position in the output, counting any header.
+    TextCopyOutStream,
+    TextOptions,
+    Text
+);
+
+copy_out_stream!(
+    /// A stream of rows parsed from the PostgreSQL CSV copy format.
+    ///
+    /// Parse errors identify the offending row by its 1-based position in the output, counting any header.
+    CsvCopyOutStream,
+    CsvOptions,
+    Csv
+);
+
+/// A row of data parsed from a text or CSV copy out stream.
+#[derive(Debug)]
+pub struct TextCopyOutRow {
+    buf: String,
+    ranges: Vec<Option<Range<usize>>>,
+}
+
+impl TextCopyOutRow {
+    /// Returns the number of values in the row.
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// Determines if the row contains no values.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Like `get`, but returns a `Result` rather than panicking.
+    pub fn try_get(&self, idx: usize) -> Result<Option<&str>, Error> {
+        match self.ranges.get(idx) {
+            Some(range) => Ok(range.clone().map(|r| &self.buf[r])),
+            None => Err(Error::column(idx.to_string())),
+        }
+    }
+
+    /// Returns the text of a value from the row, or `None` if it is null.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn get(&self, idx: usize) -> Option<&str> {
+        match self.try_get(idx) {
+            Ok(value) => value,
+            Err(e) => panic!("error retrieving column {}: {}", idx, e),
+        }
+    }
+
+    /// Parses a value from the row with its `FromStr` implementation, returning `None` if it is null.
+    ///
+    /// Errors identify the offending column.
+    pub fn parse<T>(&self, idx: usize) -> Result<Option<T>, Error>
+    where
+        T: FromStr,
+        T::Err: Into<Box<dyn error::Error + Sync + Send>>,
+    {
+        match self.try_get(idx)? {
+            Some(value) => value
+                .parse()
+                .map(Some)
+                .map_err(|e: T::Err| Error::from_sql(e.into(), idx)),
+            None => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,399 @@
+//! Conversion of binary-encoded values into their PostgreSQL text representation.
+
+use crate::types::{Kind, Type};
+use fallible_iterator::FallibleIterator;
+use postgres_protocol::types::{self, ArrayDimension, ArrayValues};
+use std::error::Error;
+use std::fmt::Write;
+use std::str;
+
+const MICROS_PER_DAY: i64 = 86_400_000_000;
+
+/// Writes the text representation of a binary-encoded value of the specified type.
+///
+/// `TIMESTAMPTZ` values are written in UTC, and `INTERVAL` values in the `postgres` interval style.
+pub(crate) fn write<W>(
+    type_: &Type,
+    raw: &[u8],
+    w: &mut W,
+) -> Result<(), Box<dyn Error + Sync + Send>>
+where
+    W: Write,
+{
+    match type_.kind() {
+        Kind::Enum(_) => return Ok(w.write_str(str::from_utf8(raw)?)?),
+        Kind::Domain(base) => return write(base, raw, w),
+        Kind::Array(member) => return array(member, raw, w),
+        _ => {}
+    }
+
+    match *type_ {
+        Type::TEXT
+        | Type::VARCHAR
+        | Type::BPCHAR
+        | Type::NAME
+        | Type::UNKNOWN
+        | Type::CHAR
+        | Type::JSON
+        | Type::XML => w.write_str(str::from_utf8(raw)?)?,
+        Type::JSONB => match raw.split_first() {
+            Some((1, json)) => w.write_str(str::from_utf8(json)?)?,
+            _ => return Err("unsupported JSONB encoding version".into()),
+        },
+        Type::BOOL => w.write_char(if types::bool_from_sql(raw)? { 't' } else { 'f' })?,
+        Type::INT2 => write!(w, "{}", types::int2_from_sql(raw)?)?,
+        Type::INT4 => write!(w, "{}", types::int4_from_sql(raw)?)?,
+        Type::INT8 => write!(w, "{}", types::int8_from_sql(raw)?)?,
+        Type::OID => write!(w, "{}", types::oid_from_sql(raw)?)?,
+        Type::FLOAT4 => write!(w, "{}", types::float4_from_sql(raw)?)?,
+        Type::FLOAT8 => write!(w, "{}", types::float8_from_sql(raw)?)?,
+        Type::NUMERIC => numeric(raw, w)?,
+        Type::BYTEA => {
+            w.write_str("\\x")?;
+            for b in raw {
+                write!(w, "{:02x}", b)?;
+            }
+        }
+        Type::UUID => {
+            let uuid = types::uuid_from_sql(raw)?;
+            for (i, b) in uuid.iter().enumerate() {
+                if let 4 | 6 | 8 | 10 = i {
+                    w.write_char('-')?;
+                }
+                write!(w, "{:02x}", b)?;
+            }
+        }
+        Type::DATE => match types::date_from_sql(raw)? {
+            i32::MAX => w.write_str("infinity")?,
+            i32::MIN => w.write_str("-infinity")?,
+            days => {
+                let (year, month, day) = civil_from_days(i64::from(days));
+                write_date(year, month, day, w)?;
+                if year <= 0 {
+                    w.write_str(" BC")?;
+                }
+            }
+        },
+        Type::TIME => time(types::time_from_sql(raw)?, w)?,
+        Type::TIMETZ => timetz(raw, w)?,
+        Type::TIMESTAMP => timestamp(types::timestamp_from_sql(raw)?, "", w)?,
+        Type::TIMESTAMPTZ => timestamp(types::timestamp_from_sql(raw)?, "+00", w)?,
+        Type::INTERVAL => interval(raw, w)?,
+        Type::INET | Type::CIDR => {
+            let inet = types::inet_from_sql(raw)?;
+            let max = if inet.addr().is_ipv4() { 32 } else { 128 };
+            write!(w, "{}", inet.addr())?;
+            if *type_ == Type::CIDR || inet.netmask() != max {
+                write!(w, "/{}", inet.netmask())?;
+            }
+        }
+        Type::MACADDR => {
+            let mac = types::macaddr_from_sql(raw)?;
+            for (i, b) in mac.iter().enumerate() {
+                if i > 0 {
+                    w.write_char(':')?;
+                }
+                write!(w, "{:02x}", b)?;
+            }
+        }
+        _ => return Err(format!("type {} has no supported text representation", type_).into()),
+    }
+
+    Ok(())
+}
+
+fn array<W>(member: &Type, raw: &[u8], w: &mut W) -> Result<(), Box<dyn Error + Sync + Send>>
+where
+    W: Write,
+{
+    let array = types::array_from_sql(raw)?;
+    let dimensions = array.dimensions().collect::<Vec<_>>()?;
+    if dimensions.is_empty() {
+        w.write_str("{}")?;
+        return Ok(());
+    }
+
+    if dimensions.iter().any(|d| d.lower_bound != 1) {
+        for dimension in &dimensions {
+            write!(
+                w,
+                "[{}:{}]",
+                dimension.lower_bound,
+                dimension.lower_bound + dimension.len - 1
+            )?;
+        }
+        w.write_char('=')?;
+    }
+
+    let mut values = array.values();
+    array_dimension(member, &dimensions, &mut values, w)?;
+    if values.next()?.is_some() {
+        return Err("array contains more values than its dimensions allow".into());
+    }
+    Ok(())
+}
+
+fn array_dimension<W>(
+    member: &Type,
+    dimensions: &[ArrayDimension],
+    values: &mut ArrayValues<'_>,
+    w: &mut W,
+) -> Result<(), Box<dyn Error + Sync + Send>>
+where
+    W: Write,
+{
+    // only the box type uses a delimiter other than a comma
+    let delimiter = if *member == Type::BOX { ';' } else { ',' };
+
+    w.write_char('{')?;
+    for i in 0..dimensions[0].len {
+        if i > 0 {
+            w.write_char(delimiter)?;
+        }
+        if dimensions.len() > 1 {
+            array_dimension(member, &dimensions[1..], values, w)?;
+            continue;
+        }
+
+        match values.next()? {
+            Some(Some(raw)) => {
+                let mut element = String::new();
+                write(member, raw, &mut element)?;
+                array_element(&element, delimiter, w)?;
+            }
+            Some(None) => w.write_str("NULL")?,
+            None => return Err("array contains fewer values than its dimensions require".into()),
+        }
+    }
+    w.write_char('}')?;
+
+    Ok(())
+}
+
+fn array_element<W>(
+    element: &str,
+    delimiter: char,
+    w: &mut W,
+) -> Result<(), Box<dyn Error + Sync + Send>>
+where
+    W: Write,
+{
+    let quote = element.is_empty()
+        || element.eq_ignore_ascii_case("NULL")
+        || element.chars().any(|c| {
+            matches!(c, '{' | '}' | '"' | '\\') || c == delimiter || c.is_ascii_whitespace()
+        });
+    if !quote {
+        w.write_str(element)?;
+        return Ok(());
+    }
+
+    w.write_char('"')?;
+    for c in element.chars() {
+        if let '"' | '\\' = c {
+            w.write_char('\\')?;
+        }
+        w.write_char(c)?;
+    }
+    w.write_char('"')?;
+
+    Ok(())
+}
+
+fn numeric<W>(raw: &[u8], w: &mut W) -> Result<(), Box<dyn Error + Sync + Send>>
+where
+    W: Write,
+{
+    if raw.len() < 8 {
+        return Err("invalid message length: numeric header too short".into());
+    }
+    let ndigits = i16::from_be_bytes([raw[0], raw[1]]);
+    let weight = i16::from_be_bytes([raw[2], raw[3]]);
+    let sign = u16::from_be_bytes([raw[4], raw[5]]);
+    let dscale = u16::from_be_bytes([raw[6], raw[7]]);
+
+    match sign {
+        0x0000 | 0x4000 => {}
+        0xC000 => return Ok(w.write_str("NaN")?),
+        0xD000 => return Ok(w.write_str("Infinity")?),
+        0xF000 => return Ok(w.write_str("-Infinity")?),
+        _ => return Err("invalid numeric sign".into()),
+    }
+    if ndigits < 0 || raw.len() - 8 != ndigits as usize * 2 {
+        return Err("invalid message length: numeric size mismatch".into());
+    }
+    let digits = raw[8..]
+        .chunks_exact(2)
+        .map(|d| i16::from_be_bytes([d[0], d[1]]))
+        .collect::<Vec<_>>();
+    // each digit is a base 10000 value, the first of which has the specified weight
+    let digit = |i: i32| {
+        if i < 0 {
+            0
+        } else {
+            digits.get(i as usize).copied().unwrap_or(0)
+        }
+    };
+
+    if sign == 0x4000 {
+        w.write_char('-')?;
+    }
+    if weight < 0 {
+        w.write_char('0')?;
+    } else {
+        write!(w, "{}", digit(0))?;
+        for i in 1..=i32::from(weight) {
+            write!(w, "{:04}", digit(i))?;
+        }
+    }
+    if dscale > 0 {
+        let mut fraction = String::new();
+        let mut i = i32::from(weight) + 1;
+        while fraction.len() < usize::from(dscale) {
+            write!(fraction, "{:04}", digit(i))?;
+            i += 1;
+        }
+        fraction.truncate(usize::from(dscale));
+        write!(w, ".{}", fraction)?;
+    }
+
+    Ok(())
+}
+
+// converts a number of days since 2000-01-01 into a year, month, and day, using the algorithm from
+// http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 10957 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+fn write_date<W>(
+    year: i64,
+    month: i64,
+    day: i64,
+    w: &mut W,
+) -> Result<(), Box<dyn Error + Sync + Send>>
+where
+    W: Write,
+{
+    // years before 1 AD are numbered from 1 BC, with no year 0
+    let year = if year > 0 { year } else { 1 - year };
+    write!(w, "{:04}-{:02}-{:02}", year, month, day)?;
+    Ok(())
+}
+
+fn time<W>(usec: i64, w: &mut W) -> Result<(), Box<dyn Error + Sync + Send>>
+where
+    W: Write,
+{
+    let secs = usec / 1_000_000;
+    write!(
+        w,
+        "{:02}:{:02}:{:02}",
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60
+    )?;
+    let fraction = usec % 1_000_000;
+    if fraction != 0 {
+        let fraction = format!(".{:06}", fraction);
+        w.write_str(fraction.trim_end_matches('0'))?;
+    }
+    Ok(())
+}
+
+fn timetz<W>(raw: &[u8], w: &mut W) -> Result<(), Box<dyn Error + Sync + Send>>
+where
+    W: Write,
+{
+    if raw.len() != 12 {
+        return Err("invalid message length: timetz size mismatch".into());
+    }
+    time(types::time_from_sql(&raw[..8])?, w)?;
+
+    // the zone is stored as seconds west of UTC
+    let offset = -i32::from_be_bytes([raw[8], raw[9], raw[10], raw[11]]);
+    w.write_char(if offset < 0 { '-' } else { '+' })?;
+    let offset = offset.abs();
+    write!(w, "{:02}", offset / 3600)?;
+    if offset % 3600 != 0 {
+        write!(w, ":{:02}", offset / 60 % 60)?;
+    }
+    if offset % 60 != 0 {
+        write!(w, ":{:02}", offset % 60)?;
+    }
+    Ok(())
+}
+
+fn timestamp<W>(usec: i64, zone: &str, w: &mut W) -> Result<(), Box<dyn Error + Sync + Send>>
+where
+    W: Write,
+{
+    match usec {
+        i64::MAX => w.write_str("infinity")?,
+        i64::MIN => w.write_str("-infinity")?,
+        usec => {
+            let (year, month, day) = civil_from_days(usec.div_euclid(MICROS_PER_DAY));
+            write_date(year, month, day, w)?;
+            w.write_char(' ')?;
+            time(usec.rem_euclid(MICROS_PER_DAY), w)?;
+            w.write_str(zone)?;
+            if year <= 0 {
+                w.write_str(" BC")?;
+            }
+        }
+    }
+    Ok(())
+}
+
+// writes an interval in the `postgres` interval style, such as `1 year 2 mons -3 days +04:05:06.789`
+fn interval<W>(raw: &[u8], w: &mut W) -> Result<(), Box<dyn Error + Sync + Send>>
+where
+    W: Write,
+{
+    let interval = types::interval_from_sql(raw)?;
+    let months = interval.months();
+    let fields = [
+        (months / 12, "year", "years"),
+        (months % 12, "mon", "mons"),
+        (interval.days(), "day", "days"),
+    ];
+
+    let mut empty = true;
+    let mut negative = false;
+    for (value, singular, plural) in fields {
+        if value == 0 {
+            continue;
+        }
+        if !empty {
+            w.write_char(' ')?;
+        }
+        let sign = if negative && value > 0 { "+" } else { "" };
+        let unit = if value == 1 { singular } else { plural };
+        write!(w, "{}{} {}", sign, value, unit)?;
+        empty = false;
+        negative = value < 0;
+    }
+
+    let usec = interval.microseconds();
+    if usec != 0 || empty {
+        if !empty {
+            w.write_char(' ')?;
+        }
+        if usec < 0 {
+            w.write_char('-')?;
+        } else if negative {
+            w.write_char('+')?;
+        }
+        time(usec.unsigned_abs() as i64, w)?;
+    }
+    Ok(())
+}
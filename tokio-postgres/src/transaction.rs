@@ -1,18 +1,22 @@
+use crate::bulk_load::BulkLoader;
 use crate::codec::FrontendMessage;
 use crate::connection::RequestMessages;
 use crate::copy_out::CopyOutStream;
+use crate::cursor::CursorStream;
+use crate::large_object::{LargeObject, Mode};
+use crate::prepared_xact::{self, PreparedXact};
 use crate::query::RowStream;
 #[cfg(feature = "runtime")]
 use crate::tls::MakeTlsConnect;
 use crate::tls::TlsConnect;
-use crate::types::{BorrowToSql, ToSql, Type};
+use crate::types::{BorrowToSql, Format, Oid, ToSql, Type};
 #[cfg(feature = "runtime")]
 use crate::Socket;
 use crate::{
-    bind, query, slice_iter, CancelToken, Client, CopyInSink, Error, Portal, Row,
+    bind, query, slice_iter, CancelToken, Client, Column, CopyInSink, Error, Portal, Row,
     SimpleQueryMessage, Statement, ToStatement,
 };
-use bytes::Buf;
+use bytes::{Buf, Bytes};
 use futures_util::TryStreamExt;
 use postgres_protocol::message::frontend;
 use tokio::io::{AsyncRead, AsyncWrite};
@@ -88,6 +92,28 @@ impl<'a> Transaction<'a> {
         self.client.batch_execute(&query).await
     }
 
+    /// Consumes the transaction, preparing it for two-phase commit with the specified global identifier.
+    ///
+    /// The transaction is dissociated from the connection, and must later be completed with `Client::commit_prepared`
+    /// or `Client::rollback_prepared`, possibly from a different connection. If preparation fails, the transaction is
+    /// rolled back. The server's `max_prepared_transactions` setting must be nonzero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called on a nested transaction.
+    pub async fn prepare_transaction(mut self, gid: &str) -> Result<PreparedXact, Error> {
+        assert!(
+            self.savepoint.is_none(),
+            "a nested transaction cannot be prepared"
+        );
+
+        self.done = true;
+        self.client
+            .batch_execute(&prepared_xact::prepare_query(gid))
+            .await?;
+        prepared_xact::get(self.client, gid).await
+    }
+
     /// Like `Client::prepare`.
     pub async fn prepare(&self, query: &str) -> Result<Statement, Error> {
         self.client.prepare(query).await
@@ -149,6 +175,25 @@ impl<'a> Transaction<'a> {
         self.client.query_raw(statement, params).await
     }
 
+    /// Like `Client::query_raw_with_formats`.
+    pub async fn query_raw_with_formats<T, P, I, F>(
+        &self,
+        statement: &T,
+        params: I,
+        formats: F,
+    ) -> Result<RowStream, Error>
+    where
+        T: ?Sized + ToStatement,
+        P: BorrowToSql,
+        I: IntoIterator<Item = P>,
+        I::IntoIter: ExactSizeIterator,
+        F: FnMut(&Column) -> Format,
+    {
+        self.client
+            .query_raw_with_formats(statement, params, formats)
+            .await
+    }
+
     /// Like `Client::execute`.
     pub async fn execute<T>(
         &self,
@@ -172,6 +217,42 @@ impl<'a> Transaction<'a> {
         self.client.execute_raw(statement, params).await
     }
 
+    /// Like `Client::query_named`.
+    pub async fn query_named(
+        &self,
+        query: &str,
+        params: &[(&str, &(dyn ToSql + Sync))],
+    ) -> Result<Vec<Row>, Error> {
+        self.client.query_named(query, params).await
+    }
+
+    /// Like `Client::query_one_named`.
+    pub async fn query_one_named(
+        &self,
+        query: &str,
+        params: &[(&str, &(dyn ToSql + Sync))],
+    ) -> Result<Row, Error> {
+        self.client.query_one_named(query, params).await
+    }
+
+    /// Like `Client::query_opt_named`.
+    pub async fn query_opt_named(
+        &self,
+        query: &str,
+        params: &[(&str, &(dyn ToSql + Sync))],
+    ) -> Result<Option<Row>, Error> {
+        self.client.query_opt_named(query, params).await
+    }
+
+    /// Like `Client::execute_named`.
+    pub async fn execute_named(
+        &self,
+        query: &str,
+        params: &[(&str, &(dyn ToSql + Sync))],
+    ) -> Result<u64, Error> {
+        self.client.execute_named(query, params).await
+    }
+
     /// Binds a statement to a set of parameters, creating a `Portal` which can be incrementally queried.
     ///
     /// Portals only last for the duration of the transaction in which they are created, and can only be used on the
@@ -202,7 +283,28 @@ impl<'a> Transaction<'a> {
         I::IntoIter: ExactSizeIterator,
     {
         let statement = statement.__convert().into_statement(self.client).await?;
-        bind::bind(self.client.inner(), statement, params).await
+        bind::bind(self.client.inner(), statement, params, None).await
+    }
+
+    /// Like `bind_raw`, but allows the format of each result column to be selected.
+    ///
+    /// Rows fetched from the portal should be read as described in `Client::query_raw_with_formats`.
+    pub async fn bind_raw_with_formats<P, T, I, F>(
+        &self,
+        statement: &T,
+        params: I,
+        formats: F,
+    ) -> Result<Portal, Error>
+    where
+        T: ?Sized + ToStatement,
+        P: BorrowToSql,
+        I: IntoIterator<Item = P>,
+        I::IntoIter: ExactSizeIterator,
+        F: FnMut(&Column) -> Format,
+    {
+        let statement = statement.__convert().into_statement(self.client).await?;
+        let formats = query::result_formats(&statement, formats);
+        bind::bind(self.client.inner(), statement, params, Some(formats)).await
     }
 
     /// Continues execution of a portal, returning a stream of the resulting rows.
@@ -227,6 +329,32 @@ impl<'a> Transaction<'a> {
         query::query_portal(self.client.inner(), portal, max_rows).await
     }
 
+    /// Binds a statement to a set of parameters, returning a stream which transparently fetches the resulting rows in
+    /// batches of `fetch_size`.
+    ///
+    /// This is a convenience wrapper over `bind` and `query_portal`. If `fetch_size` is 0 or negative, all rows will be
+    /// fetched at once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of parameters provided does not match the number expected.
+    pub async fn cursor<T>(
+        &self,
+        statement: &T,
+        params: &[&(dyn ToSql + Sync)],
+        fetch_size: i32,
+    ) -> Result<CursorStream<'_>, Error>
+    where
+        T: ?Sized + ToStatement,
+    {
+        let portal = self.bind(statement, params).await?;
+        Ok(CursorStream::portal(
+            self.client.inner(),
+            portal,
+            fetch_size,
+        ))
+    }
+
     /// Like `Client::copy_in`.
     pub async fn copy_in<T, U>(&self, statement: &T) -> Result<CopyInSink<U>, Error>
     where
@@ -254,6 +382,44 @@ impl<'a> Transaction<'a> {
         self.client.batch_execute(query).await
     }
 
+    /// Like `Client::function_call`.
+    pub async fn function_call(
+        &self,
+        function: Oid,
+        args: &[(&(dyn ToSql + Sync), Type)],
+    ) -> Result<Option<Bytes>, Error> {
+        self.client.function_call(function, args).await
+    }
+
+    /// Creates a new, empty large object, returning its OID.
+    pub async fn create_large_object(&self) -> Result<Oid, Error> {
+        self.client
+            .query_one("SELECT lo_create(0)", &[])
+            .await?
+            .try_get(0)
+    }
+
+    /// Opens the large object with the specified OID.
+    pub async fn open_large_object(&self, oid: Oid, mode: Mode) -> Result<LargeObject<'_>, Error> {
+        LargeObject::open(self.client.inner(), oid, mode).await
+    }
+
+    /// Deletes the large object with the specified OID.
+    pub async fn unlink_large_object(&self, oid: Oid) -> Result<(), Error> {
+        self.client.execute("SELECT lo_unlink($1)", &[&oid]).await?;
+        Ok(())
+    }
+
+    /// Returns a builder for a bulk load of rows into a table.
+    ///
+    /// The table name is included in the generated SQL as is, and so may be schema-qualified but must be quoted if
+    /// necessary. Conflicts are detected on the specified key columns, which must have a unique index. If no key columns
+    /// are specified, any unique constraint violation is treated as a conflict, which is only supported by
+    /// `ConflictAction::Nothing` and `ConflictAction::Error`.
+    pub fn bulk_loader(&self, table: &str, key_columns: &[&str]) -> BulkLoader<'_> {
+        BulkLoader::new(self.client, table, key_columns)
+    }
+
     /// Like `Client::cancel_token`.
     pub fn cancel_token(&self) -> CancelToken {
         self.client.cancel_token()
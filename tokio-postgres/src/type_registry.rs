@@ -0,0 +1,116 @@
+use crate::types::{Kind, Oid, Type};
+use crate::{prepare, Client, Error};
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// A cache of the definitions of types which are not built into Postgres, such as enums, composites, and the types
+/// defined by extensions.
+///
+/// Each client loads the definitions of the custom types it encounters from the database and caches them. By default
+/// every connection has its own cache, but a registry set with [`Config::type_registry`] is shared by every connection
+/// created from that configuration, so a type loaded by any one of them is available to the rest without further
+/// catalog queries.
+///
+/// Definitions are keyed by OID, and the OID assigned to a custom type differs between databases, even ones in the
+/// same cluster. A registry must only be shared between connections to the same database; sharing it with a
+/// connection to any other database will cause values to be decoded as the wrong type.
+///
+/// Cloning a registry produces a handle to the same cache.
+///
+/// [`Config::type_registry`]: crate::Config::type_registry
+#[derive(Debug, Clone, Default)]
+pub struct TypeRegistry(Arc<Mutex<HashMap<Oid, Type>>>);
+
+impl PartialEq for TypeRegistry {
+    fn eq(&self, other: &TypeRegistry) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for TypeRegistry {}
+
+impl TypeRegistry {
+    /// Creates a new, empty registry.
+    pub fn new() -> TypeRegistry {
+        TypeRegistry::default()
+    }
+
+    /// Returns the cached definition of the type with the specified OID.
+    pub fn get(&self, oid: Oid) -> Option<Type> {
+        self.0.lock().get(&oid).cloned()
+    }
+
+    pub(crate) fn insert(&self, type_: &Type) {
+        self.0.lock().insert(type_.oid(), type_.clone());
+    }
+
+    /// Removes all cached definitions.
+    ///
+    /// This is equivalent to calling [`Client::clear_type_cache`] on any of the clients sharing the registry.
+    pub fn clear(&self) {
+        self.0.lock().clear();
+    }
+
+    /// Loads the definitions of the named types, along with any types they depend on, into the registry.
+    ///
+    /// Names are resolved by the server as with a cast to `regtype`, so they may be schema-qualified or refer to array
+    /// types (e.g. `public.my_enum[]`). The definitions are loaded from the database `client` is connected to, so the
+    /// registry should only be used by connections to that same database.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn async_main(client: &tokio_postgres::Client) -> Result<(), tokio_postgres::Error> {
+    /// use tokio_postgres::TypeRegistry;
+    ///
+    /// let registry = TypeRegistry::new();
+    /// registry.load(client, ["my_enum", "hstore"]).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn load<I, S>(&self, client: &Client, names: I) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let names = names
+            .into_iter()
+            .map(|name| name.as_ref().to_string())
+            .collect::<Vec<_>>();
+
+        let rows = client
+            .query("SELECT unnest($1::TEXT[])::REGTYPE::OID", &[&names])
+            .await?;
+
+        let oids = rows
+            .iter()
+            .map(|row| row.try_get(0))
+            .collect::<Result<Vec<_>, _>>()?;
+        for type_ in prepare::get_types(client.inner(), &oids).await? {
+            self.insert_rec(&type_);
+        }
+
+        Ok(())
+    }
+
+    fn insert_rec(&self, type_: &Type) {
+        if Type::from_oid(type_.oid()).is_some() {
+            return;
+        }
+        self.insert(type_);
+
+        match type_.kind() {
+            Kind::Array(member)
+            | Kind::Range(member)
+            | Kind::Multirange(member)
+            | Kind::Domain(member) => self.insert_rec(member),
+            Kind::Composite(fields) => {
+                for field in fields {
+                    self.insert_rec(field.type_());
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use crate::connect;
+use arrow_array_53::cast::AsArray;
+use arrow_array_53::types::{Date32Type, Int32Type, Int64Type, TimestampMicrosecondType};
+use arrow_array_53::{Array, Int32Array, StringArray};
+use futures_util::{pin_mut, TryStreamExt};
+use std::sync::Arc;
+use tokio_postgres::arrow::{
+    BinaryCopyRecordBatchStream, RecordBatch, RecordBatchBuilder, RecordBatchStream,
+};
+use tokio_postgres::binary_copy::{BinaryCopyInWriter, BinaryCopyOutStream};
+use tokio_postgres::types::{Format, Type};
+
+#[tokio::test]
+async fn query_batches() {
+    let client = connect("user=postgres").await;
+
+    let rows = client
+        .query_raw(
+            "SELECT i, 'value ' || i, CASE WHEN i % 2 = 0 THEN NULL ELSE i::INT8 END, \
+                '2000-01-02'::DATE + i, '1970-01-01 00:00:01'::TIMESTAMP \
+             FROM generate_series(1, 5) i",
+            &[] as &[i32],
+        )
+        .await
+        .unwrap();
+    let stream = RecordBatchStream::new(rows, 2).unwrap();
+    assert_eq!(stream.schema().fields().len(), 5);
+    assert_eq!(stream.schema().field(0).name(), "i");
+
+    let batches = stream.try_collect::<Vec<_>>().await.unwrap();
+    assert_eq!(
+        batches
+            .iter()
+            .map(RecordBatch::num_rows)
+            .collect::<Vec<_>>(),
+        [2, 2, 1]
+    );
+
+    let batch = &batches[0];
+    assert_eq!(
+        batch.column(0).as_primitive::<Int32Type>().values(),
+        &[1, 2]
+    );
+    assert_eq!(batch.column(1).as_string::<i32>().value(1), "value 2");
+    let int8 = batch.column(2).as_primitive::<Int64Type>();
+    assert_eq!(int8.value(0), 1);
+    assert!(int8.is_null(1));
+    assert_eq!(
+        batch.column(3).as_primitive::<Date32Type>().value(0),
+        10_959
+    );
+    assert_eq!(
+        batch
+            .column(4)
+            .as_primitive::<TimestampMicrosecondType>()
+            .value(0),
+        1_000_000
+    );
+}
+
+#[tokio::test]
+async fn copy_round_trip() {
+    let client = connect("user=postgres").await;
+
+    client
+        .batch_execute("CREATE TEMPORARY TABLE foo (id INT, bar TEXT)")
+        .await
+        .unwrap();
+
+    let mut builder =
+        RecordBatchBuilder::from_types(&["id", "bar"], &[Type::INT4, Type::TEXT]).unwrap();
+    let batch = RecordBatch::try_new(
+        builder.schema(),
+        vec![
+            Arc::new(Int32Array::from(vec![1, 2, 3])),
+            Arc::new(StringArray::from(vec![Some("a"), None, Some("c")])),
+        ],
+    )
+    .unwrap();
+
+    let sink = client
+        .copy_in("COPY foo (id, bar) FROM STDIN BINARY")
+        .await
+        .unwrap();
+    let writer = BinaryCopyInWriter::new(sink, &[Type::INT4, Type::TEXT]);
+    pin_mut!(writer);
+    writer.as_mut().write_record_batch(&batch).await.unwrap();
+    assert_eq!(writer.finish().await.unwrap(), 3);
+
+    let stream = client
+        .copy_out("COPY (SELECT id, bar FROM foo ORDER BY id) TO STDOUT BINARY")
+        .await
+        .unwrap();
+    let stream = BinaryCopyOutStream::new(stream, &[Type::INT4, Type::TEXT]);
+    let batches = BinaryCopyRecordBatchStream::new(stream, &["id", "bar"], 1024)
+        .unwrap()
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    assert_eq!(batches, [batch]);
+
+    let rows = client.query("SELECT id, bar FROM foo", &[]).await.unwrap();
+    for row in &rows {
+        builder.append_row(row).unwrap();
+    }
+    assert_eq!(builder.len(), 3);
+    assert_eq!(builder.finish().num_rows(), 3);
+    assert!(builder.is_empty());
+}
+
+#[tokio::test]
+async fn errors() {
+    let client = connect("user=postgres").await;
+
+    let rows = client
+        .query_raw("SELECT 1::INT4, '{}'::INT4[]", &[] as &[i32])
+        .await
+        .unwrap();
+    let err = RecordBatchStream::new(rows, 10).err().unwrap();
+    assert!(err.to_string().contains("column 1"), "{}", err);
+
+    let rows = client
+        .query_raw_with_formats("SELECT 1::INT4", &[] as &[i32], |_| Format::Text)
+        .await
+        .unwrap();
+    let rows = rows.try_collect::<Vec<_>>().await.unwrap();
+    let mut builder = RecordBatchBuilder::new(rows[0].columns()).unwrap();
+    let err = builder.append_row(&rows[0]).unwrap_err();
+    assert!(err.to_string().contains("text format"), "{}", err);
+
+    client
+        .batch_execute("CREATE TEMPORARY TABLE foo (id INT)")
+        .await
+        .unwrap();
+    let batch = RecordBatch::try_from_iter(vec![(
+        "id",
+        Arc::new(StringArray::from(vec!["1"])) as Arc<dyn Array>,
+    )])
+    .unwrap();
+    let sink = client
+        .copy_in("COPY foo (id) FROM STDIN BINARY")
+        .await
+        .unwrap();
+    let writer = BinaryCopyInWriter::new(sink, &[Type::INT4]);
+    pin_mut!(writer);
+    let err = writer
+        .as_mut()
+        .write_record_batch(&batch)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("parameter 0"), "{}", err);
+}
@@ -1,6 +1,8 @@
 use crate::connect;
 use futures_util::{pin_mut, TryStreamExt};
+use std::sync::{Arc, Mutex};
 use tokio_postgres::binary_copy::{BinaryCopyInWriter, BinaryCopyOutStream};
+use tokio_postgres::error::SqlState;
 use tokio_postgres::types::Type;
 
 #[tokio::test]
@@ -73,6 +75,69 @@ async fn write_many_rows() {
     }
 }
 
+#[tokio::test]
+async fn write_progress() {
+    let client = connect("user=postgres").await;
+
+    client
+        .batch_execute("CREATE TEMPORARY TABLE foo (id INT NOT NULL, bar TEXT)")
+        .await
+        .unwrap();
+
+    let progress = Arc::new(Mutex::new(vec![]));
+    let sink = client
+        .copy_in("COPY foo (id, bar) FROM STDIN BINARY")
+        .await
+        .unwrap();
+    let writer = BinaryCopyInWriter::new(sink, &[Type::INT4, Type::TEXT]);
+    pin_mut!(writer);
+    writer.as_mut().set_progress_callback({
+        let progress = progress.clone();
+        move |rows, bytes| progress.lock().unwrap().push((rows, bytes))
+    });
+
+    for i in 0..1_000i32 {
+        writer
+            .as_mut()
+            .write(&[&i, &format!("the value for {}", i)])
+            .await
+            .unwrap();
+    }
+    assert_eq!(writer.rows_written(), 1_000);
+    writer.as_mut().finish().await.unwrap();
+
+    {
+        let progress = progress.lock().unwrap();
+        assert!(progress.len() > 1);
+        assert!(progress
+            .windows(2)
+            .all(|w| w[0].0 < w[1].0 && w[0].1 < w[1].1));
+        assert_eq!(*progress.last().unwrap(), (1_000, writer.bytes_sent()));
+    }
+
+    let stream = client
+        .copy_out("COPY foo (id, bar) TO STDOUT BINARY")
+        .await
+        .unwrap();
+    let stream = BinaryCopyOutStream::new(stream, &[Type::INT4, Type::TEXT]);
+    let rows = stream.try_collect::<Vec<_>>().await.unwrap();
+    assert_eq!(rows.len(), 1_000);
+
+    let sink = client
+        .copy_in("COPY foo (id, bar) FROM STDIN BINARY")
+        .await
+        .unwrap();
+    let writer = BinaryCopyInWriter::new(sink, &[Type::INT4, Type::TEXT]);
+    pin_mut!(writer);
+    for i in 1..=10i32 {
+        let id = if i == 7 { None } else { Some(i) };
+        writer.as_mut().write(&[&id, &"foo"]).await.unwrap();
+    }
+    let err = writer.finish().await.unwrap_err();
+    assert_eq!(err.code(), Some(&SqlState::NOT_NULL_VIOLATION));
+    assert_eq!(err.copy_error_row(), Some(7));
+}
+
 #[tokio::test]
 async fn write_big_rows() {
     let client = connect("user=postgres").await;
@@ -201,3 +266,74 @@ async fn read_big_rows() {
         assert_eq!(row.get::<&[u8]>(1), &vec![i as u8; 128 * 1024][..]);
     }
 }
+
+#[tokio::test]
+async fn write_for_table() {
+    let client = connect("user=postgres").await;
+
+    client
+        .batch_execute(
+            "CREATE TYPE pg_temp.mood AS ENUM ('happy', 'sad');
+            CREATE DOMAIN pg_temp.positive AS INT CHECK (VALUE > 0);
+            CREATE TEMPORARY TABLE foo (id INT, dropped INT, mood pg_temp.mood, bar TEXT, n pg_temp.positive);
+            ALTER TABLE foo DROP COLUMN dropped;",
+        )
+        .await
+        .unwrap();
+
+    let writer = BinaryCopyInWriter::for_table(&client, "foo", &[])
+        .await
+        .unwrap();
+    pin_mut!(writer);
+    let err = writer
+        .as_mut()
+        .write(&[&1i32, &None::<&str>, &"foobar", &1i32])
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("mood"), "{}", err);
+    assert_eq!(writer.rows_written(), 0);
+    writer.finish().await.unwrap();
+
+    let writer = BinaryCopyInWriter::for_table(&client, "foo", &["n"])
+        .await
+        .unwrap();
+    pin_mut!(writer);
+    writer.as_mut().write(&[&1i32]).await.unwrap();
+    assert_eq!(writer.finish().await.unwrap(), 1);
+
+    let writer = BinaryCopyInWriter::for_table(&client, "foo", &["n"])
+        .await
+        .unwrap();
+    pin_mut!(writer);
+    writer.as_mut().write(&[&0i32]).await.unwrap();
+    let err = writer.finish().await.unwrap_err();
+    assert_eq!(err.code(), Some(&SqlState::CHECK_VIOLATION));
+
+    let writer = BinaryCopyInWriter::for_table(&client, "foo", &["bar", "id"])
+        .await
+        .unwrap();
+    pin_mut!(writer);
+    let err = writer
+        .as_mut()
+        .write(&[&"foobar", &1i64])
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("parameter 1"), "{}", err);
+    writer.as_mut().write(&[&"foobar", &1i32]).await.unwrap();
+    writer.as_mut().write(&[&"baz", &2i32]).await.unwrap();
+    writer.finish().await.unwrap();
+
+    let rows = client
+        .query("SELECT id, bar FROM foo WHERE n IS NULL ORDER BY id", &[])
+        .await
+        .unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].get::<_, &str>(1), "foobar");
+    assert_eq!(rows[1].get::<_, &str>(1), "baz");
+
+    let err = BinaryCopyInWriter::for_table(&client, "foo", &["missing"])
+        .await
+        .err()
+        .unwrap();
+    assert!(err.to_string().contains("missing"), "{}", err);
+}
@@ -0,0 +1,298 @@
+use crate::connect;
+use futures_util::{pin_mut, TryStreamExt};
+use serde_1::Deserialize;
+use std::collections::HashMap;
+use tokio_postgres::binary_copy::BinaryCopyOutStream;
+use tokio_postgres::types::{Format, Type};
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(crate = "serde_1")]
+struct Person {
+    id: i32,
+    name: String,
+    nickname: Option<String>,
+    tags: Vec<String>,
+}
+
+#[tokio::test]
+async fn struct_by_name() {
+    let client = connect("user=postgres").await;
+
+    let row = client
+        .query_one(
+            "SELECT ARRAY['a', 'b'] AS tags, NULL::TEXT AS nickname, 'alice' AS name, 1 AS id, \
+             true AS extra",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let person = row.deserialize::<Person>().unwrap();
+    assert_eq!(
+        person,
+        Person {
+            id: 1,
+            name: "alice".to_string(),
+            nickname: None,
+            tags: vec!["a".to_string(), "b".to_string()],
+        }
+    );
+}
+
+#[tokio::test]
+async fn borrowed_tuple() {
+    let client = connect("user=postgres").await;
+
+    let row = client
+        .query_one(
+            "SELECT 1::INT2, 'hello'::TEXT, '\\x0102'::BYTEA, 2.5::FLOAT8",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let (a, b, c, d) = row.deserialize::<(i64, &str, &[u8], f64)>().unwrap();
+    assert_eq!(a, 1);
+    assert_eq!(b, "hello");
+    assert_eq!(c, &[1, 2]);
+    assert_eq!(d, 2.5);
+}
+
+#[tokio::test]
+async fn text_representation() {
+    let client = connect("user=postgres").await;
+    client
+        .batch_execute("SET TIME ZONE 'UTC'; SET DateStyle = 'ISO'; SET IntervalStyle = 'postgres'")
+        .await
+        .unwrap();
+
+    let values = [
+        "'0'::NUMERIC",
+        "'-12345.678900'::NUMERIC",
+        "'0.00000001'::NUMERIC",
+        "'100000000'::NUMERIC",
+        "'NaN'::NUMERIC",
+        "'-123.45e-20'::NUMERIC",
+        "'2023-04-05'::DATE",
+        "'0044-03-15 BC'::DATE",
+        "'infinity'::DATE",
+        "'13:14:15.5'::TIME",
+        "'13:14:15-05:30'::TIMETZ",
+        "'2023-04-05 06:07:08.000009'::TIMESTAMP",
+        "'1999-12-31 23:59:59 BC'::TIMESTAMP",
+        "'2023-04-05 06:07:08+02'::TIMESTAMPTZ",
+        "'-infinity'::TIMESTAMPTZ",
+        "'1 year 2 mons -3 days 04:05:06.789'::INTERVAL",
+        "'-1 day'::INTERVAL",
+        "'-04:05:06'::INTERVAL",
+        "'0'::INTERVAL",
+        "'-1 year 2 mons 3 days -00:00:01'::INTERVAL",
+        "'a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11'::UUID",
+        "'192.168.0.1'::INET",
+        "'192.168.0.1/24'::INET",
+        "'2001:db8::/32'::CIDR",
+        "'08:00:2b:01:02:03'::MACADDR",
+    ];
+
+    for value in values {
+        let row = client
+            .query_one(&*format!("SELECT {0}, format('%s', {0})", value), &[])
+            .await
+            .unwrap();
+        let (actual, expected) = row.deserialize::<(String, String)>().unwrap();
+        assert_eq!(actual, expected, "{}", value);
+    }
+}
+
+#[tokio::test]
+async fn multidimensional_array() {
+    let client = connect("user=postgres").await;
+
+    let row = client
+        .query_one(
+            "SELECT '{{1, 2, 3}, {4, NULL, 6}}'::INT4[][], '{}'::INT4[]",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let (matrix, empty) = row
+        .deserialize::<(Vec<Vec<Option<i32>>>, Vec<i32>)>()
+        .unwrap();
+    assert_eq!(
+        matrix,
+        vec![
+            vec![Some(1), Some(2), Some(3)],
+            vec![Some(4), None, Some(6)]
+        ]
+    );
+    assert!(empty.is_empty());
+}
+
+#[tokio::test]
+async fn json_passthrough() {
+    #[derive(Deserialize, Debug, PartialEq)]
+    #[serde(crate = "serde_1")]
+    struct Payload {
+        a: i32,
+        b: Vec<String>,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    #[serde(crate = "serde_1")]
+    struct Document {
+        json: Payload,
+        jsonb: HashMap<String, i32>,
+    }
+
+    let client = connect("user=postgres").await;
+
+    let row = client
+        .query_one(
+            r#"SELECT '{"a": 1, "b": ["x"]}'::JSON AS json, '{"c": 2}'::JSONB AS jsonb"#,
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let document = row.deserialize::<Document>().unwrap();
+    assert_eq!(
+        document,
+        Document {
+            json: Payload {
+                a: 1,
+                b: vec!["x".to_string()],
+            },
+            jsonb: vec![("c".to_string(), 2)].into_iter().collect(),
+        }
+    );
+}
+
+#[tokio::test]
+async fn enum_and_composite() {
+    #[derive(Deserialize, Debug, PartialEq)]
+    #[serde(crate = "serde_1", rename_all = "lowercase")]
+    enum Mood {
+        Sad,
+        Ok,
+        Happy,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    #[serde(crate = "serde_1")]
+    struct InventoryItem {
+        name: String,
+        supplier: i32,
+        price: Option<f64>,
+    }
+
+    let client = connect("user=postgres").await;
+
+    client
+        .batch_execute(
+            "CREATE TYPE pg_temp.mood AS ENUM ('sad', 'ok', 'happy');
+             CREATE TYPE pg_temp.inventory_item AS (
+                 name TEXT,
+                 supplier INTEGER,
+                 price DOUBLE PRECISION
+             );",
+        )
+        .await
+        .unwrap();
+
+    let row = client
+        .query_one(
+            "SELECT 'happy'::mood, ROW('widget', 3, NULL)::inventory_item",
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let (mood, item) = row.deserialize::<(Mood, InventoryItem)>().unwrap();
+    assert_eq!(mood, Mood::Happy);
+    assert_eq!(
+        item,
+        InventoryItem {
+            name: "widget".to_string(),
+            supplier: 3,
+            price: None,
+        }
+    );
+}
+
+#[tokio::test]
+async fn column_error() {
+    let client = connect("user=postgres").await;
+
+    let row = client
+        .query_one("SELECT 1 AS id, 'foo' AS name", &[])
+        .await
+        .unwrap();
+
+    let err = row.deserialize::<(i32, i32)>().unwrap_err();
+    assert!(err.to_string().contains("error deserializing column 1"));
+
+    let err = row.deserialize::<Person>().unwrap_err();
+    assert!(err.to_string().contains("missing field"));
+}
+
+#[tokio::test]
+async fn text_format_column() {
+    let client = connect("user=postgres").await;
+
+    let rows = client
+        .query_raw_with_formats("SELECT 1 AS id, 'foo' AS name", &[] as &[i32], |column| {
+            match column.name() {
+                "name" => Format::Text,
+                _ => Format::Binary,
+            }
+        })
+        .await
+        .unwrap();
+    let rows = rows.try_collect::<Vec<_>>().await.unwrap();
+
+    let err = rows[0].deserialize::<(i32, String)>().unwrap_err();
+    assert!(err.to_string().contains("text format"), "{}", err);
+}
+
+#[tokio::test]
+async fn binary_copy_row() {
+    #[derive(Deserialize, Debug, PartialEq)]
+    #[serde(crate = "serde_1")]
+    struct Foo {
+        id: i32,
+        bar: Option<String>,
+    }
+
+    let client = connect("user=postgres").await;
+
+    client
+        .batch_execute(
+            "CREATE TEMPORARY TABLE foo (id INT, bar TEXT);
+             INSERT INTO foo (id, bar) VALUES (1, 'foobar'), (2, NULL);",
+        )
+        .await
+        .unwrap();
+
+    let stream = client
+        .copy_out("COPY foo (id, bar) TO STDOUT BINARY")
+        .await
+        .unwrap();
+    let stream = BinaryCopyOutStream::new(stream, &[Type::INT4, Type::TEXT]);
+    pin_mut!(stream);
+
+    let mut rows = vec![];
+    while let Some(row) = stream.try_next().await.unwrap() {
+        rows.push(row.deserialize::<Foo>().unwrap());
+    }
+    assert_eq!(
+        rows,
+        vec![
+            Foo {
+                id: 1,
+                bar: Some("foobar".to_string()),
+            },
+            Foo { id: 2, bar: None },
+        ]
+    );
+}
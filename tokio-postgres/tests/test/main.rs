@@ -7,22 +7,33 @@ use futures_util::{
 };
 use pin_project_lite::pin_project;
 use std::fmt::Write;
+use std::io::SeekFrom;
 use std::pin::Pin;
+use std::sync::{Arc, Mutex};
 use std::task::{Context, Poll};
 use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeekExt, AsyncWriteExt, ReadBuf};
 use tokio::net::TcpStream;
 use tokio::time;
+use tokio_postgres::bulk_load::ConflictAction;
 use tokio_postgres::error::SqlState;
+use tokio_postgres::large_object::Mode;
 use tokio_postgres::tls::{NoTls, NoTlsStream};
-use tokio_postgres::types::{Kind, Type};
+use tokio_postgres::types::{Format, Kind, Point, ToSql, Type};
 use tokio_postgres::{
-    AsyncMessage, Client, Config, Connection, Error, IsolationLevel, SimpleQueryMessage,
+    AsyncMessage, Client, Config, Connection, Error, GenericClient, IsolationLevel, NamedStatement,
+    SimpleQueryMessage, TypeRegistry,
 };
 
+#[cfg(feature = "arrow")]
+mod arrow;
 mod binary_copy;
+#[cfg(feature = "serde")]
+mod de;
 mod parse;
 #[cfg(feature = "runtime")]
 mod runtime;
+mod text_copy;
 mod types;
 
 pin_project! {
@@ -328,7 +339,7 @@ async fn simple_query() {
     }
     match &messages[2] {
         SimpleQueryMessage::Row(row) => {
-            assert_eq!(row.columns().get(0).map(|c| c.name()), Some("id"));
+            assert_eq!(row.columns().first().map(|c| c.name()), Some("id"));
             assert_eq!(row.columns().get(1).map(|c| c.name()), Some("name"));
             assert_eq!(row.get(0), Some("1"));
             assert_eq!(row.get(1), Some("steven"));
@@ -337,7 +348,7 @@ async fn simple_query() {
     }
     match &messages[3] {
         SimpleQueryMessage::Row(row) => {
-            assert_eq!(row.columns().get(0).map(|c| c.name()), Some("id"));
+            assert_eq!(row.columns().first().map(|c| c.name()), Some("id"));
             assert_eq!(row.columns().get(1).map(|c| c.name()), Some("name"));
             assert_eq!(row.get(0), Some("2"));
             assert_eq!(row.get(1), Some("joe"));
@@ -351,6 +362,144 @@ async fn simple_query() {
     assert_eq!(messages.len(), 5);
 }
 
+#[tokio::test]
+async fn simple_query_typed() {
+    let client = connect("user=postgres").await;
+
+    let messages = client
+        .simple_query(
+            "SET bytea_output = 'escape';
+            SELECT 1::INT4 AS id, NULL::TEXT, 'a\\000b'::BYTEA, ARRAY['x', 'y z']",
+        )
+        .await
+        .unwrap();
+
+    match &messages[1] {
+        SimpleQueryMessage::Row(row) => {
+            assert_eq!(row.columns()[0].type_oid(), Type::INT4.oid());
+            assert_eq!(row.try_get_typed::<_, i32>("id").unwrap(), 1);
+            assert_eq!(row.try_get_typed::<_, Option<&str>>(1).unwrap(), None);
+            assert_eq!(row.try_get_typed::<_, Vec<u8>>(2).unwrap(), b"a\0b");
+            assert_eq!(
+                row.try_get_typed::<_, Vec<String>>(3).unwrap(),
+                vec!["x".to_string(), "y z".to_string()]
+            );
+            assert!(row.try_get_typed::<_, String>(0).is_err());
+            assert!(row.try_get_typed::<_, i32>(1).is_err());
+        }
+        _ => panic!("unexpected message"),
+    }
+}
+
+#[tokio::test]
+async fn simple_query_typed_custom_type() {
+    let client = connect("user=postgres").await;
+
+    client
+        .batch_execute("CREATE TYPE pg_temp.simple_mood AS ENUM ('sad', 'happy')")
+        .await
+        .unwrap();
+
+    let query = "SELECT 'happy'::pg_temp.simple_mood";
+    let messages = client.simple_query(query).await.unwrap();
+    match &messages[0] {
+        SimpleQueryMessage::Row(row) => {
+            let err = row.try_get_typed::<_, String>(0).unwrap_err();
+            assert!(err.to_string().contains("`unknown`"), "{}", err);
+            assert_eq!(row.try_get(0).unwrap(), Some("happy"));
+        }
+        _ => panic!("unexpected message"),
+    }
+
+    // preparing a statement which uses the type caches its definition
+    client.prepare(query).await.unwrap();
+    let messages = client.simple_query(query).await.unwrap();
+    match &messages[0] {
+        SimpleQueryMessage::Row(row) => {
+            let err = row.try_get_typed::<_, String>(0).unwrap_err();
+            assert!(err.to_string().contains("simple_mood`"), "{}", err);
+        }
+        _ => panic!("unexpected message"),
+    }
+}
+
+#[tokio::test]
+async fn type_registry_load() {
+    let client = connect("user=postgres").await;
+
+    client
+        .batch_execute("CREATE TYPE pg_temp.registry_mood AS ENUM ('sad', 'happy')")
+        .await
+        .unwrap();
+
+    let registry = TypeRegistry::new();
+    registry
+        .load(&client, ["pg_temp.registry_mood[]"])
+        .await
+        .unwrap();
+
+    let oid = client
+        .query_one("SELECT 'pg_temp.registry_mood'::REGTYPE::OID", &[])
+        .await
+        .unwrap()
+        .get::<_, u32>(0);
+    let type_ = registry.get(oid).unwrap();
+    assert_eq!(type_.name(), "registry_mood");
+    assert_eq!(
+        type_.kind(),
+        &Kind::Enum(vec!["sad".to_string(), "happy".to_string()])
+    );
+
+    registry.clear();
+    assert_eq!(registry.get(oid), None);
+}
+
+#[tokio::test]
+async fn shared_type_registry() {
+    let registry = TypeRegistry::new();
+    let mut config = "user=postgres".parse::<Config>().unwrap();
+    config.type_registry(registry.clone());
+
+    let mut clients = vec![];
+    for _ in 0..2 {
+        let socket = TcpStream::connect("127.0.0.1:5433").await.unwrap();
+        let (client, connection) = config.connect_raw(socket, NoTls).await.unwrap();
+        tokio::spawn(connection.map(|r| r.unwrap()));
+        clients.push(client);
+    }
+
+    let stmt = clients[0]
+        .prepare("SELECT $1::information_schema.cardinal_number")
+        .await
+        .unwrap();
+    let type_ = &stmt.params()[0];
+    assert_eq!(registry.get(type_.oid()).as_ref(), Some(type_));
+    assert_eq!(typeinfo_statements(&clients[0]).await, 1);
+
+    let stmt = clients[1]
+        .prepare("SELECT $1::information_schema.cardinal_number")
+        .await
+        .unwrap();
+    assert_eq!(&stmt.params()[0], type_);
+    assert_eq!(typeinfo_statements(&clients[1]).await, 0);
+
+    clients[1].clear_type_cache();
+    assert_eq!(registry.get(type_.oid()), None);
+}
+
+async fn typeinfo_statements(client: &Client) -> i64 {
+    let rows = client
+        .simple_query(
+            "SELECT count(*) FROM pg_prepared_statements WHERE statement LIKE 'WITH RECURSIVE types%'",
+        )
+        .await
+        .unwrap();
+    match &rows[0] {
+        SimpleQueryMessage::Row(row) => row.get(0).unwrap().parse().unwrap(),
+        _ => panic!("unexpected message"),
+    }
+}
+
 #[tokio::test]
 async fn cancel_query_raw() {
     let client = connect("user=postgres").await;
@@ -662,6 +811,38 @@ async fn copy_in_error() {
     assert_eq!(rows.len(), 0);
 }
 
+#[tokio::test]
+async fn copy_in_progress() {
+    let client = connect("user=postgres").await;
+
+    client
+        .batch_execute("CREATE TEMPORARY TABLE foo (id INTEGER)")
+        .await
+        .unwrap();
+
+    let progress = Arc::new(Mutex::new(vec![]));
+    let mut data = BytesMut::new();
+    for i in 0..2_000 {
+        writeln!(data, "{}", i).unwrap();
+    }
+    writeln!(data, "bogus").unwrap();
+
+    let sink = client.copy_in("COPY foo FROM STDIN").await.unwrap();
+    pin_mut!(sink);
+    sink.as_mut().set_progress_callback({
+        let progress = progress.clone();
+        move |bytes| progress.lock().unwrap().push(bytes)
+    });
+    sink.send(data.freeze()).await.unwrap();
+    let bytes_sent = sink.bytes_sent();
+    let err = sink.finish().await.unwrap_err();
+
+    assert_eq!(bytes_sent, 8_896);
+    assert_eq!(*progress.lock().unwrap(), [bytes_sent]);
+    assert_eq!(err.code(), Some(&SqlState::INVALID_TEXT_REPRESENTATION));
+    assert_eq!(err.copy_error_row(), Some(2_001));
+}
+
 #[tokio::test]
 async fn copy_out() {
     let client = connect("user=postgres").await;
@@ -692,6 +873,27 @@ async fn copy_out() {
     assert_eq!(&data[..], b"1\tjim\n2\tjoe\n");
 }
 
+#[tokio::test]
+async fn copy_out_progress() {
+    let client = connect("user=postgres").await;
+
+    let stream = client
+        .copy_out("COPY (SELECT generate_series(1, 1000)) TO STDOUT")
+        .await
+        .unwrap();
+    pin_mut!(stream);
+    assert_eq!(stream.bytes_received(), 0);
+    assert_eq!(stream.rows_received(), 0);
+
+    let mut data = BytesMut::new();
+    while let Some(chunk) = stream.try_next().await.unwrap() {
+        data.extend_from_slice(&chunk);
+        assert_eq!(stream.bytes_received(), data.len() as u64);
+    }
+    assert_eq!(stream.bytes_received(), 3_893);
+    assert_eq!(stream.rows_received(), 1_000);
+}
+
 #[tokio::test]
 async fn notices() {
     let long_name = "x".repeat(65);
@@ -732,6 +934,38 @@ async fn notices() {
     );
 }
 
+#[tokio::test]
+async fn notice_handler() {
+    let notices = Arc::new(Mutex::new(vec![]));
+
+    let socket = TcpStream::connect("127.0.0.1:5433").await.unwrap();
+    let mut config = format!("user=postgres application_name={}", "x".repeat(65))
+        .parse::<Config>()
+        .unwrap();
+    config.notice_handler({
+        let notices = notices.clone();
+        move |notice| notices.lock().unwrap().push(notice.message().to_string())
+    });
+    let (client, connection) = config.connect_raw(socket, NoTls).await.unwrap();
+    let connection = tokio::spawn(connection);
+
+    client
+        .batch_execute(
+            "DO $$BEGIN RAISE NOTICE 'hello from plpgsql'; RAISE WARNING 'careful'; END$$",
+        )
+        .await
+        .unwrap();
+
+    drop(client);
+    connection.await.unwrap().unwrap();
+
+    let notices = notices.lock().unwrap();
+    assert_eq!(notices.len(), 3);
+    assert!(notices[0].starts_with("identifier \"xxx"));
+    assert_eq!(notices[1], "hello from plpgsql");
+    assert_eq!(notices[2], "careful");
+}
+
 #[tokio::test]
 async fn notifications() {
     let (client, mut connection) = connect_raw("user=postgres").await.unwrap();
@@ -767,6 +1001,407 @@ async fn notifications() {
     assert_eq!(notifications[1].payload(), "world");
 }
 
+#[tokio::test]
+async fn listen() {
+    let client = connect("user=postgres").await;
+
+    let mut foo = client.listen("listen_Foo").await.unwrap();
+    let mut foo2 = client.listen("listen_Foo").await.unwrap();
+    let mut bar = client.listen("listen bar").await.unwrap();
+
+    client
+        .batch_execute(
+            "NOTIFY \"listen_Foo\", 'hello';
+             NOTIFY \"listen bar\", 'world';
+             NOTIFY listen_foo, 'ignored';",
+        )
+        .await
+        .unwrap();
+
+    let notification = foo.next().await.unwrap();
+    assert_eq!(notification.channel(), "listen_Foo");
+    assert_eq!(notification.payload(), "hello");
+    assert_eq!(foo2.next().await.unwrap().payload(), "hello");
+    let notification = bar.next().await.unwrap();
+    assert_eq!(notification.channel(), "listen bar");
+    assert_eq!(notification.payload(), "world");
+
+    drop(foo);
+    client
+        .batch_execute("NOTIFY \"listen_Foo\", 'again'")
+        .await
+        .unwrap();
+    assert_eq!(foo2.next().await.unwrap().payload(), "again");
+
+    drop(foo2);
+    let rows = client
+        .query("SELECT pg_listening_channels()", &[])
+        .await
+        .unwrap();
+    let channels = rows.iter().map(|r| r.get(0)).collect::<Vec<&str>>();
+    assert_eq!(channels, ["listen bar"]);
+
+    drop(client);
+    assert!(bar.next().await.is_none());
+}
+
+#[tokio::test]
+async fn function_call() {
+    let client = connect("user=postgres").await;
+
+    let oid = client
+        .query_one("SELECT 'int4pl'::regproc::oid", &[])
+        .await
+        .unwrap()
+        .get::<_, u32>(0);
+
+    let result = client
+        .function_call(oid, &[(&1i32, Type::INT4), (&2i32, Type::INT4)])
+        .await
+        .unwrap();
+    assert_eq!(result.as_deref(), Some(&3i32.to_be_bytes()[..]));
+
+    let result = client
+        .function_call(oid, &[(&None::<i32>, Type::INT4), (&2i32, Type::INT4)])
+        .await
+        .unwrap();
+    assert_eq!(result, None);
+
+    let err = client
+        .function_call(oid, &[(&"foo", Type::INT4), (&2i32, Type::INT4)])
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("error serializing parameter 0"));
+
+    client.batch_execute("SELECT 1").await.unwrap();
+}
+
+#[tokio::test]
+async fn large_object() {
+    let mut client = connect("user=postgres").await;
+
+    let transaction = client.transaction().await.unwrap();
+    let oid = transaction.create_large_object().await.unwrap();
+
+    let mut object = transaction
+        .open_large_object(oid, Mode::ReadWrite)
+        .await
+        .unwrap();
+    assert_eq!(object.oid(), oid);
+    object.write_all(b"hello world").await.unwrap();
+    assert_eq!(object.tell().await.unwrap(), 11);
+
+    object.seek(SeekFrom::Start(6)).await.unwrap();
+    let mut buf = String::new();
+    object.read_to_string(&mut buf).await.unwrap();
+    assert_eq!(buf, "world");
+
+    // data requested for a larger buffer than the one the read completes with is returned by later reads
+    object.rewind().await.unwrap();
+    let mut buf = [0; 11];
+    let poll = future::poll_fn(|cx| {
+        Poll::Ready(Pin::new(&mut object).poll_read(cx, &mut ReadBuf::new(&mut buf)))
+    })
+    .await;
+    assert!(poll.is_pending());
+    let mut buf = [0; 2];
+    object.read_exact(&mut buf).await.unwrap();
+    assert_eq!(&buf, b"he");
+    assert_eq!(object.tell().await.unwrap(), 2);
+    assert_eq!(object.seek(SeekFrom::Current(1)).await.unwrap(), 3);
+    let mut buf = [0; 8];
+    let poll = future::poll_fn(|cx| {
+        Poll::Ready(Pin::new(&mut object).poll_read(cx, &mut ReadBuf::new(&mut buf)))
+    })
+    .await;
+    assert!(poll.is_pending());
+    let mut buf = [0; 1];
+    object.read_exact(&mut buf).await.unwrap();
+    assert_eq!(&buf, b"l");
+    object.write_all(b"!").await.unwrap();
+    let err = object.seek(SeekFrom::Start(u64::MAX)).await.unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("error communicating with the server"));
+    object.rewind().await.unwrap();
+    let mut buf = String::new();
+    object.read_to_string(&mut buf).await.unwrap();
+    assert_eq!(buf, "hell! world");
+
+    object.truncate(5).await.unwrap();
+    assert_eq!(object.seek(SeekFrom::End(0)).await.unwrap(), 5);
+    object.rewind().await.unwrap();
+    let mut buf = vec![];
+    object.read_to_end(&mut buf).await.unwrap();
+    assert_eq!(buf, b"hell!");
+    object.close().await.unwrap();
+
+    // the statements used by the handle are only prepared the first time an object is opened
+    let object = transaction
+        .open_large_object(oid, Mode::Read)
+        .await
+        .unwrap();
+    object.close().await.unwrap();
+    let messages = transaction
+        .simple_query(
+            "SELECT count(*) FROM pg_prepared_statements WHERE statement = 'SELECT lo_open($1, $2)'",
+        )
+        .await
+        .unwrap();
+    match &messages[0] {
+        SimpleQueryMessage::Row(row) => assert_eq!(row.get(0), Some("1")),
+        _ => panic!("unexpected message"),
+    }
+
+    transaction.unlink_large_object(oid).await.unwrap();
+    assert!(transaction
+        .open_large_object(oid, Mode::Read)
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn bulk_load() {
+    let mut client = connect("user=postgres").await;
+
+    client
+        .batch_execute(
+            "CREATE TEMPORARY TABLE foo (id INT PRIMARY KEY, name TEXT, note TEXT DEFAULT 'new');
+            INSERT INTO foo (id, name, note) VALUES (1, 'alice', 'old'), (2, 'bob', 'old');",
+        )
+        .await
+        .unwrap();
+
+    let transaction = client.transaction().await.unwrap();
+    let rows = vec![(1, "anna"), (3, "carol"), (4, "dave")];
+    let counts = transaction
+        .bulk_loader("foo", &["id"])
+        .columns(&["id", "name"])
+        .load(
+            stream::iter(rows)
+                .map(|(id, name)| [Box::new(id) as Box<dyn ToSql + Sync>, Box::new(name)]),
+        )
+        .await
+        .unwrap();
+    assert_eq!(counts.inserted(), 2);
+    assert_eq!(counts.updated(), 1);
+    assert_eq!(counts.skipped(), 0);
+
+    let counts = transaction
+        .bulk_loader("foo", &["id"])
+        .on_conflict(ConflictAction::Nothing)
+        .load(stream::iter(vec![
+            [&2i32 as &(dyn ToSql + Sync), &"bill", &"x"],
+            [&5i32, &"eve", &"x"],
+        ]))
+        .await
+        .unwrap();
+    assert_eq!(counts.inserted(), 1);
+    assert_eq!(counts.updated(), 0);
+    assert_eq!(counts.skipped(), 1);
+
+    let rows = transaction
+        .query("SELECT id, name, note FROM foo ORDER BY id", &[])
+        .await
+        .unwrap();
+    let rows = rows
+        .iter()
+        .map(|r| (r.get(0), r.get(1), r.get(2)))
+        .collect::<Vec<(i32, &str, &str)>>();
+    assert_eq!(
+        rows,
+        [
+            (1, "anna", "old"),
+            (2, "bob", "old"),
+            (3, "carol", "new"),
+            (4, "dave", "new"),
+            (5, "eve", "x"),
+        ]
+    );
+
+    let err = transaction
+        .bulk_loader("foo", &[])
+        .load(stream::iter(vec![[
+            &1i32 as &(dyn ToSql + Sync),
+            &"x",
+            &"x",
+        ]]))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("key columns"), "{}", err);
+
+    let err = transaction
+        .bulk_loader("foo", &["id"])
+        .on_conflict(ConflictAction::Error)
+        .columns(&["id"])
+        .load(stream::iter(vec![[&1i32 as &(dyn ToSql + Sync)]]))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Some(&SqlState::UNIQUE_VIOLATION));
+}
+
+#[tokio::test]
+async fn bulk_load_generated_columns() {
+    let mut client = connect("user=postgres").await;
+
+    client
+        .batch_execute(
+            "CREATE TEMPORARY TABLE foo (
+                seq INT GENERATED ALWAYS AS IDENTITY,
+                id INT PRIMARY KEY,
+                name TEXT,
+                upper_name TEXT GENERATED ALWAYS AS (upper(name)) STORED
+            );
+            INSERT INTO foo (id, name) VALUES (1, 'alice');",
+        )
+        .await
+        .unwrap();
+
+    let transaction = client.transaction().await.unwrap();
+    let counts = transaction
+        .bulk_loader("foo", &["id"])
+        .load(stream::iter(vec![
+            [&1i32 as &(dyn ToSql + Sync), &"anna"],
+            [&2i32, &"bob"],
+        ]))
+        .await
+        .unwrap();
+    assert_eq!(counts.inserted(), 1);
+    assert_eq!(counts.updated(), 1);
+
+    let rows = transaction
+        .query("SELECT id, upper_name FROM foo ORDER BY id", &[])
+        .await
+        .unwrap();
+    let rows = rows
+        .iter()
+        .map(|r| (r.get(0), r.get(1)))
+        .collect::<Vec<(i32, &str)>>();
+    assert_eq!(rows, [(1, "ANNA"), (2, "BOB")]);
+}
+
+#[tokio::test]
+async fn column_metadata() {
+    let client = connect("user=postgres").await;
+
+    client
+        .batch_execute(
+            "CREATE TEMPORARY TABLE foo (
+                id INT,
+                name VARCHAR(20),
+                price NUMERIC(10, 2),
+                created TIMESTAMP(3),
+                elapsed INTERVAL SECOND(4),
+                flags BIT(8)
+            )",
+        )
+        .await
+        .unwrap();
+
+    let table_oid = client
+        .query_one("SELECT 'foo'::regclass::oid", &[])
+        .await
+        .unwrap()
+        .get::<_, u32>(0);
+
+    let stmt = client
+        .prepare("SELECT id, name, price, created, elapsed, flags, 1::INT8 AS one FROM foo")
+        .await
+        .unwrap();
+    let columns = stmt.columns();
+
+    assert_eq!(columns[0].table_oid(), Some(table_oid));
+    assert_eq!(columns[0].column_id(), Some(1));
+    assert_eq!(columns[0].type_size(), 4);
+    assert_eq!(columns[0].type_modifier(), -1);
+    assert_eq!(columns[0].format(), 0);
+    assert_eq!(columns[0].character_maximum_length(), None);
+
+    assert_eq!(columns[1].column_id(), Some(2));
+    assert_eq!(columns[1].type_size(), -1);
+    assert_eq!(columns[1].character_maximum_length(), Some(20));
+    assert_eq!(columns[2].numeric_precision_scale(), Some((10, 2)));
+    assert_eq!(columns[3].datetime_precision(), Some(3));
+    assert_eq!(columns[4].datetime_precision(), Some(4));
+    assert_eq!(columns[5].character_maximum_length(), Some(8));
+
+    assert_eq!(columns[6].table_oid(), None);
+    assert_eq!(columns[6].column_id(), None);
+    assert_eq!(columns[6].datetime_precision(), None);
+    assert_eq!(columns[6].numeric_precision_scale(), None);
+}
+
+#[tokio::test]
+async fn cursor() {
+    let mut client = connect("user=postgres").await;
+
+    let transaction = client.transaction().await.unwrap();
+    let rows = transaction
+        .cursor("SELECT generate_series(1, $1)", &[&10i32], 3)
+        .await
+        .unwrap()
+        .map_ok(|row| row.get::<_, i32>(0))
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    assert_eq!(rows, (1..=10).collect::<Vec<_>>());
+
+    let rows = transaction
+        .cursor("SELECT generate_series(1, 4)", &[], 2)
+        .await
+        .unwrap()
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    assert_eq!(rows.len(), 4);
+
+    let err = transaction
+        .cursor("SELECT 1 / (3 - generate_series(1, 5))", &[], 1)
+        .await
+        .unwrap()
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Some(&SqlState::DIVISION_BY_ZERO));
+}
+
+#[tokio::test]
+async fn cursor_with_hold() {
+    let client = connect("user=postgres").await;
+
+    let mut stream = client
+        .cursor_with_hold("SELECT generate_series(1, $1)", &[&5i32], 2)
+        .await
+        .unwrap();
+    let mut rows = vec![];
+    for _ in 0..3 {
+        rows.push(stream.try_next().await.unwrap().unwrap().get::<_, i32>(0));
+    }
+    assert_eq!(rows, [1, 2, 3]);
+
+    let count = client
+        .query_one("SELECT count(*) FROM pg_cursors WHERE is_holdable", &[])
+        .await
+        .unwrap();
+    assert_eq!(count.get::<_, i64>(0), 1);
+
+    drop(stream);
+    let count = client
+        .query_one("SELECT count(*) FROM pg_cursors WHERE is_holdable", &[])
+        .await
+        .unwrap();
+    assert_eq!(count.get::<_, i64>(0), 0);
+
+    let rows = client
+        .cursor_with_hold("SELECT generate_series(1, 5)", &[], 0)
+        .await
+        .unwrap()
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    assert_eq!(rows.len(), 5);
+}
+
 #[tokio::test]
 async fn query_portal() {
     let mut client = connect("user=postgres").await;
@@ -810,6 +1445,71 @@ async fn query_portal() {
     assert_eq!(r3.len(), 0);
 }
 
+#[tokio::test]
+async fn query_raw_with_formats() {
+    let client = connect("user=postgres").await;
+
+    let rows = client
+        .query_raw_with_formats(
+            "SELECT 1::INT4, 2::INT4, '(1,2)'::POINT, NULL::TEXT, '{\"a\\\"b\",NULL}'::TEXT[], 3::INT8",
+            &[] as &[i32],
+            |column| match column.type_() {
+                &Type::INT4 => Format::Binary,
+                _ => Format::Text,
+            },
+        )
+        .await
+        .unwrap()
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+
+    let row = &rows[0];
+    assert_eq!(row.format(0), Some(Format::Binary));
+    assert_eq!(row.get::<_, i32>(1), 2);
+    assert_eq!(row.format(2), Some(Format::Text));
+    assert_eq!(row.format(6), None);
+    assert_eq!(row.get::<_, Point>(2), Point::new(1., 2.));
+    assert_eq!(row.get_text::<_, Point>(2), Point::new(1., 2.));
+    assert_eq!(row.get::<_, Option<String>>(3), None);
+    assert_eq!(row.get_text::<_, Option<String>>(3), None);
+    assert_eq!(
+        row.get::<_, Vec<Option<String>>>(4),
+        vec![Some("a\"b".to_string()), None]
+    );
+    assert!(row.try_get::<_, Vec<&str>>(4).is_err());
+    assert_eq!(row.get::<_, i64>(5), 3);
+    assert!(row.try_get::<_, i32>(5).is_err());
+    assert!(row.try_get_text::<_, i32>(0).is_err());
+}
+
+#[tokio::test]
+async fn bind_raw_with_formats() {
+    let mut client = connect("user=postgres").await;
+
+    let transaction = client.transaction().await.unwrap();
+
+    let portal = transaction
+        .bind_raw_with_formats(
+            "SELECT i, i::TEXT::INT2VECTOR AS v FROM generate_series(1, 3) i",
+            &[] as &[i32],
+            |column| match column.name() {
+                "i" => Format::Binary,
+                _ => Format::Text,
+            },
+        )
+        .await
+        .unwrap();
+    let r1 = transaction.query_portal(&portal, 2).await.unwrap();
+    let r2 = transaction.query_portal(&portal, 2).await.unwrap();
+
+    assert_eq!(r1.len(), 2);
+    assert_eq!(r1[0].get::<_, i32>(0), 1);
+    assert_eq!(r1[1].get_text::<_, Vec<i16>>(1), vec![2]);
+    assert_eq!(r2.len(), 1);
+    assert_eq!(r2[0].get_text::<_, Vec<i16>>(1), vec![3]);
+}
+
 #[tokio::test]
 async fn require_channel_binding() {
     connect_raw("user=postgres channel_binding=require")
@@ -951,3 +1651,153 @@ async fn deferred_constraint() {
         .await
         .unwrap_err();
 }
+
+#[test]
+fn named_statement() {
+    let cases = [
+        ("SELECT :a, :b, :a", "SELECT $1, $2, $1"),
+        ("SELECT :a::INT, x::TEXT", "SELECT $1::INT, x::TEXT"),
+        ("SELECT ':a', E'\\':a', :b", "SELECT ':a', E'\\':a', $1"),
+        ("SELECT 'it''s :a', \":a\"", "SELECT 'it''s :a', \":a\""),
+        ("SELECT :a -- :b\n, :c", "SELECT $1 -- :b\n, $2"),
+        (
+            "SELECT /* :a /* :b */ :c */ :d",
+            "SELECT /* :a /* :b */ :c */ $1",
+        ),
+        (
+            "SELECT $$:a$$, $x$ :b $x$, :c",
+            "SELECT $$:a$$, $x$ :b $x$, $1",
+        ),
+        ("SELECT arr[1:2], :_x1", "SELECT arr[1:2], $1"),
+        ("SELECT 'unterminated :a", "SELECT 'unterminated :a"),
+    ];
+
+    for (query, expected) in cases {
+        assert_eq!(NamedStatement::new(query).query(), expected, "{}", query);
+    }
+}
+
+#[tokio::test]
+async fn query_named() {
+    let mut client = connect("user=postgres").await;
+
+    client
+        .batch_execute(
+            "
+                CREATE TEMPORARY TABLE foo (
+                    id INT,
+                    name TEXT
+                );
+                INSERT INTO foo (id, name) VALUES (1, 'alice'), (2, 'bob'), (3, 'carol');
+            ",
+        )
+        .await
+        .unwrap();
+
+    let rows = client
+        .query_named(
+            "SELECT name FROM foo WHERE id = :id OR name = :name ORDER BY id",
+            &[("name", &"carol"), ("id", &1i32), ("unused", &true)],
+        )
+        .await
+        .unwrap();
+    let names = rows.iter().map(|r| r.get(0)).collect::<Vec<&str>>();
+    assert_eq!(names, ["alice", "carol"]);
+
+    let row = client
+        .query_one_named("SELECT ':id', :id::INT", &[("id", &2i32)])
+        .await
+        .unwrap();
+    assert_eq!(row.get::<_, &str>(0), ":id");
+    assert_eq!(row.get::<_, i32>(1), 2);
+
+    let err = client
+        .query_opt_named("SELECT name FROM foo WHERE id = :id", &[])
+        .await
+        .unwrap_err();
+    assert_eq!(err.to_string(), "missing parameter `id`");
+
+    let transaction = client.transaction().await.unwrap();
+    let updated = GenericClient::execute_named(
+        &transaction,
+        "UPDATE foo SET name = :name WHERE id > :id",
+        &[("id", &1i32), ("name", &"dave")],
+    )
+    .await
+    .unwrap();
+    assert_eq!(updated, 2);
+    let row = transaction
+        .query_opt_named(
+            "SELECT count(*) FROM foo WHERE name = :name",
+            &[("name", &"dave")],
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(row.get::<_, i64>(0), 2);
+}
+
+#[tokio::test]
+async fn two_phase_commit() {
+    let mut client = connect("user=postgres").await;
+    let other = connect("user=postgres").await;
+
+    client
+        .batch_execute(
+            "DROP TABLE IF EXISTS two_phase_commit_test; CREATE TABLE two_phase_commit_test (i INT)",
+        )
+        .await
+        .unwrap();
+
+    let gid = "tokio-postgres 'two_phase_commit' 1";
+    let transaction = client.transaction().await.unwrap();
+    transaction
+        .execute("INSERT INTO two_phase_commit_test VALUES (1)", &[])
+        .await
+        .unwrap();
+    let xact = transaction.prepare_transaction(gid).await.unwrap();
+    assert_eq!(xact.gid(), gid);
+    assert_eq!(xact.owner(), "postgres");
+    assert_eq!(xact.database(), "postgres");
+
+    let prepared = other.list_prepared().await.unwrap();
+    assert!(prepared.contains(&xact));
+
+    async fn count(client: &Client) -> i64 {
+        client
+            .query_one("SELECT count(*) FROM two_phase_commit_test", &[])
+            .await
+            .unwrap()
+            .get(0)
+    }
+
+    assert_eq!(count(&client).await, 0);
+    other.commit_prepared(gid).await.unwrap();
+    assert_eq!(count(&client).await, 1);
+
+    let gid = "tokio-postgres 'two_phase_commit' 2";
+    let transaction = client.transaction().await.unwrap();
+    transaction
+        .execute("INSERT INTO two_phase_commit_test VALUES (2)", &[])
+        .await
+        .unwrap();
+    transaction.prepare_transaction(gid).await.unwrap();
+    client.rollback_prepared(gid).await.unwrap();
+    assert_eq!(count(&client).await, 1);
+    assert!(!client
+        .list_prepared()
+        .await
+        .unwrap()
+        .iter()
+        .any(|x| x.gid() == gid));
+
+    client
+        .commit_prepared("tokio-postgres 'two_phase_commit' missing")
+        .await
+        .unwrap_err();
+
+    client
+        .batch_execute("DROP TABLE two_phase_commit_test")
+        .await
+        .unwrap();
+}
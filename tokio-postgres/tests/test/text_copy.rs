@@ -0,0 +1,280 @@
+use crate::connect;
+use bytes::BytesMut;
+use futures_util::{pin_mut, TryStreamExt};
+use std::error::Error;
+use tokio_postgres::text_copy::{
+    CsvCopyInWriter, CsvCopyOutStream, CsvOptions, TextCopyInWriter, TextCopyOutStream, TextOptions,
+};
+use tokio_postgres::types::{to_sql_checked, FromSql, IsNull, Point, ToSql, Type};
+
+const VALUES: &[Option<&str>] = &[
+    Some("plain"),
+    Some("tab\there"),
+    Some("line\nbreak\r\n"),
+    Some("back\\slash"),
+    Some("\\N"),
+    Some("comma, \"quoted\""),
+    Some(""),
+    Some("\\."),
+    None,
+];
+
+#[tokio::test]
+async fn text_round_trip() {
+    let client = connect("user=postgres").await;
+
+    client
+        .batch_execute("CREATE TEMPORARY TABLE foo (id INT, bar TEXT)")
+        .await
+        .unwrap();
+
+    let sink = client
+        .copy_in("COPY foo (id, bar) FROM STDIN")
+        .await
+        .unwrap();
+    let writer = TextCopyInWriter::new(sink, &[Type::INT4, Type::TEXT], TextOptions::new());
+    pin_mut!(writer);
+    for (i, value) in VALUES.iter().enumerate() {
+        writer.as_mut().write(&[&(i as i32), value]).await.unwrap();
+    }
+    assert_eq!(writer.finish().await.unwrap(), VALUES.len() as u64);
+
+    let rows = client
+        .query("SELECT bar FROM foo ORDER BY id", &[])
+        .await
+        .unwrap();
+    let values = rows
+        .iter()
+        .map(|r| r.get::<_, Option<&str>>(0))
+        .collect::<Vec<_>>();
+    assert_eq!(values, VALUES);
+
+    let stream = client
+        .copy_out("COPY (SELECT id, bar FROM foo ORDER BY id) TO STDOUT")
+        .await
+        .unwrap();
+    let stream = TextCopyOutStream::new(stream, TextOptions::new());
+    let rows = stream.try_collect::<Vec<_>>().await.unwrap();
+    assert_eq!(rows.len(), VALUES.len());
+    for (i, (row, value)) in rows.iter().zip(VALUES).enumerate() {
+        assert_eq!(row.len(), 2);
+        assert_eq!(row.parse::<i32>(0).unwrap(), Some(i as i32));
+        assert_eq!(row.get(1), *value);
+    }
+}
+
+#[tokio::test]
+async fn csv_round_trip() {
+    let client = connect("user=postgres").await;
+
+    client
+        .batch_execute("CREATE TEMPORARY TABLE foo (id INT, bar TEXT)")
+        .await
+        .unwrap();
+
+    let options = CsvOptions::new().delimiter(b';').null("NULL").header(true);
+
+    let sink = client
+        .copy_in("COPY foo (id, bar) FROM STDIN (FORMAT csv, DELIMITER ';', NULL 'NULL', HEADER)")
+        .await
+        .unwrap();
+    let writer = CsvCopyInWriter::new(sink, &[Type::INT4, Type::TEXT], options.clone());
+    pin_mut!(writer);
+    writer.as_mut().write_header(&["id", "bar"]).await.unwrap();
+    for (i, value) in VALUES.iter().chain(&[Some("NULL")]).enumerate() {
+        writer
+            .as_mut()
+            .write_text(&[Some(&i.to_string()), *value])
+            .await
+            .unwrap();
+    }
+    writer.finish().await.unwrap();
+
+    let stream = client
+        .copy_out(
+            "COPY (SELECT id, bar FROM foo ORDER BY id) TO STDOUT \
+             (FORMAT csv, DELIMITER ';', NULL 'NULL', HEADER)",
+        )
+        .await
+        .unwrap();
+    let stream = CsvCopyOutStream::new(stream, options);
+    pin_mut!(stream);
+    let mut values = vec![];
+    while let Some(row) = stream.try_next().await.unwrap() {
+        values.push(row.get(1).map(str::to_string));
+    }
+    let header = stream.header().unwrap();
+    assert_eq!((header.get(0), header.get(1)), (Some("id"), Some("bar")));
+    let values = values.iter().map(|v| v.as_deref()).collect::<Vec<_>>();
+    assert_eq!(&values[..VALUES.len()], VALUES);
+    assert_eq!(values[VALUES.len()], Some("NULL"));
+}
+
+#[tokio::test]
+async fn write_types() {
+    let client = connect("user=postgres").await;
+
+    client
+        .batch_execute("CREATE TEMPORARY TABLE foo (a BOOL, b INT8, c FLOAT8, d BYTEA, e INT2)")
+        .await
+        .unwrap();
+
+    let sink = client
+        .copy_in("COPY foo FROM STDIN (FORMAT csv)")
+        .await
+        .unwrap();
+    let writer = CsvCopyInWriter::new(
+        sink,
+        &[
+            Type::BOOL,
+            Type::INT8,
+            Type::FLOAT8,
+            Type::BYTEA,
+            Type::INT2,
+        ],
+        CsvOptions::new(),
+    );
+    pin_mut!(writer);
+    writer
+        .as_mut()
+        .write(&[&true, &-12i64, &1.5f64, &&b"\x00\xff"[..], &None::<i16>])
+        .await
+        .unwrap();
+    writer.finish().await.unwrap();
+
+    let row = client
+        .query_one("SELECT a, b, c, d, e FROM foo", &[])
+        .await
+        .unwrap();
+    assert!(row.get::<_, bool>(0));
+    assert_eq!(row.get::<_, i64>(1), -12);
+    assert_eq!(row.get::<_, f64>(2), 1.5);
+    assert_eq!(row.get::<_, &[u8]>(3), b"\x00\xff");
+    assert_eq!(row.get::<_, Option<i16>>(4), None);
+}
+
+#[derive(Debug)]
+struct Raw<'a>(&'a [u8]);
+
+impl<'a> FromSql<'a> for Raw<'a> {
+    fn from_sql(_: &Type, raw: &'a [u8]) -> Result<Raw<'a>, Box<dyn Error + Sync + Send>> {
+        Ok(Raw(raw))
+    }
+
+    fn accepts(_: &Type) -> bool {
+        true
+    }
+}
+
+impl ToSql for Raw<'_> {
+    fn to_sql(&self, _: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        out.extend_from_slice(self.0);
+        Ok(IsNull::No)
+    }
+
+    fn accepts(_: &Type) -> bool {
+        true
+    }
+
+    to_sql_checked!();
+}
+
+#[tokio::test]
+async fn write_binary_types() {
+    let client = connect("user=postgres").await;
+
+    client
+        .batch_execute(
+            "CREATE TEMPORARY TABLE foo AS SELECT
+                '-12345.678900'::NUMERIC AS a,
+                '0.00000001'::NUMERIC AS b,
+                '0044-03-15 BC'::DATE AS c,
+                '13:14:15.5'::TIME AS d,
+                '13:14:15-05:30'::TIMETZ AS e,
+                '2023-04-05 06:07:08.000009'::TIMESTAMP AS f,
+                '2023-04-05 06:07:08+02'::TIMESTAMPTZ AS g,
+                '1 year 2 mons -3 days 04:05:06.789'::INTERVAL AS h,
+                '192.168.0.1/24'::INET AS i,
+                '2001:db8::/32'::CIDR AS j,
+                '08:00:2b:01:02:03'::MACADDR AS k,
+                'a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11'::UUID AS l,
+                ARRAY['a b', 'NULL', '', NULL, 'q\"\\', '{x,y}'] AS m,
+                '[0:1][1:2]={{1,2},{3,4}}'::INT4[] AS n,
+                '{}'::INT4[] AS o,
+                ARRAY['infinity', '2000-01-01 00:00:00.25']::TIMESTAMP[] AS p",
+        )
+        .await
+        .unwrap();
+
+    let statement = client.prepare("SELECT * FROM foo").await.unwrap();
+    let types = statement
+        .columns()
+        .iter()
+        .map(|c| c.type_().clone())
+        .collect::<Vec<_>>();
+    let row = client.query_one(&statement, &[]).await.unwrap();
+    let values = (0..row.len())
+        .map(|i| row.get::<_, Raw<'_>>(i))
+        .collect::<Vec<_>>();
+    let values = values
+        .iter()
+        .map(|v| v as &(dyn ToSql + Sync))
+        .collect::<Vec<_>>();
+
+    let sink = client.copy_in("COPY foo FROM STDIN").await.unwrap();
+    let writer = TextCopyInWriter::new(sink, &types, TextOptions::new());
+    pin_mut!(writer);
+    writer.as_mut().write(&values).await.unwrap();
+    writer.finish().await.unwrap();
+
+    let sink = client
+        .copy_in("COPY foo FROM STDIN (FORMAT csv)")
+        .await
+        .unwrap();
+    let writer = CsvCopyInWriter::new(sink, &types, CsvOptions::new());
+    pin_mut!(writer);
+    writer.as_mut().write(&values).await.unwrap();
+    writer.finish().await.unwrap();
+
+    let row = client
+        .query_one("SELECT COUNT(*), COUNT(DISTINCT foo.*) FROM foo", &[])
+        .await
+        .unwrap();
+    assert_eq!(row.get::<_, i64>(0), 3);
+    assert_eq!(row.get::<_, i64>(1), 1);
+}
+
+#[tokio::test]
+async fn errors() {
+    let client = connect("user=postgres").await;
+
+    client
+        .batch_execute("CREATE TEMPORARY TABLE foo (a POINT)")
+        .await
+        .unwrap();
+    {
+        let sink = client.copy_in("COPY foo FROM STDIN").await.unwrap();
+        let writer = TextCopyInWriter::new(sink, &[Type::POINT], TextOptions::new());
+        pin_mut!(writer);
+        let err = writer
+            .as_mut()
+            .write(&[&Point::new(1., 2.)])
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("error serializing parameter 0"));
+    }
+
+    let stream = client
+        .copy_out("COPY (VALUES (1, 'a'), (2, 'b\"c')) TO STDOUT (FORMAT csv, QUOTE '''')")
+        .await
+        .unwrap();
+    let stream = CsvCopyOutStream::new(stream, CsvOptions::new());
+    pin_mut!(stream);
+    let row = stream.try_next().await.unwrap().unwrap();
+    let err = row.parse::<i32>(1).unwrap_err();
+    assert!(err.to_string().contains("error deserializing column 1"));
+    let err = stream.try_next().await.unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("error parsing response from server"));
+}
@@ -1,4 +1,4 @@
-use geo_types_06::{Coordinate, LineString, Point, Rect};
+use geo_types_06::{Coordinate, Line, LineString, Point, Polygon, Rect};
 
 use crate::types::test_type;
 
@@ -58,3 +58,41 @@ async fn test_path_params() {
     )
     .await;
 }
+
+#[tokio::test]
+async fn test_lseg_params() {
+    test_type(
+        "LSEG",
+        &[
+            (
+                Some(Line::new(
+                    Coordinate { x: 0., y: 0. },
+                    Coordinate { x: -3.2, y: 1.618 },
+                )),
+                "lseg '[(0, 0), (-3.2, 1.618)]'",
+            ),
+            (None, "NULL"),
+        ],
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_polygon_params() {
+    let points = vec![
+        Coordinate { x: 0., y: 0. },
+        Coordinate { x: 1., y: 0. },
+        Coordinate { x: 1., y: 1. },
+    ];
+    test_type(
+        "POLYGON",
+        &[
+            (
+                Some(Polygon::new(LineString(points), vec![])),
+                "polygon '((0, 0), (1, 0), (1, 1))'",
+            ),
+            (None, "NULL"),
+        ],
+    )
+    .await;
+}
@@ -1,5 +1,5 @@
 #[cfg(feature = "with-geo-types-0_7")]
-use geo_types_07::{Coord, LineString, Point, Rect};
+use geo_types_07::{Coord, Line, LineString, Point, Polygon, Rect};
 
 use crate::types::test_type;
 
@@ -59,3 +59,41 @@ async fn test_path_params() {
     )
     .await;
 }
+
+#[tokio::test]
+async fn test_lseg_params() {
+    test_type(
+        "LSEG",
+        &[
+            (
+                Some(Line::new(
+                    Coord { x: 0., y: 0. },
+                    Coord { x: -3.2, y: 1.618 },
+                )),
+                "lseg '[(0, 0), (-3.2, 1.618)]'",
+            ),
+            (None, "NULL"),
+        ],
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_polygon_params() {
+    let points = vec![
+        Coord { x: 0., y: 0. },
+        Coord { x: 1., y: 0. },
+        Coord { x: 1., y: 1. },
+    ];
+    test_type(
+        "POLYGON",
+        &[
+            (
+                Some(Polygon::new(LineString(points), vec![])),
+                "polygon '((0, 0), (1, 0), (1, 1))'",
+            ),
+            (None, "NULL"),
+        ],
+    )
+    .await;
+}
@@ -0,0 +1,226 @@
+use jiff_01::{
+    civil::{Date as JiffDate, DateTime, Time},
+    tz::TimeZone,
+    Span, Timestamp as JiffTimestamp, Zoned,
+};
+use std::fmt;
+use tokio_postgres::types::{Date, FromSqlOwned, Timestamp};
+use tokio_postgres::Client;
+
+use crate::connect;
+use crate::types::test_type;
+
+#[tokio::test]
+async fn test_datetime_params() {
+    fn make_check(time: &str) -> (Option<DateTime>, &str) {
+        (Some(time.trim_matches('\'').parse().unwrap()), time)
+    }
+    test_type(
+        "TIMESTAMP",
+        &[
+            make_check("'1970-01-01 00:00:00.010000000'"),
+            make_check("'1965-09-25 11:19:33.100314000'"),
+            make_check("'2010-02-09 23:11:45.120200000'"),
+            (None, "NULL"),
+        ],
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_with_special_datetime_params() {
+    fn make_check(time: &str) -> (Timestamp<DateTime>, &str) {
+        (
+            Timestamp::Value(time.trim_matches('\'').parse().unwrap()),
+            time,
+        )
+    }
+    test_type(
+        "TIMESTAMP",
+        &[
+            make_check("'1970-01-01 00:00:00.010000000'"),
+            make_check("'1965-09-25 11:19:33.100314000'"),
+            make_check("'2010-02-09 23:11:45.120200000'"),
+            (Timestamp::PosInfinity, "'infinity'"),
+            (Timestamp::NegInfinity, "'-infinity'"),
+        ],
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_timestamp_params() {
+    fn make_check(time: &str) -> (Option<JiffTimestamp>, &str) {
+        (Some(time.trim_matches('\'').parse().unwrap()), time)
+    }
+    test_type(
+        "TIMESTAMP WITH TIME ZONE",
+        &[
+            make_check("'1970-01-01 00:00:00.010000000Z'"),
+            make_check("'1965-09-25 11:19:33.100314000Z'"),
+            make_check("'2010-02-09 23:11:45.120200000Z'"),
+            (None, "NULL"),
+        ],
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_with_special_timestamp_params() {
+    fn make_check(time: &str) -> (Timestamp<JiffTimestamp>, &str) {
+        (
+            Timestamp::Value(time.trim_matches('\'').parse().unwrap()),
+            time,
+        )
+    }
+    test_type(
+        "TIMESTAMP WITH TIME ZONE",
+        &[
+            make_check("'1970-01-01 00:00:00.010000000Z'"),
+            make_check("'1965-09-25 11:19:33.100314000Z'"),
+            make_check("'2010-02-09 23:11:45.120200000Z'"),
+            (Timestamp::PosInfinity, "'infinity'"),
+            (Timestamp::NegInfinity, "'-infinity'"),
+        ],
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_zoned_params() {
+    fn make_check(time: &str) -> (Option<Zoned>, &str) {
+        let timestamp: JiffTimestamp = time.trim_matches('\'').parse().unwrap();
+        (Some(timestamp.to_zoned(TimeZone::UTC)), time)
+    }
+    test_type(
+        "TIMESTAMP WITH TIME ZONE",
+        &[
+            make_check("'1970-01-01 00:00:00.010000000Z'"),
+            make_check("'1965-09-25 11:19:33.100314000Z'"),
+            make_check("'2010-02-09 23:11:45.120200000Z'"),
+            (None, "NULL"),
+        ],
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_date_params() {
+    fn make_check(date: &str) -> (Option<JiffDate>, &str) {
+        (Some(date.trim_matches('\'').parse().unwrap()), date)
+    }
+    test_type(
+        "DATE",
+        &[
+            make_check("'1970-01-01'"),
+            make_check("'1965-09-25'"),
+            make_check("'2010-02-09'"),
+            (None, "NULL"),
+        ],
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_with_special_date_params() {
+    fn make_check(date: &str) -> (Date<JiffDate>, &str) {
+        (Date::Value(date.trim_matches('\'').parse().unwrap()), date)
+    }
+    test_type(
+        "DATE",
+        &[
+            make_check("'1970-01-01'"),
+            make_check("'1965-09-25'"),
+            make_check("'2010-02-09'"),
+            (Date::PosInfinity, "'infinity'"),
+            (Date::NegInfinity, "'-infinity'"),
+        ],
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_time_params() {
+    fn make_check(time: &str) -> (Option<Time>, &str) {
+        (Some(time.trim_matches('\'').parse().unwrap()), time)
+    }
+    test_type(
+        "TIME",
+        &[
+            make_check("'00:00:00.010000000'"),
+            make_check("'11:19:33.100314000'"),
+            make_check("'23:11:45.120200000'"),
+            (None, "NULL"),
+        ],
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_span_params() {
+    test_type(
+        "INTERVAL",
+        &[
+            (
+                Some(
+                    Span::new()
+                        .years(1)
+                        .months(2)
+                        .days(3)
+                        .hours(4)
+                        .minutes(5)
+                        .seconds(6)
+                        .microseconds(789_000),
+                ),
+                "'1 year 2 mons 3 days 04:05:06.789'",
+            ),
+            (Some(Span::new().days(-1).hours(-2)), "'-1 days -02:00:00'"),
+            (Some(Span::new()), "'0'"),
+            (None, "NULL"),
+        ],
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_out_of_range_params() {
+    async fn assert_fails<T>(client: &mut Client, val: &str, sql_type: &str, message: &str)
+    where
+        T: FromSqlOwned + fmt::Debug,
+    {
+        let err = client
+            .query_one(&*format!("SELECT {}::{}", val, sql_type), &[])
+            .await
+            .unwrap()
+            .try_get::<_, T>(0)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("error deserializing column 0: {}", message)
+        );
+    }
+
+    let mut client = connect("user=postgres").await;
+    let too_large = "value too large to decode";
+
+    assert_fails::<JiffTimestamp>(&mut client, "'-infinity'", "timestamptz", too_large).await;
+    assert_fails::<JiffTimestamp>(&mut client, "'infinity'", "timestamptz", too_large).await;
+
+    assert_fails::<DateTime>(&mut client, "'-infinity'", "timestamp", too_large).await;
+    assert_fails::<DateTime>(&mut client, "'infinity'", "timestamp", too_large).await;
+    assert_fails::<DateTime>(&mut client, "'10000-01-01'", "timestamp", too_large).await;
+
+    assert_fails::<JiffDate>(&mut client, "'-infinity'", "date", too_large).await;
+    assert_fails::<JiffDate>(&mut client, "'infinity'", "date", too_large).await;
+    assert_fails::<JiffDate>(&mut client, "'10000-01-01'", "date", too_large).await;
+
+    assert_fails::<Time>(&mut client, "'24:00:00'", "time", too_large).await;
+
+    assert_fails::<Span>(
+        &mut client,
+        "'1 day -1 hour'",
+        "interval",
+        "cannot decode an interval with mixed signs",
+    )
+    .await;
+}
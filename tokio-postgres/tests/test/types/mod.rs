@@ -8,7 +8,12 @@ use std::net::IpAddr;
 use std::result;
 use std::str::FromStr;
 use std::time::{Duration, UNIX_EPOCH};
-use tokio_postgres::types::{FromSql, FromSqlOwned, IsNull, Kind, PgLsn, ToSql, Type, WrongType};
+use tokio_postgres::types::{
+    ArrayDimension, Circle, FromSql, FromSqlOwned, FromSqlTextOwned, IsNull, Kind, Line,
+    LineSegment, PgArray, PgLsn, Point, Polygon, ToSql, TsLexeme, TsPosition, TsQuery,
+    TsQueryOperand, TsVector, TsWeight, Type, WrongType,
+};
+use tokio_postgres::SimpleQueryMessage;
 
 use crate::connect;
 use bytes::BytesMut;
@@ -25,6 +30,14 @@ mod eui48_1;
 mod geo_types_06;
 #[cfg(feature = "with-geo-types-0_7")]
 mod geo_types_07;
+#[cfg(feature = "with-jiff-0_1")]
+mod jiff_01;
+#[cfg(feature = "with-ndarray-0_16")]
+mod ndarray_016;
+#[cfg(feature = "pgvector")]
+mod pgvector;
+#[cfg(feature = "postgis")]
+mod postgis;
 #[cfg(feature = "with-serde_json-1")]
 mod serde_json_1;
 #[cfg(feature = "with-smol_str-01")]
@@ -40,7 +53,7 @@ mod uuid_1;
 
 async fn test_type<T, S>(sql_type: &str, checks: &[(T, S)])
 where
-    T: PartialEq + for<'a> FromSqlOwned + ToSql + Sync,
+    T: PartialEq + for<'a> FromSqlOwned + FromSqlTextOwned + ToSql + Sync,
     S: fmt::Display,
 {
     let client = connect("user=postgres").await;
@@ -53,6 +66,16 @@ where
         let result = rows[0].get(0);
         assert_eq!(val, &result);
 
+        let messages = client
+            .simple_query(&format!("SELECT {}::{}", repr, sql_type))
+            .await
+            .unwrap();
+        let result = match &messages[0] {
+            SimpleQueryMessage::Row(row) => row.try_get_typed::<_, T>(0).unwrap(),
+            _ => panic!("unexpected message"),
+        };
+        assert_eq!(val, &result);
+
         let rows = client
             .query(&*format!("SELECT $1::{}", sql_type), &[&val])
             .await
@@ -156,6 +179,82 @@ async fn test_lsn_params() {
     .await
 }
 
+#[tokio::test]
+async fn test_point_params() {
+    test_type(
+        "POINT",
+        &[
+            (Some(Point::new(-3.2, 1.618)), "POINT(-3.2, 1.618)"),
+            (None, "NULL"),
+        ],
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_line_params() {
+    test_type(
+        "LINE",
+        &[
+            (Some(Line::new(1.0, -1.0, 0.5)), "line '{1, -1, 0.5}'"),
+            (None, "NULL"),
+        ],
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_lseg_params() {
+    test_type(
+        "LSEG",
+        &[
+            (
+                Some(LineSegment::new(
+                    Point::new(0.0, 0.0),
+                    Point::new(-3.2, 1.618),
+                )),
+                "lseg '[(0, 0), (-3.2, 1.618)]'",
+            ),
+            (None, "NULL"),
+        ],
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_polygon_params() {
+    test_type(
+        "POLYGON",
+        &[
+            (
+                Some(Polygon::new(vec![
+                    Point::new(0.0, 0.0),
+                    Point::new(1.0, 0.0),
+                    Point::new(1.0, 1.0),
+                ])),
+                "polygon '((0, 0), (1, 0), (1, 1))'",
+            ),
+            (None, "NULL"),
+        ],
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_circle_params() {
+    test_type(
+        "CIRCLE",
+        &[
+            (
+                Some(Circle::new(Point::new(1.0, 2.0), 3.5)),
+                "circle '<(1, 2), 3.5>'",
+            ),
+            (None, "NULL"),
+        ],
+    )
+    .await
+}
+
 #[tokio::test]
 async fn test_f32_params() {
     test_type(
@@ -381,6 +480,148 @@ async fn test_array_array_params() {
     .await;
 }
 
+#[tokio::test]
+async fn test_pg_array_params() {
+    let matrix = PgArray::from_nested(vec![vec![1.5f64, 2.0], vec![-3.0, 4.25]]).unwrap();
+    test_type(
+        "FLOAT8[][]",
+        &[
+            (Some(matrix), "'{{1.5,2},{-3,4.25}}'"),
+            (Some(PgArray::default()), "'{}'"),
+            (None, "NULL"),
+        ],
+    )
+    .await;
+
+    let dimensions = vec![
+        ArrayDimension {
+            len: 2,
+            lower_bound: 0,
+        },
+        ArrayDimension {
+            len: 1,
+            lower_bound: -1,
+        },
+    ];
+    let array = PgArray::new(dimensions, vec![Some(1i32), None]).unwrap();
+    assert_eq!(array.get(&[1, -1]), Some(&None));
+    assert_eq!(array.get(&[1, 0]), None);
+    test_type("INT4[]", &[(Some(array), "'[0:1][-1:-1]={{1},{NULL}}'")]).await;
+}
+
+#[tokio::test]
+async fn pg_array_nested() {
+    let client = connect("user=postgres").await;
+
+    let rows = client
+        .query("SELECT '{{1,2,3},{4,5,6}}'::INT4[]", &[])
+        .await
+        .unwrap();
+    let array = rows[0].get::<_, PgArray<i32>>(0);
+    assert_eq!(
+        array.into_nested().unwrap(),
+        vec![vec![1, 2, 3], vec![4, 5, 6]]
+    );
+
+    let err = PgArray::from_nested(vec![vec![1], vec![2, 3]]).unwrap_err();
+    assert_eq!(err.to_string(), "array rows have different lengths");
+
+    let err = client
+        .query_one("SELECT '{{1,2},{3,4}}'::INT4[]", &[])
+        .await
+        .unwrap()
+        .try_get::<_, Vec<i32>>(0)
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("array contains too many dimensions"));
+}
+
+#[tokio::test]
+async fn test_tsvector_params() {
+    let vector = TsVector::new(vec![
+        TsLexeme::new(
+            "a",
+            vec![
+                TsPosition {
+                    position: 1,
+                    weight: TsWeight::A,
+                },
+                TsPosition::new(3),
+            ],
+        ),
+        TsLexeme::new("b'c", vec![]),
+        TsLexeme::new(
+            "d\\e",
+            vec![TsPosition {
+                position: 2,
+                weight: TsWeight::C,
+            }],
+        ),
+    ]);
+    assert_eq!(vector.to_string(), r"'a':1A,3 'b''c' 'd\\e':2C");
+
+    test_type(
+        "TSVECTOR",
+        &[
+            (Some(vector), r"E'a:1A,3 b\'c d\\\\e:2C'"),
+            (Some(TsVector::default()), "''"),
+            (None, "NULL"),
+        ],
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_tsquery_params() {
+    let operand = |lexeme: &str| Box::new(TsQuery::Operand(TsQueryOperand::new(lexeme)));
+    let query = TsQuery::And(
+        operand("a"),
+        Box::new(TsQuery::Phrase(
+            Box::new(TsQuery::Not(Box::new(TsQuery::Operand(TsQueryOperand {
+                lexeme: "b".to_string(),
+                weights: vec![TsWeight::A, TsWeight::B],
+                prefix: true,
+            })))),
+            operand("c"),
+            2,
+        )),
+    );
+    assert_eq!(query.to_string(), "'a' & !'b':*AB <2> 'c'");
+
+    test_type(
+        "TSQUERY",
+        &[(Some(query), "'a & !b:*AB <2> c'"), (None, "NULL")],
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn tsquery_display() {
+    let client = connect("user=postgres").await;
+
+    let row = client.query_one("SELECT ''::TSQUERY", &[]).await.unwrap();
+    assert_eq!(row.get::<_, TsQuery>(0), TsQuery::Empty);
+    assert_eq!(TsQuery::Empty.to_string(), "");
+
+    for query in [
+        "( a | b ) & c",
+        "a <-> ( b <-> c )",
+        "!( a & b )",
+        "a | !b <-> c:D",
+        "( a <-> b ) <3> c",
+    ] {
+        let row = client
+            .query_one(
+                "SELECT $1::TEXT::TSQUERY, $1::TEXT::TSQUERY::TEXT",
+                &[&query],
+            )
+            .await
+            .unwrap();
+        assert_eq!(row.get::<_, TsQuery>(0).to_string(), row.get::<_, &str>(1));
+    }
+}
+
 #[allow(clippy::eq_op)]
 async fn test_nan_param<T>(sql_type: &str)
 where
@@ -576,6 +817,56 @@ async fn composite() {
     }
 }
 
+#[tokio::test]
+async fn nested_composite() {
+    let client = connect("user=postgres").await;
+
+    client
+        .batch_execute(
+            "CREATE TYPE pg_temp.nested_mood AS ENUM ('sad', 'happy');
+            CREATE DOMAIN pg_temp.positive AS INT4 CHECK (VALUE > 0);
+            CREATE TYPE pg_temp.nested_inner AS (
+                moods nested_mood[],
+                count positive,
+                span INT4RANGE
+            );
+            CREATE TYPE pg_temp.nested_outer AS (
+                inner_ nested_inner,
+                label TEXT
+            )",
+        )
+        .await
+        .unwrap();
+
+    let stmt = client
+        .prepare("SELECT $1::nested_outer, $2::positive")
+        .await
+        .unwrap();
+    let fields = match stmt.params()[0].kind() {
+        Kind::Composite(fields) => fields,
+        t => panic!("bad type {:?}", t),
+    };
+    assert_eq!(fields[0].name(), "inner_");
+    assert_eq!(fields[1].type_(), &Type::TEXT);
+
+    let inner = match fields[0].type_().kind() {
+        Kind::Composite(fields) => fields,
+        t => panic!("bad type {:?}", t),
+    };
+    match inner[0].type_().kind() {
+        Kind::Array(member) => assert_eq!(
+            member.kind(),
+            &Kind::Enum(vec!["sad".to_string(), "happy".to_string()])
+        ),
+        t => panic!("bad type {:?}", t),
+    }
+    assert_eq!(inner[1].type_().kind(), &Kind::Domain(Type::INT4));
+    assert_eq!(inner[2].type_(), &Type::INT4_RANGE);
+
+    assert_eq!(stmt.params()[1], *inner[1].type_());
+    assert_eq!(stmt.columns()[0].type_(), &stmt.params()[0]);
+}
+
 #[tokio::test]
 async fn enum_() {
     let client = connect("user=postgres").await;
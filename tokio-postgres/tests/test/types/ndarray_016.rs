@@ -0,0 +1,40 @@
+use ndarray_016::{arr2, ArrayD};
+use std::convert::TryFrom;
+use tokio_postgres::types::PgArray;
+
+use crate::connect;
+use crate::types::test_type;
+
+#[tokio::test]
+async fn test_ndarray_params() {
+    test_type(
+        "FLOAT8[][]",
+        &[
+            (
+                Some(arr2(&[[1.5f64, 2.0, 0.0], [-3.0, 4.25, 1.0]]).into_dyn()),
+                "'{{1.5,2,0},{-3,4.25,1}}'",
+            ),
+            (
+                Some(ArrayD::from_shape_vec(vec![0], vec![]).unwrap()),
+                "'{}'",
+            ),
+            (None, "NULL"),
+        ],
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_ndarray_zero_dimensional() {
+    let client = connect("user=postgres").await;
+
+    let array = ArrayD::from_elem(vec![], 1.5f64);
+    let err = client
+        .query_one("SELECT $1::FLOAT8[]", &[&array])
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("error serializing parameter 0"));
+
+    let err = PgArray::try_from(array).unwrap_err();
+    assert_eq!(err.to_string(), "zero-dimensional arrays are not supported");
+}
@@ -0,0 +1,83 @@
+use half_2::f16;
+use tokio_postgres::types::{FromSqlText, HalfVector, SparseVector, Type, Vector};
+
+use crate::connect;
+use crate::types::test_type;
+
+#[tokio::test]
+async fn test_vector_params() {
+    test_type(
+        "VECTOR",
+        &[
+            (Some(Vector::from(vec![1.0, -2.5, 3.0])), "'[1,-2.5,3]'"),
+            (None, "NULL"),
+        ],
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_halfvec_params() {
+    test_type(
+        "HALFVEC",
+        &[
+            (
+                Some(HalfVector::from(vec![
+                    f16::from_f32(1.0),
+                    f16::from_f32(-2.5),
+                ])),
+                "'[1,-2.5]'",
+            ),
+            (None, "NULL"),
+        ],
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_sparsevec_params() {
+    test_type(
+        "SPARSEVEC",
+        &[
+            (
+                Some(SparseVector::from_dense(&[0.0, 1.5, 0.0, -2.0, 0.0])),
+                "'{2:1.5,4:-2}/5'",
+            ),
+            (None, "NULL"),
+        ],
+    )
+    .await;
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds")]
+fn sparsevec_index_out_of_bounds() {
+    SparseVector::new(2, vec![0, 2], vec![1.0, 2.0]);
+}
+
+#[test]
+fn sparsevec_text_index_out_of_bounds() {
+    let err = SparseVector::from_sql_text(&Type::TEXT, "{3:1}/2").unwrap_err();
+    assert_eq!(err.to_string(), "sparsevec index out of bounds");
+}
+
+#[tokio::test]
+async fn vector_array() {
+    let client = connect("user=postgres").await;
+
+    let vectors = vec![Vector::from(&[1.0, 2.0][..]), Vector::from(vec![3.0, 4.0])];
+    let row = client
+        .query_one("SELECT $1::VECTOR[]", &[&vectors])
+        .await
+        .unwrap();
+    assert_eq!(row.get::<_, Vec<Vector>>(0), vectors);
+
+    let row = client
+        .query_one(
+            "SELECT '[1,2,3]'::VECTOR <-> $1",
+            &[&Vector::from(vec![1.0, 2.0, 5.0])],
+        )
+        .await
+        .unwrap();
+    assert_eq!(row.get::<_, f64>(0), 2.0);
+}
@@ -0,0 +1,109 @@
+use bytes::BytesMut;
+use geo_types_07::{
+    line_string, point, polygon, Geometry, GeometryCollection, MultiLineString, MultiPoint, Point,
+};
+use tokio_postgres::types::{Ewkb, FromSql, Kind, ToSql, Type};
+
+fn geometry_type(name: &str) -> Type {
+    Type::new(name.to_string(), 0, Kind::Simple, "public".to_string())
+}
+
+fn hex(s: &str) -> Vec<u8> {
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+fn round_trip(ewkb: &Ewkb) -> Ewkb {
+    let ty = geometry_type("geometry");
+    let mut buf = BytesMut::new();
+    ewkb.to_sql(&ty, &mut buf).unwrap();
+    Ewkb::from_sql(&ty, &buf).unwrap()
+}
+
+#[test]
+fn accepts() {
+    assert!(<Ewkb as FromSql>::accepts(&geometry_type("geometry")));
+    assert!(<Geometry<f64> as ToSql>::accepts(&geometry_type(
+        "geography"
+    )));
+    assert!(!<Ewkb as ToSql>::accepts(&Type::POINT));
+}
+
+#[test]
+fn point_with_srid() {
+    let ty = geometry_type("geometry");
+    // SRID=4326;POINT(1 2)
+    let raw = hex("0101000020E6100000000000000000F03F0000000000000040");
+
+    let ewkb = Ewkb::from_sql(&ty, &raw).unwrap();
+    assert_eq!(ewkb, Ewkb::new(Point::new(1., 2.), Some(4326)));
+
+    let mut buf = BytesMut::new();
+    ewkb.to_sql(&ty, &mut buf).unwrap();
+    assert_eq!(&buf[..], &raw[..]);
+}
+
+#[test]
+fn big_endian_iso_z() {
+    let ty = geometry_type("geometry");
+    // POINT Z (1 2 3), big endian ISO WKB
+    let raw = hex("00000003E93FF000000000000040000000000000004008000000000000");
+
+    let ewkb = Ewkb::from_sql(&ty, &raw).unwrap();
+    assert_eq!(
+        ewkb,
+        Ewkb {
+            geometry: Point::new(1., 2.).into(),
+            srid: None,
+            z: Some(vec![3.]),
+            m: None,
+        }
+    );
+    assert_eq!(round_trip(&ewkb), ewkb);
+}
+
+#[test]
+fn collection_zm() {
+    let collection = GeometryCollection(vec![
+        polygon![
+            exterior: [(x: 0., y: 0.), (x: 4., y: 0.), (x: 4., y: 4.), (x: 0., y: 0.)],
+            interiors: [[(x: 1., y: 1.), (x: 2., y: 1.), (x: 2., y: 2.), (x: 1., y: 1.)]],
+        ]
+        .into(),
+        MultiPoint(vec![point!(x: 5., y: 5.), point!(x: 6., y: 6.)]).into(),
+        MultiLineString(vec![line_string![(x: 7., y: 7.), (x: 8., y: 8.)]]).into(),
+    ]);
+    let ewkb = Ewkb {
+        geometry: Geometry::GeometryCollection(collection),
+        srid: Some(3857),
+        z: Some((0..12).map(f64::from).collect()),
+        m: Some((0..12).map(|i| f64::from(i) * 10.).collect()),
+    };
+    assert_eq!(round_trip(&ewkb), ewkb);
+
+    let geometry = Geometry::<f64>::from_sql(&geometry_type("geography"), &{
+        let mut buf = BytesMut::new();
+        ewkb.to_sql(&geometry_type("geography"), &mut buf).unwrap();
+        buf
+    })
+    .unwrap();
+    assert_eq!(geometry, ewkb.geometry);
+}
+
+#[test]
+fn mismatched_z() {
+    let ewkb = Ewkb {
+        geometry: line_string![(x: 0., y: 0.), (x: 1., y: 1.)].into(),
+        srid: None,
+        z: Some(vec![0.]),
+        m: None,
+    };
+    let mut buf = BytesMut::new();
+    let err = ewkb
+        .to_sql(&geometry_type("geometry"), &mut buf)
+        .err()
+        .unwrap();
+    assert_eq!(err.to_string(), "fewer Z values than coordinates");
+}